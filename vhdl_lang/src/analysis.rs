@@ -8,15 +8,20 @@
 mod analyze;
 mod assignment;
 mod association;
+mod compile_order;
 mod concurrent;
 mod declarative;
+mod dependencies;
+mod dependency_graph;
 mod design_unit;
 mod expression;
+mod group;
 mod literals;
 mod lock;
 mod names;
 mod overloaded;
 mod package_instance;
+mod psl;
 mod range;
 mod root;
 mod scope;
@@ -25,11 +30,15 @@ mod sequential;
 mod standard;
 mod static_expression;
 mod subprogram;
+mod suggestions;
 mod target;
 mod types;
+mod view;
 
 #[cfg(test)]
 pub(crate) mod tests;
 pub(crate) use root::{Library, LockedUnit};
 
+pub use self::compile_order::{CompileOrderFile, CompileOrderScope};
+pub use self::dependency_graph::{DependencyEdge, DependencyGraph, DependencyKind, DependencyNode};
 pub use self::root::{DesignRoot, EntHierarchy};
@@ -6,9 +6,11 @@
 
 use super::root::*;
 pub(crate) use super::scope::Scope;
+use super::suggestions::closest_designators;
 use crate::ast::*;
 use crate::data::*;
 use crate::named_entity::*;
+use crate::standard::VHDLStandard;
 use crate::syntax::TokenAccess;
 use fnv::FnvHashSet;
 use std::cell::RefCell;
@@ -137,6 +139,8 @@ pub(super) struct AnalyzeContext<'a> {
     std_sym: Symbol,
     standard_sym: Symbol,
     pub(super) is_std_logic_1164: bool,
+    // The protected types of std.reflection are implemented by the tool and have no body
+    pub(super) is_std_reflection: bool,
 
     // Record dependencies and sensitivies when
     // analyzing design units
@@ -175,6 +179,8 @@ impl<'a> AnalyzeContext<'a> {
                     &root.symbol_utf8("ieee"),
                     &root.symbol_utf8("std_logic_1164"),
                 ),
+            is_std_reflection: current_unit
+                == &UnitId::package(&root.symbol_utf8("std"), &root.symbol_utf8("reflection")),
             root,
             current_unit: current_unit.clone(),
             arena,
@@ -197,6 +203,11 @@ impl<'a> AnalyzeContext<'a> {
         &self.current_unit
     }
 
+    /// The revision of the language that the current unit is analyzed according to
+    pub fn standard(&self) -> VHDLStandard {
+        self.root.standard_of(self.current_unit.library_name())
+    }
+
     fn make_use_of(&self, use_pos: Option<&SrcPos>, unit_id: &UnitId) -> FatalResult {
         // Check local cache before taking lock
         if self.uses.borrow_mut().insert(unit_id.clone()) {
@@ -419,10 +430,47 @@ impl<'a> AnalyzeContext<'a> {
             }
         }
 
-        Err(AnalysisError::NotFatal(Diagnostic::error(
+        let candidates: Vec<Designator> = self
+            .root
+            .get_library_units(library_name)
+            .into_iter()
+            .flat_map(|units| units.keys())
+            .filter_map(|key| match key {
+                UnitKey::Primary(name) => Some(Designator::Identifier(name.clone())),
+                UnitKey::Secondary(..) => None,
+            })
+            .collect();
+
+        Err(AnalysisError::NotFatal(
+            Diagnostic::error(
+                pos,
+                format!("No primary unit '{primary_name}' within library '{library_name}'"),
+            )
+            .with_suggestions(closest_designators(primary_name, &candidates)),
+        ))
+    }
+
+    /// Lookup a mirror type such as `integer_subtype_mirror` in the std.reflection package
+    pub fn reflection_mirror_type(&self, pos: &SrcPos, name: &str) -> AnalysisResult<TypeEnt<'a>> {
+        let reflection = Designator::Identifier(self.root.symbol_utf8("reflection"));
+        let design: EntRef<'a> = self
+            .lookup_in_library(&self.std_sym, pos, &reflection)?
+            .into();
+
+        if let AnyEntKind::Design(Design::Package(_, region)) = design.kind() {
+            let designator = Designator::Identifier(self.root.symbol_utf8(name));
+            if let Some(typ) = region
+                .lookup_immediate(&designator)
+                .and_then(|named| TypeEnt::from_any(named.first()))
+            {
+                return Ok(typ);
+            }
+        }
+
+        Err(AnalysisError::not_fatal_error(
             pos,
-            format!("No primary unit '{primary_name}' within library '{library_name}'"),
-        )))
+            format!("No declaration of '{name}' within package 'std.reflection'"),
+        ))
     }
 
     // Returns None when analyzing the standard package itsel
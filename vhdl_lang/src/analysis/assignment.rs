@@ -24,6 +24,17 @@ impl<'a> AnalyzeContext<'a> {
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
         let ttyp = as_fatal(self.resolve_target(scope, target, assignment_type, diagnostics))?;
+        self.analyze_expr_right_hand(scope, ttyp, rhs, diagnostics)
+    }
+
+    /// Analyze the expressions of a right hand side against the type of the target
+    pub fn analyze_expr_right_hand(
+        &self,
+        scope: &Scope<'a>,
+        ttyp: Option<TypeEnt<'a>>,
+        rhs: &mut AssignmentRightHand<WithPos<Expression>>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
         match rhs {
             AssignmentRightHand::Simple(expr) => {
                 self.analyze_expression_for_target(scope, ttyp, expr, diagnostics)?;
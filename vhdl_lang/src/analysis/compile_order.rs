@@ -0,0 +1,179 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! The order in which files may be analyzed by other tools
+
+use super::root::DesignRoot;
+use super::LockedUnit;
+use crate::ast::*;
+use fnv::{FnvHashMap, FnvHashSet};
+use std::path::PathBuf;
+
+/// Libraries that are built into every simulator and synthesis tool
+const PREDEFINED_LIBRARIES: [&str; 2] = ["std", "ieee"];
+
+/// The design units to find the compile order of
+#[derive(Clone, Debug)]
+pub enum CompileOrderScope {
+    /// All files of a library
+    Library(String),
+    /// The files of a unit, given as library and unit name, and all files it depends on.
+    /// The predefined `std` and `ieee` libraries are left out
+    Top(String, String),
+}
+
+/// A file to analyze into a library
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CompileOrderFile {
+    pub library: String,
+    pub file_name: PathBuf,
+}
+
+/// A file within a library, the same file may be analyzed into several libraries
+type FileKey = (String, PathBuf);
+
+impl DesignRoot {
+    /// The files of the scope ordered such that a file only depends on files before it.
+    /// Requires the design to be analyzed, fails if the files depend on each other
+    pub fn compile_order(
+        &self,
+        scope: &CompileOrderScope,
+    ) -> Result<Vec<CompileOrderFile>, String> {
+        let dependencies = self.design_dependencies();
+        let units = match scope {
+            CompileOrderScope::Library(library_name) => {
+                let library = self
+                    .get_lib(&self.symbol_utf8(library_name))
+                    .ok_or_else(|| format!("No library named '{library_name}'"))?;
+                library.units().collect()
+            }
+            CompileOrderScope::Top(library_name, unit_name) => {
+                self.reachable_units(library_name, unit_name, &dependencies)?
+            }
+        };
+
+        let file_of = |unit: &LockedUnit| -> FileKey {
+            (
+                unit.unit_id().library_name().name_utf8(),
+                unit.ident().pos.source.file_name().to_owned(),
+            )
+        };
+
+        let mut files: Vec<FileKey> = units.iter().map(|unit| file_of(unit)).collect();
+        files.sort();
+        files.dedup();
+
+        let mut file_dependencies: FnvHashMap<FileKey, Vec<FileKey>> = FnvHashMap::default();
+        for unit in units.iter() {
+            let file = file_of(unit);
+            let depends_on = file_dependencies.entry(file.clone()).or_default();
+            for dependency in dependencies.get(unit.unit_id()).into_iter().flatten() {
+                let Some(dependency) = self.get_unit(dependency) else {
+                    continue;
+                };
+                let dependency = file_of(dependency);
+                if dependency != file && files.binary_search(&dependency).is_ok() {
+                    depends_on.push(dependency);
+                }
+            }
+            depends_on.sort();
+            depends_on.dedup();
+        }
+
+        let mut order = Vec::with_capacity(files.len());
+        let mut visited = FnvHashSet::default();
+        let mut visiting = Vec::new();
+        for file in files.iter() {
+            visit_file(
+                file,
+                &file_dependencies,
+                &mut visited,
+                &mut visiting,
+                &mut order,
+            )?;
+        }
+
+        Ok(order
+            .into_iter()
+            .map(|(library, file_name)| CompileOrderFile { library, file_name })
+            .collect())
+    }
+
+    /// The unit, the units it depends on and the secondary units of those units
+    fn reachable_units<'a>(
+        &'a self,
+        library_name: &str,
+        unit_name: &str,
+        dependencies: &FnvHashMap<UnitId, FnvHashSet<UnitId>>,
+    ) -> Result<Vec<&'a LockedUnit>, String> {
+        let top = self
+            .get_lib(&self.symbol_utf8(library_name))
+            .and_then(|library| library.primary_unit(&self.symbol_utf8(unit_name)))
+            .ok_or_else(|| format!("No design unit named '{library_name}.{unit_name}'"))?;
+
+        let mut reachable = FnvHashSet::default();
+        let mut pending = vec![top];
+        let mut result = Vec::new();
+        while let Some(unit) = pending.pop() {
+            if !reachable.insert(unit.unit_id().clone()) {
+                continue;
+            }
+
+            let library_name = unit.unit_id().library_name();
+            let is_predefined = PREDEFINED_LIBRARIES
+                .iter()
+                .any(|predefined| library_name.name_utf8().eq_ignore_ascii_case(predefined));
+            if is_predefined {
+                continue;
+            }
+            result.push(unit);
+
+            for dependency in dependencies.get(unit.unit_id()).into_iter().flatten() {
+                pending.extend(self.get_unit(dependency));
+            }
+            if unit.unit_id().secondary_name().is_none() {
+                if let Some(library) = self.get_lib(library_name) {
+                    pending.extend(library.secondary_units(unit.unit_id().primary_name()));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Depth first traversal that adds a file after the files it depends on
+fn visit_file(
+    file: &FileKey,
+    file_dependencies: &FnvHashMap<FileKey, Vec<FileKey>>,
+    visited: &mut FnvHashSet<FileKey>,
+    visiting: &mut Vec<FileKey>,
+    order: &mut Vec<FileKey>,
+) -> Result<(), String> {
+    if visited.contains(file) {
+        return Ok(());
+    }
+    if let Some(idx) = visiting.iter().position(|other| other == file) {
+        let cycle: Vec<String> = visiting[idx..]
+            .iter()
+            .chain(std::iter::once(file))
+            .map(|(_, file_name)| file_name.to_string_lossy().into_owned())
+            .collect();
+        return Err(format!(
+            "Circular dependency between files: {}",
+            cycle.join(" -> ")
+        ));
+    }
+
+    visiting.push(file.clone());
+    for dependency in file_dependencies.get(file).into_iter().flatten() {
+        visit_file(dependency, file_dependencies, visited, visiting, order)?;
+    }
+    visiting.pop();
+
+    visited.insert(file.clone());
+    order.push(file.clone());
+    Ok(())
+}
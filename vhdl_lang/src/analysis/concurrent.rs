@@ -11,6 +11,7 @@ use super::*;
 use crate::ast::*;
 use crate::data::*;
 use crate::named_entity::*;
+use crate::syntax::HasTokenSpan;
 use analyze::*;
 use target::AssignmentType;
 
@@ -77,10 +78,25 @@ impl<'a> AnalyzeContext<'a> {
     ) -> FatalResult {
         match statement.statement.item {
             ConcurrentStatement::Block(ref mut block) => {
+                let nested = scope.nested();
                 if let Some(ref mut guard_condition) = block.guard_condition {
                     self.boolean_expr(scope, guard_condition, diagnostics)?;
+                    // LRM 11.2 A guarded block implicitly declares the signal GUARD
+                    let guard = self.arena.alloc(
+                        self.guard_designator(),
+                        Some(parent),
+                        Related::None,
+                        AnyEntKind::Object(Object {
+                            class: ObjectClass::Signal,
+                            iface: None,
+                            subtype: Subtype::new(self.boolean()),
+                            has_default: false,
+                            signal_kind: None,
+                        }),
+                        Some(guard_condition.pos.clone()),
+                    );
+                    nested.add(guard, diagnostics);
                 }
-                let nested = scope.nested();
                 if let Some(ref mut list) = block.header.generic_clause {
                     self.analyze_interface_list(&nested, parent, list, diagnostics)?;
                 }
@@ -132,6 +148,9 @@ impl<'a> AnalyzeContext<'a> {
                     end_label_pos: _,
                 } = gen;
                 let typ = as_fatal(self.drange_type(scope, discrete_range, diagnostics))?;
+                if self.static_null_range(discrete_range).is_some() {
+                    self.inactive_generate_body(body, "the range is statically null", diagnostics);
+                }
                 let nested = scope.nested();
                 nested.add(
                     index_name.define(self.arena, parent, AnyEntKind::LoopParameter(typ)),
@@ -144,13 +163,37 @@ impl<'a> AnalyzeContext<'a> {
                     conditionals,
                     else_item,
                 } = &mut gen.conds;
+                // Set when a previous condition is statically true
+                let mut is_decided = false;
                 for conditional in conditionals.iter_mut() {
                     let Conditional { condition, item } = conditional;
                     self.boolean_expr(scope, condition, diagnostics)?;
+                    let value = self.static_boolean(&condition.item);
+                    if is_decided {
+                        self.inactive_generate_body(
+                            item,
+                            "a previous condition is statically true",
+                            diagnostics,
+                        );
+                    } else if value == Some(false) {
+                        self.inactive_generate_body(
+                            item,
+                            "the condition is statically false",
+                            diagnostics,
+                        );
+                    }
+                    is_decided |= value == Some(true);
                     let nested = scope.nested();
                     self.analyze_generate_body(&nested, parent, item, diagnostics)?;
                 }
                 if let Some(ref mut else_item) = else_item {
+                    if is_decided {
+                        self.inactive_generate_body(
+                            else_item,
+                            "a previous condition is statically true",
+                            diagnostics,
+                        );
+                    }
                     let nested = scope.nested();
                     self.analyze_generate_body(&nested, parent, else_item, diagnostics)?;
                 }
@@ -181,7 +224,15 @@ impl<'a> AnalyzeContext<'a> {
             }
             ConcurrentStatement::Assignment(ref mut assign) => {
                 // @TODO more delaymechanism
-                let ConcurrentSignalAssignment { target, rhs, .. } = assign;
+                let ConcurrentSignalAssignment {
+                    target,
+                    rhs,
+                    guarded,
+                    ..
+                } = assign;
+                if *guarded {
+                    self.check_guard_signal(scope, &target.pos, diagnostics);
+                }
                 self.analyze_waveform_assignment(
                     scope,
                     target,
@@ -204,7 +255,9 @@ impl<'a> AnalyzeContext<'a> {
                             severity,
                         },
                 } = assert;
-                self.boolean_expr(scope, condition, diagnostics)?;
+                if !self.analyze_psl_property_instance(scope, condition, diagnostics)? {
+                    self.boolean_expr(scope, condition, diagnostics)?;
+                }
                 if let Some(expr) = report {
                     self.expr_with_ttyp(scope, self.string(), expr, diagnostics)?;
                 }
@@ -212,6 +265,9 @@ impl<'a> AnalyzeContext<'a> {
                     self.expr_with_ttyp(scope, self.severity_level(), expr, diagnostics)?;
                 }
             }
+            ConcurrentStatement::Psl(ref mut directive) => {
+                self.analyze_psl_directive(scope, directive, diagnostics)?;
+            }
         };
         Ok(())
     }
@@ -252,6 +308,41 @@ impl<'a> AnalyzeContext<'a> {
         Ok(())
     }
 
+    /// Mark a generate body that is never elaborated so that it can be faded out
+    fn inactive_generate_body(
+        &self,
+        body: &GenerateBody,
+        reason: &str,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let GenerateBody {
+            alternative_label: _,
+            decl,
+            statements,
+            end_label_pos: _,
+        } = body;
+
+        let decl_pos = decl.iter().flatten().map(|decl| decl.get_pos(self.ctx));
+        let statement_pos = statements.iter().map(|statement| {
+            if let Some(ref label) = statement.label.tree {
+                label.pos.combine(&statement.statement.pos)
+            } else {
+                statement.statement.pos.clone()
+            }
+        });
+
+        if let Some(pos) = decl_pos
+            .chain(statement_pos)
+            .reduce(|pos, other| pos.combine_into(&other))
+        {
+            diagnostics.push(
+                Diagnostic::hint(pos, format!("Inactive generate branch, {reason}"))
+                    .with_code(DiagnosticCode::Unreachable)
+                    .with_tag(DiagnosticTag::Unnecessary),
+            );
+        }
+    }
+
     fn analyze_instance(
         &self,
         scope: &Scope<'a>,
@@ -418,6 +509,32 @@ impl<'a> AnalyzeContext<'a> {
         self.analyze_assoc_elems(scope, aspect.list.items.as_mut_slice(), diagnostics)
     }
 
+    fn guard_designator(&self) -> Designator {
+        Designator::Identifier(self.root.symbol_utf8("GUARD"))
+    }
+
+    /// LRM 11.6 A guarded assignment requires a GUARD signal to be visible
+    fn check_guard_signal(
+        &self,
+        scope: &Scope<'a>,
+        pos: &SrcPos,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let is_signal = match scope.lookup(pos, &self.guard_designator()) {
+            Ok(NamedEntities::Single(ent)) => {
+                ObjectEnt::from_any(ent).is_some_and(|obj| obj.class() == ObjectClass::Signal)
+            }
+            _ => false,
+        };
+
+        if !is_signal {
+            diagnostics.error(
+                pos,
+                "Guarded assignment requires a GUARD signal, declared implicitly by a guarded block",
+            );
+        }
+    }
+
     pub fn sensitivity_list_check(
         &self,
         scope: &Scope<'a>,
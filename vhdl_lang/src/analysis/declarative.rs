@@ -9,6 +9,7 @@ use super::*;
 use crate::ast::*;
 use crate::data::*;
 use crate::named_entity::{Signature, *};
+use crate::standard::VHDLStandard;
 use crate::{ast, named_entity, HasTokenSpan};
 use analyze::*;
 use fnv::FnvHashMap;
@@ -19,7 +20,7 @@ impl Declaration {
         use Declaration::*;
         use ObjectClass::*;
         match parent {
-            AnyEntKind::Design(Design::Architecture(..))
+            AnyEntKind::Design(Design::Architecture(..) | Design::VerificationUnit(_))
             | AnyEntKind::Concurrent(Some(Concurrent::Block | Concurrent::Generate)) => matches!(
                 self,
                 Object(ObjectDeclaration {
@@ -36,9 +37,17 @@ impl Declaration {
                     | Use(_)
                     | Package(_)
                     | Configuration(_)
+                    | View(_)
+                    | GroupTemplate(_)
+                    | Group(_)
+                    | Psl(_)
+                    | Disconnection(_)
             ),
             AnyEntKind::Design(Design::Configuration) => {
-                matches!(self, Use(_) | Attribute(ast::Attribute::Specification(_)))
+                matches!(
+                    self,
+                    Use(_) | Attribute(ast::Attribute::Specification(_)) | Group(_)
+                )
             }
             AnyEntKind::Design(Design::Entity(..)) => matches!(
                 self,
@@ -52,6 +61,11 @@ impl Declaration {
                     | SubprogramBody(_)
                     | Use(_)
                     | Package(_)
+                    | View(_)
+                    | GroupTemplate(_)
+                    | Group(_)
+                    | Psl(_)
+                    | Disconnection(_)
             ),
             AnyEntKind::Design(Design::PackageBody | Design::UninstPackage(..))
             | AnyEntKind::Overloaded(
@@ -61,6 +75,7 @@ impl Declaration {
                 | Overloaded::UninstSubprogram(..),
             )
             | AnyEntKind::Concurrent(Some(Concurrent::Process))
+            | AnyEntKind::Sequential(Some(Sequential::Block))
             | AnyEntKind::Type(named_entity::Type::Protected(..)) => matches!(
                 self,
                 Object(ObjectDeclaration {
@@ -75,6 +90,9 @@ impl Declaration {
                     | SubprogramBody(_)
                     | Use(_)
                     | Package(_)
+                    | View(_)
+                    | GroupTemplate(_)
+                    | Group(_)
             ),
             AnyEntKind::Design(Design::Package(..)) => matches!(
                 self,
@@ -88,6 +106,11 @@ impl Declaration {
                     | SubprogramInstantiation(_)
                     | Use(_)
                     | Package(_)
+                    | View(_)
+                    | GroupTemplate(_)
+                    | Group(_)
+                    | Psl(ast::PslDeclaration::Named(_))
+                    | Disconnection(_)
             ),
             _ => {
                 // AnyEntKind::Library is used in tests for a generic declarative region
@@ -358,6 +381,19 @@ impl<'a> AnalyzeContext<'a> {
                 }
 
                 if let Some(subtype) = as_fatal(subtype)? {
+                    if object_decl.class == ObjectClass::SharedVariable
+                        && self.standard() >= VHDLStandard::VHDL2002
+                        && !matches!(subtype.base().kind(), named_entity::Type::Protected(..))
+                    {
+                        diagnostics.error(
+                            &object_decl.subtype_indication.type_mark.pos,
+                            format!(
+                                "Shared variable must be of a protected type since {}",
+                                VHDLStandard::VHDL2002
+                            ),
+                        );
+                    }
+
                     let kind = if object_decl.class == ObjectClass::Constant
                         && object_decl.expression.is_none()
                     {
@@ -367,6 +403,7 @@ impl<'a> AnalyzeContext<'a> {
                             class: object_decl.class,
                             iface: None,
                             has_default: object_decl.expression.is_some(),
+                            signal_kind: object_decl.signal_kind,
                             subtype,
                         })
                     };
@@ -572,12 +609,77 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
             Declaration::Configuration(..) => {}
+            Declaration::View(ref mut view) => {
+                self.analyze_view_declaration(scope, parent, view, diagnostics)?;
+            }
+            Declaration::Psl(ref mut psl) => {
+                self.analyze_psl_declaration(scope, parent, psl, diagnostics);
+            }
+            Declaration::Disconnection(ref mut disconnection) => {
+                self.analyze_disconnection_specification(scope, disconnection, diagnostics)?;
+            }
+            Declaration::GroupTemplate(ref mut template) => {
+                self.analyze_group_template_declaration(scope, parent, template, diagnostics);
+            }
+            Declaration::Group(ref mut group) => {
+                self.analyze_group_declaration(scope, parent, group, diagnostics)?;
+            }
             Declaration::Type(..) => unreachable!("Handled elsewhere"),
         };
 
         Ok(())
     }
 
+    /// LRM 7.4 Disconnection specification
+    fn analyze_disconnection_specification(
+        &self,
+        scope: &Scope<'a>,
+        disconnection: &mut DisconnectionSpecification,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let typ =
+            as_fatal(self.resolve_type_mark(scope, &mut disconnection.type_mark, diagnostics))?;
+
+        if let GuardedSignalList::Names(ref mut names) = disconnection.signals {
+            for name in names.iter_mut() {
+                let Some(object_name) = as_fatal(self.resolve_object_name(
+                    scope,
+                    &name.pos,
+                    &mut name.item,
+                    "is not a guarded signal",
+                    diagnostics,
+                ))?
+                else {
+                    continue;
+                };
+
+                let is_guarded = matches!(
+                    object_name.base,
+                    ObjectBase::Object(obj) if obj.kind().signal_kind.is_some()
+                );
+                if !is_guarded || object_name.type_mark.is_some() {
+                    diagnostics.error(
+                        &name.pos,
+                        format!("{} is not a guarded signal", object_name.base.describe()),
+                    );
+                } else if let Some(typ) = typ {
+                    if typ.base() != object_name.type_mark().base() {
+                        diagnostics.error(
+                            &disconnection.type_mark.pos,
+                            format!(
+                                "{} does not match the type of {}",
+                                typ.describe(),
+                                object_name.base.describe()
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        self.expr_with_ttyp(scope, self.time(), &mut disconnection.after, diagnostics)
+    }
+
     fn find_deferred_constant_declaration(
         &self,
         scope: &Scope<'a>,
@@ -719,9 +821,11 @@ impl<'a> AnalyzeContext<'a> {
                 | EntityClass::Constant
                 | EntityClass::Type
                 | EntityClass::Subtype
-                | EntityClass::Literal
-                | EntityClass::Units
                 | EntityClass::File
+                | EntityClass::Property
+                | EntityClass::Sequence
+                | EntityClass::Group
+                | EntityClass::View
                 | EntityClass::Label => {
                     if ent.parent != Some(parent) {
                         diagnostics.push(Diagnostic::error(
@@ -731,6 +835,17 @@ impl<'a> AnalyzeContext<'a> {
                         return Ok(());
                     }
                 }
+                EntityClass::Literal | EntityClass::Units => {
+                    // Literals and units are declared within the region of their type
+                    let type_parent = ent.parent.and_then(|typ| typ.parent);
+                    if ent.parent != Some(parent) && type_parent != Some(parent) {
+                        diagnostics.push(Diagnostic::error(
+                            designator,
+                            "Attribute specification must be in the immediate declarative part",
+                        ));
+                        return Ok(());
+                    }
+                }
             }
 
             if Some(*entity_class) != get_entity_class(ent) {
@@ -771,42 +886,62 @@ impl<'a> AnalyzeContext<'a> {
                     AnyEntKind::InterfaceFile(file_type.type_mark().to_owned()),
                 )
             }
-            InterfaceDeclaration::Object(ref mut object_decl) => {
-                let subtype = self.resolve_subtype_indication(
-                    scope,
-                    &mut object_decl.subtype_indication,
-                    diagnostics,
-                );
+            InterfaceDeclaration::Object(ref mut object_decl) => match object_decl.mode {
+                ModeIndication::Simple(ref mut mode) => {
+                    let subtype = self.resolve_subtype_indication(
+                        scope,
+                        &mut mode.subtype_indication,
+                        diagnostics,
+                    );
 
-                if let Some(ref mut expression) = object_decl.expression {
-                    if let Ok(ref subtype) = subtype {
-                        self.expr_pos_with_ttyp(
-                            scope,
-                            subtype.type_mark(),
-                            &expression.pos,
-                            &mut expression.item,
-                            diagnostics,
-                        )?;
-                    } else {
-                        self.expr_unknown_ttyp(scope, expression, diagnostics)?
+                    if let Some(ref mut expression) = mode.expression {
+                        if let Ok(ref subtype) = subtype {
+                            self.expr_pos_with_ttyp(
+                                scope,
+                                subtype.type_mark(),
+                                &expression.pos,
+                                &mut expression.item,
+                                diagnostics,
+                            )?;
+                        } else {
+                            self.expr_unknown_ttyp(scope, expression, diagnostics)?
+                        }
                     }
-                }
 
-                let subtype = subtype?;
-                self.arena.define(
-                    &mut object_decl.ident,
-                    parent,
-                    AnyEntKind::Object(Object {
-                        class: object_decl.class,
-                        iface: Some(ObjectInterface::new(
-                            object_decl.list_type,
-                            object_decl.mode,
-                        )),
-                        subtype,
-                        has_default: object_decl.expression.is_some(),
-                    }),
-                )
-            }
+                    let subtype = subtype?;
+                    self.arena.define(
+                        &mut object_decl.ident,
+                        parent,
+                        AnyEntKind::Object(Object {
+                            class: object_decl.class,
+                            iface: Some(ObjectInterface::new(object_decl.list_type, mode.mode)),
+                            subtype,
+                            has_default: mode.expression.is_some(),
+                            signal_kind: mode.bus.then_some(SignalKind::Bus),
+                        }),
+                    )
+                }
+                ModeIndication::View(ref mut view) => {
+                    let view_ref = self.resolve_view_name(scope, &mut view.name, diagnostics)?;
+                    let subtype =
+                        self.view_interface_subtype(scope, view_ref, view, diagnostics)?;
+                    self.arena.define(
+                        &mut object_decl.ident,
+                        parent,
+                        AnyEntKind::Object(Object {
+                            class: object_decl.class,
+                            iface: Some(ObjectInterface::View(
+                                object_decl.list_type,
+                                view.kind,
+                                view_ref,
+                            )),
+                            subtype,
+                            has_default: false,
+                            signal_kind: None,
+                        }),
+                    )
+                }
+            },
             InterfaceDeclaration::Type(ref mut ident) => {
                 let typ = TypeEnt::from_any(self.arena.define(
                     ident,
@@ -941,7 +1076,7 @@ impl Diagnostic {
     }
 }
 
-fn get_entity_class(ent: EntRef) -> Option<EntityClass> {
+pub(super) fn get_entity_class(ent: EntRef) -> Option<EntityClass> {
     match ent.actual_kind() {
         // Alias is never the direct target of attribute
         AnyEntKind::ExternalAlias { .. } => None,
@@ -979,12 +1114,20 @@ fn get_entity_class(ent: EntRef) -> Option<EntityClass> {
             ObjectClass::SharedVariable => Some(EntityClass::Variable),
         },
         AnyEntKind::LoopParameter(_) => None, // @TODO is it allowed?
-        AnyEntKind::PhysicalLiteral(_) => None, // @TODO maybe Units?
+        AnyEntKind::PhysicalLiteral(_) => Some(EntityClass::Units),
         AnyEntKind::DeferredConstant(_) => Some(EntityClass::Constant),
         AnyEntKind::Library => None,
+        AnyEntKind::View(_) => Some(EntityClass::View),
+        AnyEntKind::Group(Group::Template(_)) => None,
+        AnyEntKind::Group(Group::Declaration(_)) => Some(EntityClass::Group),
+        AnyEntKind::Psl(psl) => match psl {
+            Psl::Property => Some(EntityClass::Property),
+            Psl::Sequence => Some(EntityClass::Sequence),
+            Psl::Parameter => None,
+        },
         AnyEntKind::Design(des) => match des {
             Design::Entity(_, _) => Some(EntityClass::Entity),
-            Design::Architecture(_) => Some(EntityClass::Architecture),
+            Design::Architecture(..) => Some(EntityClass::Architecture),
             Design::Configuration => Some(EntityClass::Configuration),
             Design::Package(_, _) => Some(EntityClass::Package),
             // Should never be target of attribute
@@ -992,6 +1135,7 @@ fn get_entity_class(ent: EntRef) -> Option<EntityClass> {
             Design::UninstPackage(_, _) => None,
             Design::PackageInstance(_) => None,
             Design::Context(_) => None,
+            Design::VerificationUnit(_) => None,
         },
     }
 }
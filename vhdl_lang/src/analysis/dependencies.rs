@@ -0,0 +1,330 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! The dependencies between design units that tools outside of the analysis rely on
+
+use super::dependency_graph::DependencyKind;
+use super::root::DesignRoot;
+use super::LockedUnit;
+use crate::ast::*;
+use crate::data::Symbol;
+use fnv::{FnvHashMap, FnvHashSet};
+
+impl DesignRoot {
+    /// The units that each unit directly depends on. Requires the design to be analyzed.
+    ///
+    /// Besides the dependencies registered during analysis this includes the units bound
+    /// by configuration declarations, whose block configurations are not analyzed,
+    /// and the entities that instantiated components are bound to by default
+    pub(super) fn design_dependencies(&self) -> FnvHashMap<UnitId, FnvHashSet<UnitId>> {
+        let mut dependencies = self.unit_dependencies();
+        for library in self.libraries() {
+            for unit in library.units() {
+                let targets = Targets::of(unit);
+                let mut bound = self.bound_units(unit.unit_id(), &targets.bindings);
+                bound.extend(self.default_bound_entities(unit, &targets));
+                if bound.is_empty() {
+                    continue;
+                }
+                let bound: Vec<UnitId> = bound.into_iter().cloned().collect();
+                dependencies
+                    .entry(unit.unit_id().clone())
+                    .or_default()
+                    .extend(bound);
+            }
+        }
+        dependencies
+    }
+
+    /// The entities, architectures and configurations named by binding indications
+    fn bound_units<'a>(&'a self, unit_id: &UnitId, bindings: &'a [Binding]) -> Vec<&'a UnitId> {
+        let mut units = Vec::new();
+        for binding in bindings {
+            let library_name = match &binding.library {
+                Some(library_name) if !library_name.name_utf8().eq_ignore_ascii_case("work") => {
+                    library_name
+                }
+                _ => unit_id.library_name(),
+            };
+            let Some(library) = self.get_lib(library_name) else {
+                continue;
+            };
+            units.extend(
+                library
+                    .primary_unit(&binding.unit)
+                    .map(|unit| unit.unit_id()),
+            );
+            if let Some(architecture) = &binding.architecture {
+                units.extend(
+                    library
+                        .secondary_units(&binding.unit)
+                        .map(|unit| unit.unit_id())
+                        .filter(|unit_id| unit_id.secondary_name() == Some(architecture)),
+                );
+            }
+        }
+        units
+    }
+
+    /// The entities of the same name as the components that are instantiated without a
+    /// configuration specification, LRM 7.3.3.
+    /// These are searched for in the library of the unit and then in the libraries named by
+    /// its context clause, or that of its entity
+    fn default_bound_entities(&self, unit: &LockedUnit, targets: &Targets) -> Vec<&UnitId> {
+        let components: Vec<&Symbol> = targets.default_bound_components().collect();
+        if components.is_empty() {
+            return Vec::new();
+        }
+
+        let unit_id = unit.unit_id();
+        let mut library_names = vec![unit_id.library_name().clone()];
+        library_names.extend(targets.libraries.iter().cloned());
+        if unit_id.secondary_name().is_some() {
+            if let Some(primary) = self
+                .get_lib(unit_id.library_name())
+                .and_then(|library| library.primary_unit(unit_id.primary_name()))
+            {
+                library_names.extend(Targets::of(primary).libraries);
+            }
+        }
+        let libraries: Vec<_> = library_names
+            .iter()
+            .filter(|name| !name.name_utf8().eq_ignore_ascii_case("work"))
+            .filter_map(|name| self.get_lib(name))
+            .collect();
+
+        components
+            .into_iter()
+            .filter_map(|component| {
+                libraries
+                    .iter()
+                    .filter_map(|library| library.primary_unit(component))
+                    .find(|unit| unit.kind() == AnyKind::Primary(PrimaryKind::Entity))
+                    .map(|unit| unit.unit_id())
+            })
+            .collect()
+    }
+}
+
+/// The names of the units that a design unit instantiates or binds in a configuration.
+/// Names are compared without their library since a unit rarely depends on
+/// two units of the same name
+#[derive(Default)]
+pub(super) struct Targets {
+    instantiated: FnvHashSet<Symbol>,
+    configured: FnvHashSet<Symbol>,
+    bindings: Vec<Binding>,
+    /// The names of the instantiated components
+    components: FnvHashSet<Symbol>,
+    /// The names of the components that configuration specifications bind explicitly
+    specified_components: FnvHashSet<Symbol>,
+    /// The libraries named by the context clause
+    libraries: Vec<Symbol>,
+}
+
+/// The unit named by the entity aspect of a binding indication, such as `entity lib.ent(arch)`
+struct Binding {
+    library: Option<Symbol>,
+    unit: Symbol,
+    architecture: Option<Symbol>,
+}
+
+impl Targets {
+    pub(super) fn of(unit: &LockedUnit) -> Targets {
+        let mut targets = Targets::default();
+        let Some(guard) = unit.unit.get() else {
+            return targets;
+        };
+        match guard.data() {
+            AnyDesignUnit::Primary(primary) => match primary {
+                AnyPrimaryUnit::Entity(entity) => {
+                    targets.context_clause(&entity.context_clause);
+                    targets.declarations(&entity.decl);
+                    targets.statements(&entity.statements);
+                }
+                AnyPrimaryUnit::Package(package) => targets.declarations(&package.decl),
+                AnyPrimaryUnit::PackageInstance(instance) => {
+                    targets
+                        .instantiated
+                        .extend(unit_name(&instance.package_name.item));
+                }
+                AnyPrimaryUnit::Configuration(config) => {
+                    targets
+                        .configured
+                        .extend(unit_name(&config.entity_name.item));
+                    targets.block_configuration(&config.block_config);
+                }
+                AnyPrimaryUnit::Context(_) | AnyPrimaryUnit::VerificationUnit(_) => {}
+            },
+            AnyDesignUnit::Secondary(secondary) => match secondary {
+                AnySecondaryUnit::Architecture(arch) => {
+                    targets.context_clause(&arch.context_clause);
+                    targets.declarations(&arch.decl);
+                    targets.statements(&arch.statements);
+                }
+                AnySecondaryUnit::PackageBody(body) => targets.declarations(&body.decl),
+            },
+        }
+        targets
+    }
+
+    pub(super) fn kind_of(&self, unit_id: &UnitId, dependency: &UnitId) -> DependencyKind {
+        let name = dependency.primary_name();
+        if unit_id.secondary_name().is_some()
+            && unit_id.library_name() == dependency.library_name()
+            && unit_id.primary_name() == name
+            && dependency.secondary_name().is_none()
+        {
+            DependencyKind::Implements
+        } else if self.instantiated.contains(name) || self.is_default_binding(dependency) {
+            DependencyKind::Instantiation
+        } else if self.configured.contains(name) {
+            DependencyKind::Configuration
+        } else {
+            DependencyKind::Use
+        }
+    }
+
+    /// The names of the components that are bound to an entity of the same name by default
+    fn default_bound_components(&self) -> impl Iterator<Item = &Symbol> {
+        self.components
+            .iter()
+            .filter(|component| !self.specified_components.contains(*component))
+    }
+
+    /// An entity that an instantiated component is bound to by default
+    fn is_default_binding(&self, dependency: &UnitId) -> bool {
+        dependency.kind() == AnyKind::Primary(PrimaryKind::Entity)
+            && self
+                .default_bound_components()
+                .any(|component| component == dependency.primary_name())
+    }
+
+    fn context_clause(&mut self, context_clause: &ContextClause) {
+        for item in context_clause {
+            if let ContextItem::Library(clause) = item {
+                self.libraries.extend(
+                    clause
+                        .name_list
+                        .items
+                        .iter()
+                        .map(|name| name.item.item.clone()),
+                );
+            }
+        }
+    }
+
+    fn declarations(&mut self, declarations: &[Declaration]) {
+        for declaration in declarations {
+            match declaration {
+                Declaration::Package(instance) => {
+                    self.instantiated
+                        .extend(unit_name(&instance.package_name.item));
+                }
+                Declaration::Configuration(spec) => {
+                    if spec.bind_ind.entity_aspect.is_some() {
+                        self.specified_components
+                            .extend(unit_name(&spec.spec.component_name.item));
+                    }
+                    self.binding(&spec.bind_ind);
+                }
+                Declaration::SubprogramBody(body) => self.declarations(&body.declarations),
+                _ => {}
+            }
+        }
+    }
+
+    fn statements(&mut self, statements: &[LabeledConcurrentStatement]) {
+        for statement in statements {
+            match &statement.statement.item {
+                ConcurrentStatement::Instance(instance) => match &instance.unit {
+                    InstantiatedUnit::Entity(name, _) | InstantiatedUnit::Configuration(name) => {
+                        self.instantiated.extend(unit_name(&name.item));
+                    }
+                    InstantiatedUnit::Component(name) => {
+                        self.components.extend(unit_name(&name.item));
+                    }
+                },
+                ConcurrentStatement::Block(block) => {
+                    self.declarations(&block.decl);
+                    self.statements(&block.statements);
+                }
+                ConcurrentStatement::Process(process) => self.declarations(&process.decl),
+                ConcurrentStatement::ForGenerate(generate) => self.generate_body(&generate.body),
+                ConcurrentStatement::IfGenerate(generate) => {
+                    for conditional in generate.conds.conditionals.iter() {
+                        self.generate_body(&conditional.item);
+                    }
+                    if let Some(body) = &generate.conds.else_item {
+                        self.generate_body(body);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(generate) => {
+                    for alternative in generate.sels.alternatives.iter() {
+                        self.generate_body(&alternative.item);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn generate_body(&mut self, body: &GenerateBody) {
+        if let Some(decl) = &body.decl {
+            self.declarations(decl);
+        }
+        self.statements(&body.statements);
+    }
+
+    fn block_configuration(&mut self, config: &BlockConfiguration) {
+        for item in config.items.iter() {
+            match item {
+                ConfigurationItem::Block(block) => self.block_configuration(block),
+                ConfigurationItem::Component(component) => {
+                    if let Some(bind_ind) = &component.bind_ind {
+                        self.binding(bind_ind);
+                    }
+                    if let Some(block) = &component.block_config {
+                        self.block_configuration(block);
+                    }
+                }
+            }
+        }
+    }
+
+    fn binding(&mut self, bind_ind: &BindingIndication) {
+        let (name, architecture) = match &bind_ind.entity_aspect {
+            Some(EntityAspect::Entity(name, architecture)) => (name, architecture.as_ref()),
+            Some(EntityAspect::Configuration(name)) => (name, None),
+            Some(EntityAspect::Open) | None => return,
+        };
+        let Some(unit) = unit_name(&name.item) else {
+            return;
+        };
+        let library = match &name.item {
+            SelectedName::Selected(prefix, _) => unit_name(&prefix.item),
+            SelectedName::Designator(_) => None,
+        };
+        self.configured.insert(unit.clone());
+        self.bindings.push(Binding {
+            library,
+            unit,
+            architecture: architecture.map(|architecture| architecture.item.clone()),
+        });
+    }
+}
+
+/// The last name of a selected name such as `lib.unit`
+fn unit_name(name: &SelectedName) -> Option<Symbol> {
+    let designator = match name {
+        SelectedName::Designator(designator) => &designator.item,
+        SelectedName::Selected(_, designator) => &designator.item.item,
+    };
+    match designator {
+        Designator::Identifier(symbol) => Some(symbol.clone()),
+        _ => None,
+    }
+}
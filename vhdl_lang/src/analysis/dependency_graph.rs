@@ -0,0 +1,252 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! The dependencies between design units as a graph
+
+use super::dependencies::Targets;
+use super::root::DesignRoot;
+use super::LockedUnit;
+use crate::ast::*;
+use fnv::{FnvHashMap, FnvHashSet};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Why a design unit depends on another
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum DependencyKind {
+    /// Names made visible by a use clause or context reference, or selected by name
+    Use,
+    /// An entity, configuration or package that is instantiated
+    Instantiation,
+    /// An entity or configuration that is bound by a configuration
+    Configuration,
+    /// The primary unit of an architecture or package body
+    Implements,
+}
+
+impl DependencyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DependencyKind::Use => "use",
+            DependencyKind::Instantiation => "instantiation",
+            DependencyKind::Configuration => "configuration",
+            DependencyKind::Implements => "implements",
+        }
+    }
+}
+
+/// A design unit, architectures are named `entity(architecture)` and package bodies `package(body)`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DependencyNode {
+    pub library: String,
+    pub name: String,
+    pub kind: String,
+    pub file_name: PathBuf,
+}
+
+impl DependencyNode {
+    /// The name that identifies the node in the graph
+    pub fn id(&self) -> String {
+        format!("{}.{}", self.library, self.name)
+    }
+}
+
+/// A dependency from one node to another, given by their ids
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct DependencyEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: DependencyKind,
+}
+
+/// The design units and the dependencies between them, sorted by name
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct DependencyGraph {
+    pub nodes: Vec<DependencyNode>,
+    pub edges: Vec<DependencyEdge>,
+}
+
+impl DesignRoot {
+    /// The dependency graph of all design units, or only of those reachable from a root
+    /// unit given as library and unit name. Requires the design to be analyzed.
+    ///
+    /// The implicit dependency of every unit on `std.standard` is left out
+    pub fn dependency_graph(&self, root: Option<(&str, &str)>) -> Result<DependencyGraph, String> {
+        let root_id = match root {
+            Some((library_name, unit_name)) => {
+                let unit = self
+                    .get_lib(&self.symbol_utf8(library_name))
+                    .and_then(|library| library.primary_unit(&self.symbol_utf8(unit_name)))
+                    .ok_or_else(|| format!("No design unit named '{library_name}.{unit_name}'"))?;
+                Some(node_of(unit).id())
+            }
+            None => None,
+        };
+
+        let dependencies = self.design_dependencies();
+        let mut graph = DependencyGraph::default();
+        for library in self.libraries() {
+            for unit in library.units() {
+                if is_std_standard(unit.unit_id()) {
+                    continue;
+                }
+                let node = node_of(unit);
+                let targets = Targets::of(unit);
+
+                for dependency in dependencies.get(unit.unit_id()).into_iter().flatten() {
+                    if is_std_standard(dependency) {
+                        continue;
+                    }
+                    let Some(dependency) = self.get_unit(dependency) else {
+                        continue;
+                    };
+                    graph.edges.push(DependencyEdge {
+                        from: node.id(),
+                        to: node_of(dependency).id(),
+                        kind: targets.kind_of(unit.unit_id(), dependency.unit_id()),
+                    });
+                }
+                graph.nodes.push(node);
+            }
+        }
+        graph.nodes.sort_by_key(|node| node.id());
+        graph.edges.sort();
+
+        Ok(match root_id {
+            Some(root_id) => graph.reachable_from(&root_id),
+            None => graph,
+        })
+    }
+}
+
+impl DependencyGraph {
+    /// The subgraph of the root node, the nodes it depends on and the secondary units of those
+    fn reachable_from(self, root_id: &str) -> DependencyGraph {
+        let mut outgoing: FnvHashMap<&str, Vec<&str>> = FnvHashMap::default();
+        for edge in self.edges.iter() {
+            outgoing.entry(&edge.from).or_default().push(&edge.to);
+            if edge.kind == DependencyKind::Implements {
+                outgoing.entry(&edge.to).or_default().push(&edge.from);
+            }
+        }
+
+        let mut reachable: FnvHashSet<&str> = FnvHashSet::default();
+        let mut pending = vec![root_id];
+        while let Some(id) = pending.pop() {
+            if reachable.insert(id) {
+                pending.extend(outgoing.get(id).into_iter().flatten());
+            }
+        }
+
+        let reachable: FnvHashSet<String> = reachable.into_iter().map(str::to_owned).collect();
+        DependencyGraph {
+            nodes: self
+                .nodes
+                .into_iter()
+                .filter(|node| reachable.contains(&node.id()))
+                .collect(),
+            edges: self
+                .edges
+                .into_iter()
+                .filter(|edge| reachable.contains(&edge.from))
+                .collect(),
+        }
+    }
+
+    /// The graph in the Graphviz DOT language with a cluster for each library
+    pub fn to_dot(&self) -> String {
+        let mut libraries: BTreeMap<&str, Vec<&DependencyNode>> = BTreeMap::new();
+        for node in self.nodes.iter() {
+            libraries.entry(&node.library).or_default().push(node);
+        }
+
+        let mut dot = String::from("digraph dependencies {\n  node [shape=box];\n");
+        for (library, nodes) in libraries {
+            dot.push_str(&format!(
+                "  subgraph {} {{\n    label={};\n",
+                dot_string(&format!("cluster_{library}")),
+                dot_string(library)
+            ));
+            for node in nodes {
+                dot.push_str(&format!(
+                    "    {} [label={}];\n",
+                    dot_string(&node.id()),
+                    dot_string(&format!("{}\n{}", node.name, node.kind))
+                ));
+            }
+            dot.push_str("  }\n");
+        }
+        for edge in self.edges.iter() {
+            dot.push_str(&format!(
+                "  {} -> {} [label={}];\n",
+                dot_string(&edge.from),
+                dot_string(&edge.to),
+                dot_string(edge.kind.as_str())
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let nodes: Vec<_> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                json!({
+                    "id": node.id(),
+                    "library": node.library,
+                    "name": node.name,
+                    "kind": node.kind,
+                    "file_name": node.file_name.to_string_lossy(),
+                })
+            })
+            .collect();
+        let edges: Vec<_> = self
+            .edges
+            .iter()
+            .map(|edge| {
+                json!({
+                    "from": edge.from,
+                    "to": edge.to,
+                    "kind": edge.kind.as_str(),
+                })
+            })
+            .collect();
+        json!({ "nodes": nodes, "edges": edges })
+    }
+}
+
+fn node_of(unit: &LockedUnit) -> DependencyNode {
+    let unit_id = unit.unit_id();
+    DependencyNode {
+        library: unit_id.library_name().to_string(),
+        name: unit.display_name(),
+        kind: unit.kind().describe().to_owned(),
+        file_name: unit.ident().pos.source.file_name().to_owned(),
+    }
+}
+
+fn is_std_standard(unit_id: &UnitId) -> bool {
+    unit_id
+        .library_name()
+        .name_utf8()
+        .eq_ignore_ascii_case("std")
+        && unit_id
+            .primary_name()
+            .name_utf8()
+            .eq_ignore_ascii_case("standard")
+}
+
+/// Quote a string as a DOT identifier
+fn dot_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
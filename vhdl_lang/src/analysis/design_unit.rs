@@ -9,6 +9,7 @@ use crate::ast::*;
 use crate::data::*;
 use crate::named_entity::*;
 use analyze::*;
+use suggestions::closest_names;
 
 impl<'a> AnalyzeContext<'a> {
     pub fn analyze_primary_unit(
@@ -24,6 +25,9 @@ impl<'a> AnalyzeContext<'a> {
                 self.analyze_package_instance(unit, diagnostics)
             }
             AnyPrimaryUnit::Context(unit) => self.analyze_context(unit, diagnostics),
+            AnyPrimaryUnit::VerificationUnit(unit) => {
+                self.analyze_verification_unit(unit, diagnostics)
+            }
         }
     }
 
@@ -147,7 +151,7 @@ impl<'a> AnalyzeContext<'a> {
         }
         self.analyze_declarative_part(&scope, ent, &mut unit.decl, diagnostics)?;
 
-        if !self.has_package_body() {
+        if !self.has_package_body() && !self.is_std_reflection {
             scope.close(diagnostics);
         }
 
@@ -217,6 +221,121 @@ impl<'a> AnalyzeContext<'a> {
         Ok(())
     }
 
+    fn analyze_verification_unit(
+        &self,
+        unit: &mut VerificationUnit,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        // Pre-define verification unit and overwrite it later
+        let ent = self.arena.explicit(
+            unit.name().clone(),
+            self.work_library(),
+            AnyEntKind::Design(Design::VerificationUnit(Region::default())),
+            Some(unit.pos()),
+        );
+        unit.ident.decl.set(ent.id());
+
+        let bound = if let Some(ref mut binding) = unit.binding {
+            match self.verification_unit_binding(binding) {
+                Ok(design) => Some(design.0),
+                Err(err) => {
+                    err.add_to(diagnostics)?;
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // The items of a bound verification unit are analyzed as if they were
+        // part of the entity or architecture it is bound to
+        let (root_scope, scope) = match bound.map(|ent| ent.kind()) {
+            Some(AnyEntKind::Design(
+                Design::Entity(visibility, region) | Design::Architecture(visibility, region, _),
+            )) => {
+                let root_scope = Scope::new(Region::with_visibility(visibility.clone()));
+                self.analyze_context_clause(&root_scope, &mut unit.context_clause, diagnostics)?;
+                let scope = Scope::extend(region, Some(&root_scope));
+                (root_scope, scope)
+            }
+            _ => {
+                let root_scope = Scope::default();
+                self.add_implicit_context_clause(&root_scope)?;
+                self.analyze_context_clause(&root_scope, &mut unit.context_clause, diagnostics)?;
+                let scope = root_scope.nested();
+                (root_scope, scope)
+            }
+        };
+        root_scope.add(ent, diagnostics);
+
+        for inherit in unit.inherits.iter_mut() {
+            match self.lookup_in_library(
+                self.work_library_name(),
+                &inherit.item.pos,
+                &Designator::Identifier(inherit.item.item.clone()),
+            ) {
+                Ok(design) => {
+                    inherit.set_unique_reference(design.into());
+                    if let AnyEntKind::Design(Design::VerificationUnit(ref region)) =
+                        design.0.kind()
+                    {
+                        scope.make_all_potentially_visible(Some(&inherit.item.pos), region);
+                    } else {
+                        diagnostics.error(
+                            &inherit.item.pos,
+                            format!("{} is not a verification unit", design.describe()),
+                        );
+                    }
+                }
+                Err(err) => err.add_to(diagnostics)?,
+            }
+        }
+
+        self.define_labels_for_concurrent_part(&scope, ent, &mut unit.statements, diagnostics)?;
+        self.analyze_declarative_part(&scope, ent, &mut unit.decl, diagnostics)?;
+        self.analyze_concurrent_part(&scope, ent, &mut unit.statements, diagnostics)?;
+
+        let region = scope.into_region();
+        let kind = AnyEntKind::Design(Design::VerificationUnit(region));
+        unsafe { ent.set_kind(kind) }
+
+        Ok(())
+    }
+
+    /// Resolve the entity and optional architecture a verification unit is bound to
+    fn verification_unit_binding(
+        &self,
+        binding: &mut VerificationUnitBinding,
+    ) -> AnalysisResult<DesignEnt<'a>> {
+        let entity_name = binding.entity_name.item.clone();
+        let primary = self.lookup_in_library(
+            self.work_library_name(),
+            &entity_name.pos,
+            &Designator::Identifier(entity_name.item.clone()),
+        )?;
+        binding.entity_name.set_unique_reference(primary.into());
+
+        if !matches!(primary.kind(), Design::Entity(..)) {
+            return Err(AnalysisError::not_fatal_error(
+                &entity_name.pos,
+                format!("{} does not denote an entity", primary.describe()),
+            ));
+        }
+
+        if let Some(ref mut architecture_name) = binding.architecture_name {
+            let arch = self.get_architecture(
+                self.work_library_name(),
+                &architecture_name.item.pos,
+                &entity_name.item,
+                &architecture_name.item.item,
+            )?;
+            architecture_name.set_unique_reference(arch.into());
+            Ok(arch)
+        } else {
+            Ok(primary)
+        }
+    }
+
     fn analyze_architecture(
         &self,
         unit: &mut ArchitectureBody,
@@ -252,10 +371,15 @@ impl<'a> AnalyzeContext<'a> {
         let root_scope = Scope::new(Region::with_visibility(visibility.clone()));
         self.analyze_context_clause(&root_scope, &mut unit.context_clause, diagnostics)?;
 
+        // Pre-define architecture and overwrite it later
         let arch = self.arena.define(
             &mut unit.ident,
             primary.into(),
-            AnyEntKind::Design(Design::Architecture(primary)),
+            AnyEntKind::Design(Design::Architecture(
+                Visibility::default(),
+                Region::default(),
+                primary,
+            )),
         );
 
         root_scope.add(arch, diagnostics);
@@ -269,6 +393,12 @@ impl<'a> AnalyzeContext<'a> {
         self.analyze_declarative_part(&scope, arch, &mut unit.decl, diagnostics)?;
         self.analyze_concurrent_part(&scope, arch, &mut unit.statements, diagnostics)?;
         scope.close(diagnostics);
+
+        // Keep the scope so that verification units bound to the architecture can extend it
+        let region = scope.into_region();
+        let visibility = root_scope.into_visibility();
+        let kind = AnyEntKind::Design(Design::Architecture(visibility, region, primary));
+        unsafe { arch.set_kind(kind) }
         Ok(())
     }
 
@@ -489,10 +619,21 @@ impl<'a> AnalyzeContext<'a> {
                             library_name.set_unique_reference(library);
                             scope.make_potentially_visible(Some(&library_name.item.pos), library);
                         } else {
-                            diagnostics.push(Diagnostic::error(
-                                &library_name.item,
-                                format!("No such library '{}'", library_name.item),
-                            ));
+                            let candidates: Vec<String> = self
+                                .root
+                                .available_libraries()
+                                .map(|sym| sym.name_utf8())
+                                .collect();
+                            diagnostics.push(
+                                Diagnostic::error(
+                                    &library_name.item,
+                                    format!("No such library '{}'", library_name.item),
+                                )
+                                .with_suggestions(closest_names(
+                                    &library_name.item.item.name_utf8(),
+                                    candidates.iter().map(String::as_str),
+                                )),
+                            );
                         }
                     }
                 }
@@ -0,0 +1,181 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Analysis of group template declarations and group declarations
+
+use super::analyze::*;
+use super::declarative::get_entity_class;
+use super::names::*;
+use super::scope::*;
+use crate::ast::*;
+use crate::data::*;
+use crate::named_entity::*;
+
+impl<'a> AnalyzeContext<'a> {
+    /// LRM 6.9 Group template declarations
+    pub fn analyze_group_template_declaration(
+        &self,
+        scope: &Scope<'a>,
+        parent: EntRef<'a>,
+        template: &mut GroupTemplateDeclaration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let last = template.entity_classes.len().saturating_sub(1);
+        if template
+            .entity_classes
+            .iter()
+            .enumerate()
+            .any(|(i, entry)| entry.is_box && i != last)
+        {
+            diagnostics.error(
+                template.ident.tree.pos(),
+                "Only the last entity class entry of a group template may contain '<>'",
+            );
+        }
+
+        let ent = self.arena.define(
+            &mut template.ident,
+            parent,
+            AnyEntKind::Group(Group::Template(template.entity_classes.clone())),
+        );
+        scope.add(ent, diagnostics);
+    }
+
+    /// LRM 6.10 Group declarations
+    pub fn analyze_group_declaration(
+        &self,
+        scope: &Scope<'a>,
+        parent: EntRef<'a>,
+        group: &mut GroupDeclaration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let template_name = &mut group.template_name;
+        let Some(resolved) = as_fatal(self.name_resolve(
+            scope,
+            &template_name.pos,
+            &mut template_name.item,
+            diagnostics,
+        ))?
+        else {
+            return Ok(());
+        };
+
+        let (template, entries) = match resolved {
+            ResolvedName::Final(ent) => match ent.actual_kind() {
+                AnyEntKind::Group(Group::Template(entries)) => (ent, entries),
+                _ => {
+                    diagnostics.error(
+                        &template_name.pos,
+                        format!("Expected group template, got {}", ent.describe()),
+                    );
+                    return Ok(());
+                }
+            },
+            other => {
+                diagnostics.error(
+                    &template_name.pos,
+                    format!("Expected group template, got {}", other.describe()),
+                );
+                return Ok(());
+            }
+        };
+
+        let has_box = entries.last().is_some_and(|entry| entry.is_box);
+        let required = entries.len() - usize::from(has_box);
+        if group.constituents.len() < required
+            || (!has_box && group.constituents.len() > entries.len())
+        {
+            diagnostics.error(
+                &template_name.pos,
+                format!(
+                    "Group template '{}' expects {}{} constituents, got {}",
+                    template.designator(),
+                    if has_box { "at least " } else { "" },
+                    required,
+                    group.constituents.len()
+                ),
+            );
+        }
+
+        for (i, constituent) in group.constituents.iter_mut().enumerate() {
+            let Some(class) = entries
+                .get(i.min(entries.len().saturating_sub(1)))
+                .map(|entry| entry.class)
+            else {
+                continue;
+            };
+            self.analyze_group_constituent(scope, constituent, class, diagnostics)?;
+        }
+
+        let ent = self.arena.define(
+            &mut group.ident,
+            parent,
+            AnyEntKind::Group(Group::Declaration(template)),
+        );
+        scope.add(ent, diagnostics);
+        Ok(())
+    }
+
+    fn analyze_group_constituent(
+        &self,
+        scope: &Scope<'a>,
+        constituent: &mut WithPos<Name>,
+        class: EntityClass,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let Some(resolved) = as_fatal(self.name_resolve(
+            scope,
+            &constituent.pos,
+            &mut constituent.item,
+            diagnostics,
+        ))?
+        else {
+            return Ok(());
+        };
+
+        let candidates: Vec<EntRef<'a>> = match resolved {
+            ResolvedName::Final(ent) => vec![ent],
+            ResolvedName::Type(typ) => vec![typ.into()],
+            ResolvedName::Design(design) => vec![design.into()],
+            ResolvedName::ObjectName(ObjectName {
+                base: ObjectBase::Object(obj),
+                type_mark: None,
+            }) => vec![*obj],
+            ResolvedName::ObjectName(ObjectName {
+                base: ObjectBase::DeferredConstant(ent),
+                type_mark: None,
+            }) => vec![ent],
+            ResolvedName::Overloaded(_, overloaded) => {
+                if let Some(ent) = overloaded.as_unique() {
+                    constituent.set_unique_reference(ent);
+                }
+                overloaded.entities().map(|ent| ent.into()).collect()
+            }
+            other => {
+                diagnostics.error(
+                    &constituent.pos,
+                    format!("{} cannot be a group constituent", other.describe()),
+                );
+                return Ok(());
+            }
+        };
+
+        if !candidates
+            .iter()
+            .any(|ent| get_entity_class(ent.as_actual()) == Some(class))
+        {
+            let described = match candidates.as_slice() {
+                [ent] => ent.describe(),
+                _ => format!("'{}'", constituent.item),
+            };
+            diagnostics.error(
+                &constituent.pos,
+                format!("{described} is not of class {class}"),
+            );
+        }
+        Ok(())
+    }
+}
@@ -15,6 +15,7 @@ use super::scope::*;
 use crate::ast::*;
 use crate::data::*;
 use crate::named_entity::*;
+use crate::standard::VHDLStandard;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ObjectBase<'a> {
@@ -164,6 +165,9 @@ impl<'a> ResolvedName<'a> {
             AnyEntKind::File(_)
             | AnyEntKind::InterfaceFile(_)
             | AnyEntKind::Component(_)
+            | AnyEntKind::View(_)
+            | AnyEntKind::Psl(_)
+            | AnyEntKind::Group(_)
             | AnyEntKind::PhysicalLiteral(_) => ResolvedName::Final(ent),
             AnyEntKind::Design(_)
             | AnyEntKind::Library
@@ -223,6 +227,9 @@ impl<'a> ResolvedName<'a> {
             | AnyEntKind::Concurrent(_)
             | AnyEntKind::Sequential(_)
             | AnyEntKind::LoopParameter(_)
+            | AnyEntKind::View(_)
+            | AnyEntKind::Psl(_)
+            | AnyEntKind::Group(_)
             | AnyEntKind::PhysicalLiteral(_) => ResolvedName::Final(ent),
             AnyEntKind::Attribute(_) | AnyEntKind::ElementDeclaration(_) => {
                 return Err(format!(
@@ -801,7 +808,10 @@ impl<'a> AnalyzeContext<'a> {
                     self.expr_with_ttyp(scope, typ, expr, diagnostics)?;
                 }
 
-                if typ.is_scalar() {
+                // VHDL-2019 extends 'image to composite types
+                let is_composite =
+                    matches!(typ.base().kind(), Type::Array { .. } | Type::Record(_));
+                if typ.is_scalar() || (is_composite && self.standard() >= VHDLStandard::VHDL2019) {
                     Ok(AttrResolveResult::Value(self.string().base()))
                 } else {
                     diagnostics.push(Diagnostic::cannot_be_prefix_of_attribute(
@@ -976,6 +986,37 @@ impl<'a> AnalyzeContext<'a> {
                 diagnostics.error(name_pos, "Range cannot be used as an expression");
                 Err(EvalError::Unknown)
             }
+            AttributeDesignator::Converse => {
+                diagnostics.error(
+                    name_pos,
+                    "Attribute 'converse may only be used in a mode view indication",
+                );
+                Err(EvalError::Unknown)
+            }
+            AttributeDesignator::Reflect => {
+                // File objects resolve to final names that have no type mark
+                let typ = match prefix {
+                    ResolvedName::Final(ent) => match ent.actual_kind() {
+                        AnyEntKind::File(subtype) => subtype.type_mark(),
+                        _ => prefix.as_type_of_attr_prefix(prefix_pos, attr, diagnostics)?,
+                    },
+                    _ => prefix.as_type_of_attr_prefix(prefix_pos, attr, diagnostics)?,
+                };
+                check_no_attr_argument(attr, diagnostics);
+                // T'reflect gives a subtype mirror while O'reflect gives a value mirror
+                let mirror = if matches!(prefix, ResolvedName::Type(_)) {
+                    "subtype_mirror"
+                } else {
+                    "value_mirror"
+                };
+                let name = match reflection_type_class(typ) {
+                    Some(class) => format!("{class}_{mirror}"),
+                    None => mirror.to_owned(),
+                };
+                let typ =
+                    catch_analysis_err(self.reflection_mirror_type(name_pos, &name), diagnostics)?;
+                Ok(AttrResolveResult::Value(typ.base()))
+            }
             AttributeDesignator::Type(attr) => self
                 .resolve_type_attribute_suffix(prefix, &attr, name_pos, diagnostics)
                 .map(|typ| AttrResolveResult::Type(typ.base())),
@@ -1679,6 +1720,15 @@ impl Declaration {
             Declaration::Use(_) => "use",
             Declaration::Package(_) => "package instantiation",
             Declaration::Configuration(_) => "configuration",
+            Declaration::View(_) => "view",
+            Declaration::Psl(PslDeclaration::Named(decl)) => match decl.kind {
+                PslNamedKind::Property => "property",
+                PslNamedKind::Sequence => "sequence",
+            },
+            Declaration::Psl(PslDeclaration::DefaultClock(_)) => "default clock",
+            Declaration::Disconnection(_) => "disconnection specification",
+            Declaration::GroupTemplate(_) => "group template",
+            Declaration::Group(_) => "group",
         }
     }
 }
@@ -1768,6 +1818,23 @@ impl Diagnostic {
     }
 }
 
+/// The type class prefix of the std.reflection mirror types, such as `integer` for `integer_value_mirror`.
+/// Returns `None` when the class is not known, for example for generic types
+fn reflection_type_class(typ: TypeEnt) -> Option<&'static str> {
+    match typ.base().kind() {
+        Type::Enum(_) => Some("enumeration"),
+        Type::Integer | Type::Universal(UniversalType::Integer) => Some("integer"),
+        Type::Real | Type::Universal(UniversalType::Real) => Some("floating"),
+        Type::Physical => Some("physical"),
+        Type::Record(_) => Some("record"),
+        Type::Array { .. } => Some("array"),
+        Type::Access(_) => Some("access"),
+        Type::File => Some("file"),
+        Type::Protected(..) => Some("protected"),
+        Type::Incomplete | Type::Subtype(_) | Type::Interface | Type::Alias(_) => None,
+    }
+}
+
 fn check_no_attr_argument(suffix: &AttributeSuffix, diagnostics: &mut dyn DiagnosticHandler) {
     if let Some(ref expr) = suffix.expr {
         diagnostics.error(
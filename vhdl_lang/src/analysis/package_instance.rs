@@ -346,6 +346,12 @@ impl<'a> AnalyzeContext<'a> {
                 AnyEntKind::DeferredConstant(self.map_subtype(mapping, *subtype)?)
             }
             AnyEntKind::Library => AnyEntKind::Library,
+            AnyEntKind::Psl(psl) => AnyEntKind::Psl(*psl),
+            AnyEntKind::Group(group) => AnyEntKind::Group(group.clone()),
+            AnyEntKind::View(view) => AnyEntKind::View(View {
+                typ: self.map_type_ent(mapping, view.typ),
+                elements: view.elements.clone(),
+            }),
             AnyEntKind::Design(design) => match design {
                 Design::PackageInstance(region) => AnyEntKind::Design(Design::PackageInstance(
                     self.map_region(parent, mapping, region)?,
@@ -538,6 +544,7 @@ impl<'a> AnalyzeContext<'a> {
             iface,
             subtype,
             has_default,
+            signal_kind,
         } = obj;
 
         Ok(Object {
@@ -545,6 +552,7 @@ impl<'a> AnalyzeContext<'a> {
             iface: *iface,
             subtype: self.map_subtype(mapping, *subtype)?,
             has_default: *has_default,
+            signal_kind: *signal_kind,
         })
     }
 
@@ -0,0 +1,137 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Analysis of PSL declarations and directives embedded in VHDL (LRM 15.10)
+//!
+//! The temporal layer of PSL is not type checked,
+//! only the VHDL names referenced by properties and sequences are resolved.
+
+use super::analyze::*;
+use super::scope::*;
+use crate::ast::*;
+use crate::data::*;
+use crate::named_entity::*;
+
+impl<'a> AnalyzeContext<'a> {
+    pub fn analyze_psl_expression(
+        &self,
+        scope: &Scope<'a>,
+        expression: &mut PslExpression,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for name in expression.names.iter_mut() {
+            match scope.lookup(&name.pos, &name.item.item) {
+                Ok(visible) => name.item.set_reference(&visible),
+                Err(diagnostic) => diagnostics.push(diagnostic),
+            }
+        }
+    }
+
+    pub fn analyze_psl_declaration(
+        &self,
+        scope: &Scope<'a>,
+        parent: EntRef<'a>,
+        decl: &mut PslDeclaration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        match decl {
+            PslDeclaration::Named(named) => {
+                let kind = match named.kind {
+                    PslNamedKind::Property => Psl::Property,
+                    PslNamedKind::Sequence => Psl::Sequence,
+                };
+                let ent = named
+                    .ident
+                    .define(self.arena, parent, AnyEntKind::Psl(kind));
+
+                let nested = scope.nested();
+                for parameter in named.parameters.iter_mut() {
+                    nested.add(
+                        parameter.define(self.arena, ent, AnyEntKind::Psl(Psl::Parameter)),
+                        diagnostics,
+                    );
+                }
+                self.analyze_psl_expression(&nested, &mut named.expression, diagnostics);
+
+                scope.add(ent, diagnostics);
+            }
+            PslDeclaration::DefaultClock(default_clock) => {
+                self.analyze_psl_expression(scope, &mut default_clock.clock, diagnostics);
+            }
+        }
+    }
+
+    fn psl_property(&self, scope: &Scope<'a>, pos: &SrcPos, name: &mut Name) -> bool {
+        let Some(designator) = as_simple_name_mut(name) else {
+            return false;
+        };
+        let Ok(visible) = scope.lookup(pos, &designator.item) else {
+            return false;
+        };
+        match visible.into_non_overloaded() {
+            Ok(ent) if matches!(ent.kind(), AnyEntKind::Psl(_)) => {
+                designator.set_unique_reference(ent);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// A concurrent assertion of a property instance such as `assert p;` or `assert p(a, b);`
+    /// parses as a VHDL assertion, returns true if the condition was analyzed as such an instance.
+    pub fn analyze_psl_property_instance(
+        &self,
+        scope: &Scope<'a>,
+        condition: &mut WithPos<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<bool> {
+        let Some(name) = as_name_mut(&mut condition.item) else {
+            return Ok(false);
+        };
+        match name {
+            Name::Designator(_) => Ok(self.psl_property(scope, &condition.pos, name)),
+            Name::CallOrIndexed(ref mut fcall) => {
+                if !self.psl_property(scope, &fcall.name.pos, &mut fcall.name.item) {
+                    return Ok(false);
+                }
+                for parameter in fcall.parameters.iter_mut() {
+                    if let ActualPart::Expression(ref mut expr) = parameter.actual.item {
+                        self.expr_pos_unknown_ttyp(
+                            scope,
+                            &parameter.actual.pos,
+                            expr,
+                            diagnostics,
+                        )?;
+                    }
+                }
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    pub fn analyze_psl_directive(
+        &self,
+        scope: &Scope<'a>,
+        directive: &mut PslDirective,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let PslDirective {
+            kind: _,
+            property,
+            report,
+            severity,
+        } = directive;
+        self.analyze_psl_expression(scope, property, diagnostics);
+        if let Some(expr) = report {
+            self.expr_with_ttyp(scope, self.string(), expr, diagnostics)?;
+        }
+        if let Some(expr) = severity {
+            self.expr_with_ttyp(scope, self.severity_level(), expr, diagnostics)?;
+        }
+        Ok(())
+    }
+}
@@ -17,16 +17,6 @@ use crate::data::*;
 use crate::named_entity::*;
 
 impl<'a> AnalyzeContext<'a> {
-    pub fn range_unknown_typ(
-        &self,
-        scope: &Scope<'a>,
-        range: &mut Range,
-        diagnostics: &mut dyn DiagnosticHandler,
-    ) -> FatalResult {
-        as_fatal(self.range_type(scope, range, diagnostics))?;
-        Ok(())
-    }
-
     pub fn drange_unknown_type(
         &self,
         scope: &Scope<'a>,
@@ -13,6 +13,7 @@ use crate::named_entity::*;
 use crate::ast::search::*;
 use crate::ast::*;
 use crate::data::*;
+use crate::standard::VHDLStandard;
 use crate::syntax::{Symbols, Token, TokenAccess};
 use fnv::{FnvHashMap, FnvHashSet};
 use parking_lot::RwLock;
@@ -69,6 +70,23 @@ impl LockedUnit {
             tokens,
         }
     }
+
+    /// The name of the unit within its library, architectures are named `entity(architecture)`
+    /// and package bodies `package(body)`
+    pub fn display_name(&self) -> String {
+        let unit_id = &self.unit_id;
+        match self.kind() {
+            AnyKind::Secondary(SecondaryKind::Architecture) => format!(
+                "{}({})",
+                unit_id.primary_name(),
+                unit_id.secondary_name().unwrap_or(unit_id.primary_name())
+            ),
+            AnyKind::Secondary(SecondaryKind::PackageBody) => {
+                format!("{}(body)", unit_id.primary_name())
+            }
+            AnyKind::Primary(_) => unit_id.primary_name().to_string(),
+        }
+    }
 }
 
 impl HasIdent for LockedUnit {
@@ -99,6 +117,9 @@ pub struct Library {
     /// Design units which were not added since they were duplicates.
     /// They need to be kept for later refresh which might make them not duplicates.
     duplicates: Vec<(SrcPos, LockedUnit)>,
+
+    /// Revision of the language the library is analyzed according to
+    standard: VHDLStandard,
 }
 
 impl Library {
@@ -122,6 +143,7 @@ impl Library {
             added: FnvHashSet::default(),
             removed: FnvHashSet::default(),
             duplicates: Vec::new(),
+            standard: VHDLStandard::default(),
         }
     }
 
@@ -129,6 +151,22 @@ impl Library {
         &self.name
     }
 
+    pub fn standard(&self) -> VHDLStandard {
+        self.standard
+    }
+
+    fn set_standard(&mut self, standard: VHDLStandard) {
+        if self.standard != standard {
+            self.standard = standard;
+
+            // Re-analyze all units as if they were changed
+            for unit in self.units.values() {
+                self.added.insert(unit.unit_id().clone());
+                self.removed.insert(unit.unit_id().clone());
+            }
+        }
+    }
+
     fn add_design_unit(&mut self, unit: LockedUnit) {
         let unit_id = unit.unit_id().clone();
         match self.units.entry(unit.key().clone()) {
@@ -253,6 +291,10 @@ impl Library {
         self.id
     }
 
+    pub(crate) fn units(&self) -> impl Iterator<Item = &LockedUnit> {
+        self.units.values()
+    }
+
     pub(crate) fn primary_units(&self) -> impl Iterator<Item = &LockedUnit> {
         self.units.iter().filter_map(|(key, value)| match key {
             UnitKey::Primary(_) => Some(value),
@@ -359,6 +401,18 @@ impl DesignRoot {
         self.libraries.get(sym)
     }
 
+    /// Set the revision of the language that the library is analyzed according to
+    pub fn set_standard(&mut self, library_name: Symbol, standard: VHDLStandard) {
+        self.get_or_create_library(library_name)
+            .set_standard(standard);
+    }
+
+    pub fn standard_of(&self, library_name: &Symbol) -> VHDLStandard {
+        self.get_lib(library_name)
+            .map(|library| library.standard())
+            .unwrap_or_default()
+    }
+
     pub(crate) fn get_design_entity<'a>(
         &'a self,
         library_name: &Symbol,
@@ -469,7 +523,7 @@ impl DesignRoot {
                                 )
                             }
                             // Find all architectures which implement the entity
-                            AnyEntKind::Design(Design::Architecture(ent_of_arch)) => {
+                            AnyEntKind::Design(Design::Architecture(_, _, ent_of_arch)) => {
                                 ent_of_arch.id == ent_id
                             }
                             _ => false,
@@ -687,6 +741,19 @@ impl DesignRoot {
             })
     }
 
+    /// The design unit that the position is within, if any
+    pub(crate) fn unit_at<'a>(&'a self, pos: &'a SrcPos) -> Option<&'a LockedUnit> {
+        self.units_by_source(&pos.source)
+            .filter(|unit| {
+                let (Some(first), Some(last)) = (unit.tokens.first(), unit.tokens.last()) else {
+                    return false;
+                };
+                first.pos.start() <= pos.start() && pos.end() <= last.pos.end()
+            })
+            // The same file may be part of several libraries
+            .min_by_key(|unit| unit.unit_id().library_name().name_utf8())
+    }
+
     /// Search all units in a source file denoted by `source`.
     pub fn search_source(&self, source: &Source, searcher: &mut impl Searcher) -> SearchResult {
         for unit in self.units_by_source(source) {
@@ -824,6 +891,20 @@ impl DesignRoot {
         }
     }
 
+    /// The units that each unit directly depends on, as registered during analysis
+    pub(super) fn unit_dependencies(&self) -> FnvHashMap<UnitId, FnvHashSet<UnitId>> {
+        let mut dependencies: FnvHashMap<UnitId, FnvHashSet<UnitId>> = FnvHashMap::default();
+        for (unit_id, users) in self.users_of.read().iter() {
+            for user in users.iter() {
+                dependencies
+                    .entry(user.clone())
+                    .or_default()
+                    .insert(unit_id.clone());
+            }
+        }
+        dependencies
+    }
+
     /// Register a dependency of library unit for everything within library since .all was used
     pub(super) fn make_use_of_library_all(&self, user: &UnitId, library_name: &Symbol) {
         match self
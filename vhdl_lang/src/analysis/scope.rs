@@ -4,6 +4,7 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
+use super::suggestions::closest_designators;
 use crate::ast::*;
 use crate::data::*;
 use crate::named_entity::*;
@@ -128,6 +129,15 @@ impl<'a> ScopeInner<'a> {
         visible.into_unambiguous(pos, designator)
     }
 
+    /// Collect the designators of everything declared in or visible from this region
+    fn visible_designators_into(&self, designators: &mut Vec<Designator>) {
+        designators.extend(self.region.entities.keys().cloned());
+        self.region.visibility.designators_into(designators);
+        if let Some(ref parent) = self.parent {
+            parent.0.borrow().visible_designators_into(designators);
+        }
+    }
+
     /// Lookup a designator from within the region itself
     /// Thus all parent regions and visibility is relevant
     fn lookup_uncached(
@@ -158,24 +168,32 @@ impl<'a> ScopeInner<'a> {
 
         match result {
             Some(visible) => Ok(visible),
-            None => Err(Diagnostic::error(
-                pos,
-                match designator {
-                    Designator::Identifier(ident) => {
-                        format!("No declaration of '{ident}'")
-                    }
-                    Designator::OperatorSymbol(operator) => {
-                        format!("No declaration of operator '{operator}'")
-                    }
-                    Designator::Character(chr) => {
-                        format!("No declaration of '{chr}'")
-                    }
-                    Designator::Anonymous(_) => "No declaration of <anonymous>".to_owned(),
-                },
-            )),
+            None => Err(self.no_declaration_error(pos, designator)),
         }
     }
 
+    fn no_declaration_error(&self, pos: &SrcPos, designator: &Designator) -> Diagnostic {
+        let mut candidates = Vec::new();
+        self.visible_designators_into(&mut candidates);
+
+        Diagnostic::error(
+            pos,
+            match designator {
+                Designator::Identifier(ident) => {
+                    format!("No declaration of '{ident}'")
+                }
+                Designator::OperatorSymbol(operator) => {
+                    format!("No declaration of operator '{operator}'")
+                }
+                Designator::Character(chr) => {
+                    format!("No declaration of '{chr}'")
+                }
+                Designator::Anonymous(_) => "No declaration of <anonymous>".to_owned(),
+            },
+        )
+        .with_suggestions(closest_designators(designator, &candidates))
+    }
+
     fn lookup(
         &mut self,
         pos: &SrcPos,
@@ -8,6 +8,7 @@ use super::names::ResolvedName;
 use super::overloaded::Disambiguated;
 use super::overloaded::SubprogramKind;
 use super::scope::*;
+use super::suggestions::closest_designators;
 use crate::ast::*;
 use crate::data::*;
 use crate::named_entity::*;
@@ -327,6 +328,20 @@ impl Diagnostic {
         pos: &SrcPos,
         suffix: &Designator,
     ) -> Diagnostic {
+        let candidates: Vec<Designator> = match named_entity.actual_kind() {
+            AnyEntKind::Type(Type::Record(region)) => region
+                .iter()
+                .map(|elem| elem.designator().clone())
+                .collect(),
+            AnyEntKind::Type(Type::Protected(region, _))
+            | AnyEntKind::Design(Design::Package(_, region))
+            | AnyEntKind::Design(Design::UninstPackage(_, region))
+            | AnyEntKind::Design(Design::PackageInstance(region)) => {
+                region.entities.keys().cloned().collect()
+            }
+            _ => Vec::new(),
+        };
+
         Diagnostic::error(
             pos,
             format!(
@@ -335,5 +350,6 @@ impl Diagnostic {
                 named_entity.describe(),
             ),
         )
+        .with_suggestions(closest_designators(suffix, &candidates))
     }
 }
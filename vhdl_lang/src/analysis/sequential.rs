@@ -114,7 +114,12 @@ impl<'a> AnalyzeContext<'a> {
                 match SequentialRoot::from(parent) {
                     SequentialRoot::Function(ttyp) => {
                         if let Some(ref mut expression) = expression {
-                            self.expr_with_ttyp(scope, ttyp, expression, diagnostics)?;
+                            self.analyze_expr_right_hand(
+                                scope,
+                                Some(ttyp),
+                                expression,
+                                diagnostics,
+                            )?;
                         } else {
                             diagnostics.error(
                                 &statement.statement.pos,
@@ -218,6 +223,15 @@ impl<'a> AnalyzeContext<'a> {
                 for conditional in conditionals {
                     let Conditional { condition, item } = conditional;
                     self.boolean_expr(scope, condition, diagnostics)?;
+                    if let Some(value) = self.static_boolean(&condition.item) {
+                        diagnostics.push(
+                            Diagnostic::warning(
+                                &condition.pos,
+                                format!("Condition is always {value}"),
+                            )
+                            .with_code(DiagnosticCode::ConstantCondition),
+                        );
+                    }
                     self.analyze_sequential_part(scope, parent, item, diagnostics)?;
                 }
                 if let Some(else_item) = else_item {
@@ -247,6 +261,15 @@ impl<'a> AnalyzeContext<'a> {
                 match iteration_scheme {
                     Some(IterationScheme::For(ref mut index, ref mut drange)) => {
                         let typ = as_fatal(self.drange_type(scope, drange, diagnostics))?;
+                        if let Some(pos) = self.static_null_range(drange) {
+                            diagnostics.push(
+                                Diagnostic::warning(
+                                    pos,
+                                    "Loop range is statically null, the loop body is never executed",
+                                )
+                                .with_code(DiagnosticCode::Unreachable),
+                            );
+                        }
                         let region = scope.nested();
                         region.add(
                             self.arena
@@ -267,6 +290,17 @@ impl<'a> AnalyzeContext<'a> {
             SequentialStatement::ProcedureCall(ref mut pcall) => {
                 self.analyze_procedure_call(scope, pcall, diagnostics)?;
             }
+            SequentialStatement::SequentialBlock(ref mut block) => {
+                let SequentialBlock {
+                    decl,
+                    statements,
+                    end_label_pos: _,
+                } = block;
+                let nested = scope.nested();
+                self.define_labels_for_sequential_part(&nested, parent, statements, diagnostics)?;
+                self.analyze_declarative_part(&nested, parent, decl, diagnostics)?;
+                self.analyze_sequential_part(&nested, parent, statements, diagnostics)?;
+            }
             SequentialStatement::SignalAssignment(ref mut assign) => {
                 // @TODO more
                 let SignalAssignment { target, rhs, .. } = assign;
@@ -371,10 +405,75 @@ impl<'a> AnalyzeContext<'a> {
             self.analyze_sequential_statement(scope, parent, statement, diagnostics)?;
         }
 
+        check_unreachable_statements(parent, statements, diagnostics);
+
         Ok(())
     }
 }
 
+/// Returns a description of the statement if control never continues to the next statement.
+/// Jumps that are illegal at this location are ignored since they are already reported as errors.
+fn unconditional_jump(parent: EntRef, statement: &SequentialStatement) -> Option<&'static str> {
+    match statement {
+        SequentialStatement::Return(_) => {
+            if matches!(SequentialRoot::from(parent), SequentialRoot::Process) {
+                None
+            } else {
+                Some("return")
+            }
+        }
+        SequentialStatement::Exit(ExitStatement {
+            condition: None,
+            loop_label,
+        }) if find_outer_loop(parent, loop_label.as_ref().map(|label| label.item.name())) => {
+            Some("exit")
+        }
+        SequentialStatement::Next(NextStatement {
+            condition: None,
+            loop_label,
+        }) if find_outer_loop(parent, loop_label.as_ref().map(|label| label.item.name())) => {
+            Some("next")
+        }
+        SequentialStatement::Wait(WaitStatement {
+            sensitivity_clause,
+            condition_clause: None,
+            timeout_clause: None,
+        }) if sensitivity_clause.is_empty() => Some("wait"),
+        _ => None,
+    }
+}
+
+fn labeled_statement_pos(statement: &LabeledSequentialStatement) -> SrcPos {
+    if let Some(ref label) = statement.label.tree {
+        label.pos.combine(&statement.statement.pos)
+    } else {
+        statement.statement.pos.clone()
+    }
+}
+
+/// Warn about statements that follow a statement that unconditionally transfers control
+fn check_unreachable_statements(
+    parent: EntRef,
+    statements: &[LabeledSequentialStatement],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    for (idx, statement) in statements.iter().enumerate() {
+        if let Some(kind) = unconditional_jump(parent, &statement.statement.item) {
+            if let (Some(first), Some(last)) = (statements.get(idx + 1), statements.last()) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        labeled_statement_pos(first).combine(&labeled_statement_pos(last)),
+                        format!("Unreachable code after {kind} statement"),
+                    )
+                    .with_code(DiagnosticCode::Unreachable)
+                    .with_tag(DiagnosticTag::Unnecessary),
+                );
+            }
+            return;
+        }
+    }
+}
+
 enum SequentialRoot<'a> {
     Process,
     Procedure,
@@ -14,6 +14,7 @@ use crate::syntax::Symbols;
 
 use super::analyze::AnalyzeContext;
 use crate::named_entity::*;
+use crate::standard::VHDLStandard;
 
 #[derive(Clone, Copy)]
 pub(crate) struct UniversalTypes {
@@ -449,6 +450,7 @@ impl<'a> AnalyzeContext<'a> {
                             iface: Some(ObjectInterface::Parameter(Mode::Out)),
                             subtype: Subtype::new(type_mark),
                             has_default: false,
+                            signal_kind: None,
                         }),
                     ),
                 ],
@@ -533,6 +535,7 @@ impl<'a> AnalyzeContext<'a> {
                     iface: Some(ObjectInterface::Parameter(Mode::InOut)),
                     subtype: Subtype::new(type_ent.to_owned()),
                     has_default: false,
+                    signal_kind: None,
                 }),
             )],
             None,
@@ -737,6 +740,11 @@ impl<'a> AnalyzeContext<'a> {
             self.comparison(Operator::NE, typ),
         ]
         .into_iter()
+        .chain(if self.standard() >= VHDLStandard::VHDL2019 {
+            Some(self.create_to_string(typ)).into_iter()
+        } else {
+            None.into_iter()
+        })
     }
 
     fn concatenations(
@@ -796,12 +804,15 @@ impl<'a> AnalyzeContext<'a> {
             self.comparison(Operator::NE, typ),
         ]
         .into_iter()
-        .chain(if is_one_dimensional && is_character_elem {
-            // To string is only defined for 1d array types with character elements
-            Some(self.create_to_string(typ)).into_iter()
-        } else {
-            None.into_iter()
-        })
+        .chain(
+            if is_one_dimensional && is_character_elem || self.standard() >= VHDLStandard::VHDL2019
+            {
+                // Before VHDL-2019 to string is only defined for 1d array types with character elements
+                Some(self.create_to_string(typ)).into_iter()
+            } else {
+                None.into_iter()
+            },
+        )
         .chain(
             (if is_one_dimensional {
                 Some(self.concatenations(typ, *elem_type))
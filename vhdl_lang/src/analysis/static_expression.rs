@@ -1,5 +1,11 @@
+use super::analyze::AnalyzeContext;
 use crate::analysis::static_expression::BitStringConversionError::EmptySignedExpansion;
-use crate::ast::{BaseSpecifier, BitString};
+use crate::ast::{
+    AbstractLiteral, BaseSpecifier, BitString, Designator, Direction, DiscreteRange, Expression,
+    Literal, Name, Operator, Range, WithRef,
+};
+use crate::data::{SrcPos, WithPos};
+use crate::named_entity::{AnyEntKind, Overloaded};
 use crate::Latin1String;
 use itertools::Itertools;
 use std::cmp::Ordering;
@@ -295,6 +301,194 @@ pub(crate) fn bit_string_to_string(
     }
 }
 
+/// The value of an expression that could be evaluated at analysis time
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) enum StaticValue {
+    Boolean(bool),
+    Integer(i64),
+    Real(f64),
+}
+
+impl<'a> AnalyzeContext<'a> {
+    /// Evaluates an analyzed expression to a static value.
+    ///
+    /// Only literals, the literals of the predefined type BOOLEAN and the predefined operators
+    /// are evaluated. Returns `None` for everything whose value is not known until elaboration,
+    /// such as generics or function calls.
+    pub(crate) fn static_value(&self, expr: &Expression) -> Option<StaticValue> {
+        match expr {
+            Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Integer(value))) => {
+                Some(StaticValue::Integer(i64::try_from(*value).ok()?))
+            }
+            Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Real(value))) => {
+                Some(StaticValue::Real(*value))
+            }
+            Expression::Name(name) => self.static_name_value(name),
+            Expression::Unary(op, operand) => {
+                if !self.is_predefined_operator(op) {
+                    return None;
+                }
+                static_unary(op.item.item, self.static_value(&operand.item)?)
+            }
+            Expression::Binary(op, left, right) => {
+                if !self.is_predefined_operator(op) {
+                    return None;
+                }
+                let left = self.static_value(&left.item);
+                let right = self.static_value(&right.item);
+
+                // The short circuit operators have a static value when either side is known
+                match (op.item.item, left, right) {
+                    (Operator::And, Some(StaticValue::Boolean(false)), _)
+                    | (Operator::And, _, Some(StaticValue::Boolean(false))) => {
+                        return Some(StaticValue::Boolean(false));
+                    }
+                    (Operator::Or, Some(StaticValue::Boolean(true)), _)
+                    | (Operator::Or, _, Some(StaticValue::Boolean(true))) => {
+                        return Some(StaticValue::Boolean(true));
+                    }
+                    _ => {}
+                }
+
+                static_binary(op.item.item, left?, right?)
+            }
+            _ => None,
+        }
+    }
+
+    /// Evaluates an analyzed expression to a static boolean value
+    pub(crate) fn static_boolean(&self, expr: &Expression) -> Option<bool> {
+        if let Some(StaticValue::Boolean(value)) = self.static_value(expr) {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the position of a range whose bounds are static and which denotes a null range
+    pub(crate) fn static_null_range(&self, drange: &DiscreteRange) -> Option<SrcPos> {
+        let constraint = match drange {
+            DiscreteRange::Range(Range::Range(constraint))
+            | DiscreteRange::Discrete(_, Some(Range::Range(constraint))) => constraint,
+            _ => return None,
+        };
+
+        let left = self.static_value(&constraint.left_expr.item)?;
+        let right = self.static_value(&constraint.right_expr.item)?;
+        let ordering = match (left, right) {
+            (StaticValue::Integer(left), StaticValue::Integer(right)) => left.cmp(&right),
+            (StaticValue::Real(left), StaticValue::Real(right)) => left.partial_cmp(&right)?,
+            _ => return None,
+        };
+
+        let is_null = match constraint.direction {
+            Direction::Ascending => ordering == Ordering::Greater,
+            Direction::Descending => ordering == Ordering::Less,
+        };
+
+        if is_null {
+            Some(constraint.left_expr.pos.combine(&constraint.right_expr.pos))
+        } else {
+            None
+        }
+    }
+
+    fn static_name_value(&self, name: &Name) -> Option<StaticValue> {
+        if let Name::Designator(designator) = name {
+            let ent = self.arena.get(designator.reference.get()?);
+            if let AnyEntKind::Overloaded(Overloaded::EnumLiteral(signature)) = ent.kind() {
+                if signature.return_type()?.base() == self.boolean().base() {
+                    let is_true =
+                        ent.designator() == &Designator::Identifier(self.root.symbol_utf8("true"));
+                    return Some(StaticValue::Boolean(is_true));
+                }
+            }
+        }
+        None
+    }
+
+    /// An operator that could not be resolved is assumed to be predefined
+    /// since only the universal operators remain ambiguous after analysis
+    fn is_predefined_operator(&self, op: &WithPos<WithRef<Operator>>) -> bool {
+        if let Some(id) = op.item.reference.get() {
+            self.arena.get(id).is_implicit()
+        } else {
+            true
+        }
+    }
+}
+
+fn static_unary(op: Operator, value: StaticValue) -> Option<StaticValue> {
+    use StaticValue::*;
+    match (op, value) {
+        (Operator::Not, Boolean(value)) => Some(Boolean(!value)),
+        (Operator::Plus, Integer(_) | Real(_)) => Some(value),
+        (Operator::Minus, Integer(value)) => Some(Integer(value.checked_neg()?)),
+        (Operator::Minus, Real(value)) => Some(Real(-value)),
+        (Operator::Abs, Integer(value)) => Some(Integer(value.checked_abs()?)),
+        (Operator::Abs, Real(value)) => Some(Real(value.abs())),
+        _ => None,
+    }
+}
+
+fn static_binary(op: Operator, left: StaticValue, right: StaticValue) -> Option<StaticValue> {
+    use StaticValue::*;
+    let value = match (left, right) {
+        (Boolean(left), Boolean(right)) => Boolean(match op {
+            Operator::And => left && right,
+            Operator::Or => left || right,
+            Operator::Nand => !(left && right),
+            Operator::Nor => !(left || right),
+            Operator::Xor => left != right,
+            Operator::Xnor => left == right,
+            _ => return static_compare(op, left.cmp(&right)),
+        }),
+        (Integer(left), Integer(right)) => Integer(match op {
+            Operator::Plus => left.checked_add(right)?,
+            Operator::Minus => left.checked_sub(right)?,
+            Operator::Times => left.checked_mul(right)?,
+            // VHDL integer division truncates towards zero
+            Operator::Div => left.checked_div(right)?,
+            Operator::Rem => left.checked_rem(right)?,
+            Operator::Mod => {
+                let rem = left.checked_rem(right)?;
+                if rem != 0 && (rem < 0) != (right < 0) {
+                    rem + right
+                } else {
+                    rem
+                }
+            }
+            Operator::Pow => left.checked_pow(u32::try_from(right).ok()?)?,
+            _ => return static_compare(op, left.cmp(&right)),
+        }),
+        (Real(left), Real(right)) => Real(match op {
+            Operator::Plus => left + right,
+            Operator::Minus => left - right,
+            Operator::Times => left * right,
+            Operator::Div => left / right,
+            _ => return static_compare(op, left.partial_cmp(&right)?),
+        }),
+        (Real(left), Integer(right)) if op == Operator::Pow => {
+            Real(left.powi(i32::try_from(right).ok()?))
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn static_compare(op: Operator, ordering: Ordering) -> Option<StaticValue> {
+    let value = match op {
+        Operator::EQ => ordering == Ordering::Equal,
+        Operator::NE => ordering != Ordering::Equal,
+        Operator::LT => ordering == Ordering::Less,
+        Operator::LTE => ordering != Ordering::Greater,
+        Operator::GT => ordering == Ordering::Greater,
+        Operator::GTE => ordering != Ordering::Less,
+        _ => return None,
+    };
+    Some(StaticValue::Boolean(value))
+}
+
 #[cfg(test)]
 mod test_mod {
     use crate::analysis::static_expression::{bit_string_to_string, BitStringConversionError};
@@ -67,8 +67,20 @@ impl<'a> AnalyzeContext<'a> {
                     diagnostics,
                 );
                 let return_type =
-                    self.resolve_type_mark(&subpgm_region, &mut fun.return_type, diagnostics);
-                (Signature::new(params?, Some(return_type?)), generic_map)
+                    self.resolve_type_mark(&subpgm_region, &mut fun.return_type, diagnostics)?;
+                if let Some(ref mut ident) = fun.return_identifier {
+                    // The return identifier denotes the subtype of the returned value
+                    let subtype = TypeEnt::define_with_opt_id(
+                        self.arena,
+                        None,
+                        ident,
+                        ent,
+                        None,
+                        Type::Subtype(Subtype::new(return_type)),
+                    );
+                    subpgm_region.add(subtype.into(), diagnostics);
+                }
+                (Signature::new(params?, Some(return_type)), generic_map)
             }
             SubprogramSpecification::Procedure(procedure) => {
                 let generic_map = if let Some(header) = &mut procedure.header {
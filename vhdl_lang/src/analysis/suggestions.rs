@@ -0,0 +1,164 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! "Did you mean" suggestions for names that could not be resolved
+
+use crate::ast::Designator;
+use crate::data::*;
+
+/// The maximum number of names suggested for a single misspelled name
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Separates the suggestions from the message of a diagnostic
+const SUGGESTIONS_SEPARATOR: &str = ", did you mean ";
+
+/// Edit distance between two names, ignoring case.
+///
+/// Swapping two adjacent characters counts as a single edit since it is a common typo.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().flat_map(char::to_lowercase).collect();
+    let b: Vec<char> = b.chars().flat_map(char::to_lowercase).collect();
+
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 0..a.len() {
+        current[0] = i + 1;
+        for j in 0..b.len() {
+            let substitution = previous[j] + usize::from(a[i] != b[j]);
+            let mut distance = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+            if i > 0 && j > 0 && a[i] == b[j - 1] && a[i - 1] == b[j] {
+                distance = distance.min(before_previous[j - 1] + 1);
+            }
+            current[j + 1] = distance;
+        }
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// The candidates closest to `name`, best match first.
+///
+/// Candidates further away than a third of the length of the name are not considered similar,
+/// thus names shorter than three characters get no suggestions.
+pub(crate) fn closest_names<'n>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'n str>,
+) -> Vec<String> {
+    let max_distance = name.chars().count() / 3;
+    if max_distance == 0 {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let distance = edit_distance(name, candidate);
+            (distance > 0 && distance <= max_distance).then_some((distance, candidate))
+        })
+        .collect();
+    scored.sort_unstable();
+    scored.dedup_by(|(_, a), (_, b)| a.eq_ignore_ascii_case(b));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.to_owned())
+        .collect()
+}
+
+/// The identifiers among the designators that are closest to `designator`
+pub(crate) fn closest_designators<'d>(
+    designator: &Designator,
+    candidates: impl IntoIterator<Item = &'d Designator>,
+) -> Vec<String> {
+    let Designator::Identifier(name) = designator else {
+        return Vec::new();
+    };
+    let candidates: Vec<String> = candidates
+        .into_iter()
+        .filter_map(|candidate| match candidate {
+            Designator::Identifier(sym) => Some(sym.name_utf8()),
+            _ => None,
+        })
+        .collect();
+    closest_names(&name.name_utf8(), candidates.iter().map(String::as_str))
+}
+
+impl Diagnostic {
+    /// Suggest replacing the name at the position of the diagnostic with one of the suggestions
+    pub(crate) fn with_suggestions(mut self, suggestions: Vec<String>) -> Diagnostic {
+        let Some((last, first)) = suggestions.split_last() else {
+            return self;
+        };
+
+        let quoted = |name: &String| format!("'{name}'");
+        let alternatives = if first.is_empty() {
+            quoted(last)
+        } else {
+            format!(
+                "{} or {}",
+                first.iter().map(quoted).collect::<Vec<_>>().join(", "),
+                quoted(last)
+            )
+        };
+        self.message = format!("{}{SUGGESTIONS_SEPARATOR}{}?", self.message, alternatives);
+
+        for suggestion in suggestions {
+            self.add_fix(self.pos.clone(), suggestion);
+        }
+        self
+    }
+
+    /// The message without any suggestions, which depend on the names that happen to be visible
+    pub(crate) fn message_without_suggestions(&self) -> &str {
+        if self.fixes.is_empty() {
+            return &self.message;
+        }
+        self.message
+            .rfind(SUGGESTIONS_SEPARATOR)
+            .map_or(&self.message, |end| &self.message[..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distance_ignores_case() {
+        assert_eq!(edit_distance("counter", "COUNTER"), 0);
+        assert_eq!(edit_distance("countr", "counter"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("mylbi", "mylib"), 1);
+    }
+
+    #[test]
+    fn closest_names_are_sorted_by_distance() {
+        assert_eq!(
+            closest_names("data_in", ["data_out", "data_i", "dat_in", "clk"]),
+            vec!["dat_in".to_owned(), "data_i".to_owned()]
+        );
+    }
+
+    #[test]
+    fn short_names_allow_a_single_edit() {
+        assert_eq!(
+            closest_names("clk", ["clk2", "ck", "rst", "clock"]),
+            vec!["ck".to_owned(), "clk2".to_owned()]
+        );
+    }
+
+    #[test]
+    fn at_most_three_suggestions() {
+        assert_eq!(
+            closest_names("sig", ["sig0", "sig1", "sig2", "sig3"]).len(),
+            MAX_SUGGESTIONS
+        );
+    }
+}
@@ -62,6 +62,20 @@ impl<'a> AnalyzeContext<'a> {
                     assignment_type.to_str()
                 ),
             ));
+        } else if let Some(mode) = self.view_mode_of_name(target) {
+            let is_input = match mode {
+                ElementMode::Simple(mode) => mode == Mode::In,
+                ElementMode::View(view) | ElementMode::Array(view) => view.has_input_element(),
+            };
+            if is_input {
+                diagnostics.push(Diagnostic::error(
+                    target_pos,
+                    format!(
+                        "Element of {} with mode in may not be the target of an assignment",
+                        object_name.base.describe()
+                    ),
+                ));
+            }
         }
         Ok(object_name.type_mark())
     }
@@ -5,6 +5,7 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::VHDLStandard;
 
 #[test]
 fn overloaded_name_may_not_be_assignment_target() {
@@ -176,8 +177,10 @@ end architecture;
 #[test]
 fn objects_may_be_assignment_target() {
     let mut builder = LibraryBuilder::new();
-    builder.code(
+    // Shared variables of non-protected type are only legal in VHDL-1993
+    builder.code_with_standard(
         "libname",
+        VHDLStandard::VHDL1993,
         "
 entity ent is
 end entity;
@@ -0,0 +1,332 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{CompileOrderFile, CompileOrderScope};
+use pretty_assertions::assert_eq;
+
+fn file(library: &str, code: &Code) -> CompileOrderFile {
+    CompileOrderFile {
+        library: library.to_owned(),
+        file_name: code.source().file_name().to_owned(),
+    }
+}
+
+#[test]
+fn files_of_library_come_after_their_dependencies() {
+    let mut builder = LibraryBuilder::new();
+    let arch = builder.code(
+        "libname",
+        "
+architecture a of ent is
+  signal sig : work.pkg.typ;
+begin
+end architecture;
+",
+    );
+    let ent = builder.code(
+        "libname",
+        "
+use work.pkg.all;
+
+entity ent is
+end entity;
+",
+    );
+    let pkg = builder.code(
+        "libname",
+        "
+package pkg is
+  subtype typ is natural;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.compile_order(&CompileOrderScope::Library("libname".to_owned())),
+        Ok(vec![
+            file("libname", &pkg),
+            file("libname", &ent),
+            file("libname", &arch)
+        ])
+    );
+}
+
+#[test]
+fn files_reachable_from_top_unit() {
+    let mut builder = LibraryBuilder::new();
+    let top = builder.code(
+        "top_lib",
+        "
+library ieee, child_lib;
+use ieee.std_logic_1164.all;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal sig : std_logic;
+begin
+  inst: entity child_lib.child;
+end architecture;
+",
+    );
+    builder.code(
+        "child_lib",
+        "
+entity unrelated is
+end entity;
+",
+    );
+    let child_arch = builder.code(
+        "child_lib",
+        "
+architecture a of child is
+begin
+end architecture;
+",
+    );
+    let child = builder.code(
+        "child_lib",
+        "
+entity child is
+end entity;
+",
+    );
+    builder.add_std_logic_1164();
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.compile_order(&CompileOrderScope::Top(
+            "top_lib".to_owned(),
+            "top".to_owned()
+        )),
+        Ok(vec![
+            file("child_lib", &child),
+            file("child_lib", &child_arch),
+            file("top_lib", &top)
+        ])
+    );
+}
+
+#[test]
+fn files_of_entities_bound_by_configurations_come_first() {
+    let mut builder = LibraryBuilder::new();
+    let cfg = builder.code_with_file_name(
+        "libname",
+        "a_cfg.vhd",
+        "
+configuration cfg of top is
+  for rtl
+    for u1 : comp
+      use entity work.leaf(rtl);
+    end for;
+  end for;
+end configuration;
+",
+    );
+    let top = builder.code_with_file_name(
+        "libname",
+        "b_top.vhd",
+        "
+entity top is
+end entity;
+
+architecture rtl of top is
+  component comp is
+  end component;
+begin
+  u1: comp;
+end architecture;
+",
+    );
+    let leaf = builder.code_with_file_name(
+        "libname",
+        "z_leaf.vhd",
+        "
+entity leaf is
+end entity;
+
+architecture rtl of leaf is
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let library_order = root
+        .compile_order(&CompileOrderScope::Library("libname".to_owned()))
+        .unwrap();
+    let position_of = |code: &Code| {
+        library_order
+            .iter()
+            .position(|other| *other == file("libname", code))
+            .unwrap()
+    };
+    assert!(position_of(&leaf) < position_of(&cfg));
+    assert!(position_of(&top) < position_of(&cfg));
+
+    assert_eq!(
+        root.compile_order(&CompileOrderScope::Top(
+            "libname".to_owned(),
+            "cfg".to_owned()
+        )),
+        Ok(vec![
+            file("libname", &top),
+            file("libname", &leaf),
+            file("libname", &cfg)
+        ])
+    );
+}
+
+#[test]
+fn files_of_entities_bound_to_components_by_default_come_first() {
+    let mut builder = LibraryBuilder::new();
+    let top = builder.code_with_file_name(
+        "libname",
+        "a_top.vhd",
+        "
+entity top is
+end entity;
+
+architecture rtl of top is
+  component sub is
+    port (d : in bit);
+  end component;
+  component other is
+    port (d : in bit);
+  end component;
+  for u2 : other use entity work.leaf;
+begin
+  u1: sub port map (d => '0');
+  u2: other port map (d => '0');
+end architecture;
+",
+    );
+    let sub = builder.code_with_file_name(
+        "libname",
+        "b_sub.vhd",
+        "
+entity sub is
+  port (d : in bit);
+end entity;
+
+architecture rtl of sub is
+begin
+end architecture;
+",
+    );
+    let leaf = builder.code_with_file_name(
+        "libname",
+        "c_leaf.vhd",
+        "
+entity leaf is
+  port (d : in bit);
+end entity;
+",
+    );
+    // An entity of the same name as a component that is bound to another entity is not used
+    builder.code_with_file_name(
+        "libname",
+        "d_other.vhd",
+        "
+entity other is
+  port (d : in bit);
+end entity;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.compile_order(&CompileOrderScope::Top(
+            "libname".to_owned(),
+            "top".to_owned()
+        )),
+        Ok(vec![
+            file("libname", &sub),
+            file("libname", &leaf),
+            file("libname", &top)
+        ])
+    );
+}
+
+#[test]
+fn files_that_depend_on_each_other_are_an_error() {
+    let mut builder = LibraryBuilder::new();
+    let first = builder.code(
+        "libname",
+        "
+package pkg1 is
+  constant c1 : natural := 0;
+end package;
+
+use work.pkg2.all;
+
+package pkg3 is
+  constant c3 : natural := c2;
+end package;
+",
+    );
+    let second = builder.code(
+        "libname",
+        "
+use work.pkg1.all;
+
+package pkg2 is
+  constant c2 : natural := c1;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    // Files are visited in the order of their names
+    let mut file_names = [
+        first.source().file_name().to_string_lossy(),
+        second.source().file_name().to_string_lossy(),
+    ];
+    file_names.sort();
+    let [a, b] = file_names;
+    assert_eq!(
+        root.compile_order(&CompileOrderScope::Library("libname".to_owned())),
+        Err(format!(
+            "Circular dependency between files: {a} -> {b} -> {a}"
+        ))
+    );
+}
+
+#[test]
+fn unknown_scope_is_an_error() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+",
+    );
+
+    let (root, _) = builder.get_analyzed_root();
+    assert_eq!(
+        root.compile_order(&CompileOrderScope::Library("missing".to_owned())),
+        Err("No library named 'missing'".to_owned())
+    );
+    assert_eq!(
+        root.compile_order(&CompileOrderScope::Top(
+            "libname".to_owned(),
+            "missing".to_owned()
+        )),
+        Err("No design unit named 'libname.missing'".to_owned())
+    );
+}
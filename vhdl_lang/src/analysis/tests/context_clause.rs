@@ -366,15 +366,18 @@ end architecture;
             Diagnostic::error(
                 code.s("pkg1", 1),
                 "No primary unit 'pkg1' within library 'libname'",
-            ),
+            )
+            .with_suggestions(vec!["pkg".to_owned()]),
             Diagnostic::error(
                 code.s("pkg1", 2),
                 "No primary unit 'pkg1' within library 'libname'",
-            ),
+            )
+            .with_suggestions(vec!["pkg".to_owned()]),
             Diagnostic::error(
                 code.s("pkg1", 3),
                 "No primary unit 'pkg1' within library 'libname'",
-            ),
+            )
+            .with_suggestions(vec!["pkg".to_owned()]),
         ],
     )
 }
@@ -499,7 +502,8 @@ end package;
         vec![Diagnostic::error(
             code.s1("const2"),
             "No declaration of 'const2' within package 'pkg'",
-        )],
+        )
+        .with_suggestions(vec!["const".to_owned()])],
     );
 }
 #[test]
@@ -526,7 +530,8 @@ end package;
         vec![Diagnostic::error(
             code.s1("const2"),
             "No declaration of 'const2' within package 'pkg'",
-        )],
+        )
+        .with_suggestions(vec!["const".to_owned()])],
     );
 }
 
@@ -561,7 +566,8 @@ end package;
             Diagnostic::error(
                 code.s1("const2"),
                 "No declaration of 'const2' within package instance 'ipkg'",
-            ),
+            )
+            .with_suggestions(vec!["const".to_owned()]),
         ],
     );
 }
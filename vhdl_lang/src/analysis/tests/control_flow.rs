@@ -0,0 +1,256 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::{DiagnosticCode, DiagnosticTag};
+
+#[test]
+fn unreachable_code_after_return() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  procedure proc(x : inout natural) is
+  begin
+    return;
+    x := 0;
+    x := 1;
+  end;
+end package body;
+        ",
+    );
+    let (_, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("x := 0;").pos().combine(&code.s1("x := 1;")),
+            "Unreachable code after return statement",
+        )
+        .with_code(DiagnosticCode::Unreachable)
+        .with_tag(DiagnosticTag::Unnecessary)],
+    )
+}
+
+#[test]
+fn unreachable_code_after_exit_next_and_wait() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+begin
+  process
+  begin
+    loop
+      exit;
+      s <= 0;
+    end loop;
+
+    lp: loop
+      loop
+        next lp;
+        s <= 1;
+      end loop;
+    end loop;
+
+    wait;
+    s <= 2;
+  end process;
+end architecture;
+        ",
+    );
+    let (_, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(code.s1("s <= 0;"), "Unreachable code after exit statement")
+                .with_code(DiagnosticCode::Unreachable)
+                .with_tag(DiagnosticTag::Unnecessary),
+            Diagnostic::warning(code.s1("s <= 1;"), "Unreachable code after next statement")
+                .with_code(DiagnosticCode::Unreachable)
+                .with_tag(DiagnosticTag::Unnecessary),
+            Diagnostic::warning(code.s1("s <= 2;"), "Unreachable code after wait statement")
+                .with_code(DiagnosticCode::Unreachable)
+                .with_tag(DiagnosticTag::Unnecessary),
+        ],
+    )
+}
+
+#[test]
+fn conditional_jumps_do_not_cause_unreachable_code() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+begin
+  process
+  begin
+    loop
+      exit when s = 0;
+      next when s = 1;
+      s <= 0;
+    end loop;
+
+    wait until s = 1;
+    s <= 2;
+    wait for 1 ns;
+    s <= 3;
+    wait on s;
+  end process;
+end architecture;
+        ",
+    );
+    let (_, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn statically_constant_if_conditions() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+begin
+  process
+  begin
+    if s = 0 then
+    elsif 1 + 1 = 2 then
+    elsif not true and s = 1 then
+    end if;
+    wait;
+  end process;
+end architecture;
+        ",
+    );
+    let (_, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(code.s1("1 + 1 = 2"), "Condition is always true")
+                .with_code(DiagnosticCode::ConstantCondition),
+            Diagnostic::warning(code.s1("not true and s = 1"), "Condition is always false")
+                .with_code(DiagnosticCode::ConstantCondition),
+        ],
+    )
+}
+
+#[test]
+fn statically_null_loop_range() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+begin
+  process
+  begin
+    for i in 3 to 0 loop
+    end loop;
+    for i in 0 downto 3 loop
+    end loop;
+    for i in 3 downto 0 loop
+    end loop;
+    for i in 0 to s loop
+    end loop;
+    wait;
+  end process;
+end architecture;
+        ",
+    );
+    let (_, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("3 to 0"),
+                "Loop range is statically null, the loop body is never executed",
+            )
+            .with_code(DiagnosticCode::Unreachable),
+            Diagnostic::warning(
+                code.s1("0 downto 3"),
+                "Loop range is statically null, the loop body is never executed",
+            )
+            .with_code(DiagnosticCode::Unreachable),
+        ],
+    )
+}
+
+#[test]
+fn inactive_generate_branches() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (g : boolean);
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+begin
+  gen0: if false generate
+    s <= 0;
+  elsif g generate
+    s <= 1;
+  elsif true generate
+    s <= 2;
+  else generate
+    s <= 3;
+  end generate;
+
+  gen1: for i in 1 to 0 generate
+    s <= 4;
+  end generate;
+end architecture;
+        ",
+    );
+    let (_, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::hint(
+                code.s1("s <= 0;"),
+                "Inactive generate branch, the condition is statically false",
+            )
+            .with_code(DiagnosticCode::Unreachable)
+            .with_tag(DiagnosticTag::Unnecessary),
+            Diagnostic::hint(
+                code.s1("s <= 3;"),
+                "Inactive generate branch, a previous condition is statically true",
+            )
+            .with_code(DiagnosticCode::Unreachable)
+            .with_tag(DiagnosticTag::Unnecessary),
+            Diagnostic::hint(
+                code.s1("s <= 4;"),
+                "Inactive generate branch, the range is statically null",
+            )
+            .with_code(DiagnosticCode::Unreachable)
+            .with_tag(DiagnosticTag::Unnecessary),
+        ],
+    )
+}
@@ -319,3 +319,91 @@ end architecture;
         )],
     );
 }
+
+#[test]
+fn attribute_specification_of_all_entity_classes() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    attribute myattr : boolean;
+
+    type enum_t is (alpha, beta);
+    type distance_t is range 0 to 1000
+      units
+        mm;
+        m = 1000 mm;
+      end units;
+    file f : std.textio.text;
+    group pair_t is (signal, signal);
+    signal s0, s1 : bit;
+    group pair : pair_t (s0, s1);
+
+    attribute myattr of alpha : literal is true;
+    attribute myattr of m : units is true;
+    attribute myattr of f : file is true;
+    attribute myattr of pair : group is true;
+    attribute myattr of lbl : label is true;
+begin
+    lbl : s0 <= s1;
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn attribute_specification_of_character_literal() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    type enum_t is ('x', 'y');
+    attribute myattr : boolean;
+    attribute myattr of 'x' [return enum_t] : literal is true;
+begin
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn group_template_is_not_of_class_group() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    attribute myattr : boolean;
+    group pair_t is (signal, signal);
+    attribute myattr of pair_t : group is true;
+begin
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("pair_t", 2),
+            "group template 'pair_t' is not of class group",
+        )],
+    );
+}
@@ -0,0 +1,213 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{DependencyEdge, DependencyGraph, DependencyKind};
+use pretty_assertions::assert_eq;
+
+fn edge(from: &str, to: &str, kind: DependencyKind) -> DependencyEdge {
+    DependencyEdge {
+        from: from.to_owned(),
+        to: to.to_owned(),
+        kind,
+    }
+}
+
+fn node_ids(graph: &DependencyGraph) -> Vec<String> {
+    graph.nodes.iter().map(|node| node.id()).collect()
+}
+
+fn design() -> LibraryBuilder {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "lib",
+        "
+package pkg is
+  constant c : natural := 0;
+end package;
+
+package body pkg is
+end package body;
+
+package gen_pkg is
+  generic (width : natural);
+end package;
+
+package inst_pkg is new work.gen_pkg generic map (width => 8);
+
+entity child is
+end entity;
+
+architecture rtl of child is
+begin
+end architecture;
+
+use work.pkg.all;
+
+entity top is
+end entity;
+
+architecture rtl of top is
+  component comp is
+  end component;
+begin
+  inst: entity work.child;
+  inst2: comp;
+end architecture;
+
+configuration cfg of top is
+  for rtl
+    for inst2: comp
+      use entity work.child(rtl);
+    end for;
+  end for;
+end configuration;
+
+entity unrelated is
+end entity;
+",
+    );
+    builder
+}
+
+#[test]
+fn edges_are_labeled_with_their_kind() {
+    let (root, diagnostics) = design().get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let graph = root.dependency_graph(None).unwrap();
+
+    assert_eq!(
+        node_ids(&graph),
+        vec![
+            "lib.cfg",
+            "lib.child",
+            "lib.child(rtl)",
+            "lib.gen_pkg",
+            "lib.inst_pkg",
+            "lib.pkg",
+            "lib.pkg(body)",
+            "lib.top",
+            "lib.top(rtl)",
+            "lib.unrelated",
+            "std.env",
+            "std.textio",
+        ]
+    );
+    assert_eq!(
+        graph.edges,
+        vec![
+            edge("lib.cfg", "lib.child", DependencyKind::Configuration),
+            edge("lib.cfg", "lib.child(rtl)", DependencyKind::Configuration),
+            edge("lib.cfg", "lib.top", DependencyKind::Configuration),
+            edge("lib.child(rtl)", "lib.child", DependencyKind::Implements),
+            edge("lib.inst_pkg", "lib.gen_pkg", DependencyKind::Instantiation),
+            edge("lib.pkg(body)", "lib.pkg", DependencyKind::Implements),
+            edge("lib.top", "lib.pkg", DependencyKind::Use),
+            edge("lib.top(rtl)", "lib.child", DependencyKind::Instantiation),
+            edge("lib.top(rtl)", "lib.top", DependencyKind::Implements),
+        ]
+    );
+}
+
+#[test]
+fn component_instantiations_are_edges_to_the_entity_bound_by_default() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "lib",
+        "
+entity sub is
+  port (d : in bit);
+end entity;
+
+entity top is
+end entity;
+
+architecture rtl of top is
+  component sub is
+    port (d : in bit);
+  end component;
+begin
+  inst: sub port map (d => '0');
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let graph = root.dependency_graph(Some(("lib", "top"))).unwrap();
+
+    assert_eq!(
+        graph.edges,
+        vec![
+            edge("lib.top(rtl)", "lib.sub", DependencyKind::Instantiation),
+            edge("lib.top(rtl)", "lib.top", DependencyKind::Implements),
+        ]
+    );
+}
+
+#[test]
+fn subgraph_reachable_from_root() {
+    let (root, diagnostics) = design().get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let graph = root.dependency_graph(Some(("lib", "top"))).unwrap();
+
+    assert_eq!(
+        node_ids(&graph),
+        vec![
+            "lib.child",
+            "lib.child(rtl)",
+            "lib.pkg",
+            "lib.pkg(body)",
+            "lib.top",
+            "lib.top(rtl)",
+        ]
+    );
+    assert_eq!(
+        root.dependency_graph(Some(("lib", "missing"))),
+        Err("No design unit named 'lib.missing'".to_owned())
+    );
+}
+
+#[test]
+fn dot_has_a_cluster_for_each_library() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "lib1",
+        "
+package pkg is
+end package;
+",
+    );
+    builder.code(
+        "lib2",
+        "
+library lib1;
+use lib1.pkg.all;
+
+entity ent is
+end entity;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let graph = root.dependency_graph(Some(("lib2", "ent"))).unwrap();
+
+    assert_eq!(
+        graph.to_dot(),
+        r#"digraph dependencies {
+  node [shape=box];
+  subgraph "cluster_lib1" {
+    label="lib1";
+    "lib1.pkg" [label="pkg\npackage"];
+  }
+  subgraph "cluster_lib2" {
+    label="lib2";
+    "lib2.ent" [label="ent\nentity"];
+  }
+  "lib2.ent" -> "lib1.pkg" [label="use"];
+}
+"#
+    );
+}
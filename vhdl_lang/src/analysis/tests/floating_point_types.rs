@@ -0,0 +1,174 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::named_entity::{AnyEntKind, Type};
+
+#[test]
+fn real_range_declares_floating_point_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    constant lim : real := 10.0;
+    type voltage is range -lim to lim;
+    type count is range 0 to 10;
+end package;
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let voltage = root
+        .search_reference(code.source(), code.s1("voltage").start())
+        .unwrap();
+    assert!(matches!(voltage.kind(), AnyEntKind::Type(Type::Real)));
+
+    let count = root
+        .search_reference(code.source(), code.s1("count").start())
+        .unwrap();
+    assert!(matches!(count.kind(), AnyEntKind::Type(Type::Integer)));
+}
+
+#[test]
+fn floating_point_type_has_implicit_operators_and_attributes() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+    type voltage is range -10.0 to 10.0;
+    subtype small_voltage is voltage range -1.0 to 1.0;
+
+    constant hi : voltage := voltage'high;
+    constant lo : voltage := voltage'low;
+    constant l : voltage := voltage'left;
+    constant r : voltage := voltage'right;
+    constant small : small_voltage := small_voltage'high;
+    constant ascending : boolean := voltage'ascending;
+
+    constant arith : voltage := hi + lo * 2.0 - hi / 2.0;
+    constant power : voltage := hi ** 2;
+    constant absolute : voltage := abs lo;
+    constant negated : voltage := -hi;
+    constant compare : boolean := hi > lo;
+    constant max : voltage := maximum(hi, lo);
+    constant img : string := voltage'image(hi);
+    constant val : voltage := voltage'value(\"1.0\");
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn floating_point_type_conversion_between_closely_related_types() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+    type voltage is range -10.0 to 10.0;
+    type current is range 0.0 to 1.0e3;
+
+    constant hi : voltage := voltage'high;
+    constant c0 : current := current(hi);
+    constant c1 : real := real(hi);
+    constant c2 : integer := integer(hi);
+    constant c3 : voltage := voltage(3);
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn floating_point_types_are_distinct() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    type voltage is range -10.0 to 10.0;
+    type current is range 0.0 to 1.0e3;
+
+    constant v : voltage := 1.0;
+    constant c : current := v;
+    constant m : voltage := v mod v;
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1(":= v;").s1("v"),
+                "constant 'v' of real type 'voltage' does not match real type 'current'",
+            ),
+            Diagnostic::error(code.s1("mod"), "Found no match for operator \"mod\""),
+        ],
+    );
+}
+
+#[test]
+fn floating_point_type_is_not_discrete() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    type voltage is range -10.0 to 10.0;
+    type arr_t is array (voltage) of bit;
+    constant p : integer := voltage'pos(1.0);
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("(voltage)").s1("voltage"),
+                "Non-discrete real type 'voltage' cannot be used in discrete range",
+            ),
+            Diagnostic::error(
+                code.s1("voltage'pos(1.0)"),
+                "real type 'voltage' cannot be the the prefix of 'pos attribute",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn numeric_range_bounds_must_have_same_type_class() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    type mixed is range 0.0 to 10;
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("0.0 to 10"),
+            "Range type mismatch, left is type universal_real, right is type universal_integer",
+        )],
+    );
+}
@@ -0,0 +1,146 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn group_constituents_are_resolved() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    group path_t is (signal, signal <>);
+    signal clk, d, q : bit;
+    group path : path_t (clk, d, q);
+begin
+end architecture;
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("path_t", 2).start()),
+        Some(code.s1("path_t").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("(clk").s1("clk").start()),
+        Some(code.s1("clk").pos())
+    );
+}
+
+#[test]
+fn group_constituent_must_match_entity_class() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    group pair_t is (signal, label);
+    signal s0, s1 : bit;
+    group pair : pair_t (s0, s1);
+begin
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("s1", 2),
+            "signal 's1' is not of class label",
+        )],
+    );
+}
+
+#[test]
+fn group_constituents_must_match_template_length() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    group pair_t is (signal, signal);
+    group many_t is (signal, signal, signal <>);
+    signal s0, s1, s2 : bit;
+    group pair : pair_t (s0, s1, s2);
+    group many : many_t (s0);
+begin
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("pair_t", 2),
+                "Group template 'pair_t' expects 2 constituents, got 3",
+            ),
+            Diagnostic::error(
+                code.s("many_t", 2),
+                "Group template 'many_t' expects at least 2 constituents, got 1",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn box_must_be_on_last_entity_class_entry() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    group bad_t is (signal <>, constant);
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("bad_t"),
+            "Only the last entity class entry of a group template may contain '<>'",
+        )],
+    );
+}
+
+#[test]
+fn group_requires_group_template() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    constant c : natural := 0;
+    group grp : c (c);
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("grp : c").s1("c"),
+            "Expected group template, got constant 'c'",
+        )],
+    );
+}
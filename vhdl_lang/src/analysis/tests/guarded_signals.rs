@@ -0,0 +1,174 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn guarded_block_declares_guard_signal() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, d, q : bit;
+  signal b : boolean;
+begin
+  blk : block (clk = '1' and not clk'stable)
+  begin
+    q <= guarded d;
+    b <= not guard;
+  end block;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("not guard").s1("guard").start()),
+        Some(code.s1("clk = '1' and not clk'stable").pos())
+    );
+}
+
+#[test]
+fn guarded_assignment_requires_guard_signal() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal d, q : bit;
+begin
+  q <= guarded d;
+  blk : block
+  begin
+    q <= guarded d;
+  end block;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("q", 2),
+                "Guarded assignment requires a GUARD signal, declared implicitly by a guarded block",
+            ),
+            Diagnostic::error(
+                code.s("q", 3),
+                "Guarded assignment requires a GUARD signal, declared implicitly by a guarded block",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn explicit_guard_signal_allows_guarded_assignment() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal guard : boolean;
+  signal d, q : bit;
+begin
+  q <= guarded d;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn guarded_signals_and_disconnection_specifications() {
+    let mut builder = LibraryBuilder::new();
+    builder.add_std_logic_1164();
+    builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (
+    enable : in boolean;
+    data : inout std_logic bus
+  );
+  disconnect data : std_logic after 1 ns;
+end entity;
+
+architecture a of ent is
+  signal reg : std_logic register;
+  signal b1, b2 : std_logic bus;
+  disconnect reg : std_logic after 2 ns;
+  disconnect b1, b2 : std_logic after 0 ns;
+  disconnect others : std_logic_vector after 1 ns;
+begin
+  blk : block (enable)
+  begin
+    reg <= guarded '1';
+    with reg select
+      b1 <= guarded '0' when '1', 'Z' when others;
+  end block;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn disconnection_specification_errors() {
+    let mut builder = LibraryBuilder::new();
+    builder.add_std_logic_1164();
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal plain : std_logic;
+  signal guarded_sig : std_logic bus;
+  disconnect plain : std_logic after 1 ns;
+  disconnect guarded_sig : bit after 1 ns;
+  disconnect guarded_sig : std_logic after 1;
+begin
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("disconnect plain").s1("plain"),
+                "signal 'plain' is not a guarded signal",
+            ),
+            Diagnostic::error(
+                code.s1(": bit").s1("bit"),
+                "type 'BIT' does not match the type of signal 'guarded_sig'",
+            ),
+            Diagnostic::error(
+                code.s1("after 1;").s1("1"),
+                "integer literal does not match physical type 'TIME'",
+            ),
+        ],
+    );
+}
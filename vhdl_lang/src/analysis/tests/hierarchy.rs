@@ -5,6 +5,7 @@
 //! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::data::DiagnosticCode;
 use crate::EntHierarchy;
 use crate::Source;
 use pretty_assertions::assert_eq;
@@ -44,7 +45,13 @@ end architecture;
     );
 
     let (root, diagnostics) = builder.get_analyzed_root();
-    check_no_diagnostics(&diagnostics);
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(code.s1("false"), "Condition is always false")
+                .with_code(DiagnosticCode::ConstantCondition),
+        ],
+    );
     assert_eq!(
         get_hierarchy(&root, "libname", code.source()),
         vec![nested(
@@ -307,8 +307,9 @@ fn forbid_homographs_if_generate() {
         "libname",
         "
 entity ent is
+generic (g : boolean);
 begin
-gen_if: if true generate
+gen_if: if g generate
 constant a1 : natural := 0;
 constant a : natural := 0;
 constant a1 : natural := 0;
@@ -896,16 +897,17 @@ fn alternate_generate_labels_are_homographs_of_inner_declarations() {
         "libname",
         "
 entity ent is
+  generic (g : boolean);
 end entity;
 
 architecture a of ent is
 begin
-  gen: if alt1: true generate
+  gen: if alt1: g generate
     constant alt1 : boolean := true;
     constant alt2 : boolean := true;
     constant alt3 : boolean := true;
   begin
-  elsif alt2: false generate
+  elsif alt2: not g generate
     constant alt1 : boolean := true;
     constant alt2 : boolean := true;
     constant alt3 : boolean := true;
@@ -7,26 +7,37 @@
 mod assignment_typecheck;
 mod association_formal;
 mod circular_dependencies;
+mod compile_order;
 mod context_clause;
+mod control_flow;
 mod custom_attributes;
 mod declarations;
 mod deferred_constant;
+mod dependency_graph;
+mod floating_point_types;
+mod groups;
+mod guarded_signals;
 mod hierarchy;
 mod homographs;
 mod implicit;
 mod incomplete_type;
 mod incremental_analysis;
+mod mode_view;
 mod package_instance;
 mod protected_type;
+mod psl;
+mod reflection;
 mod resolves_design_units;
 mod resolves_names;
 mod resolves_type_mark;
 mod sensitivity_list;
 mod subprogram_arguments;
 mod subprogram_instance;
+mod suggestions;
 mod tool_directive;
 mod typecheck_expression;
 mod util;
+mod vhdl2019;
 mod visibility;
 
 use std::cell::RefCell;
@@ -0,0 +1,280 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::VHDLStandard;
+
+const BUS_PKG: &str = "
+package bus_pkg is
+  type bus_t is record
+    addr : natural;
+    wdata : natural;
+    rdata : natural;
+  end record;
+
+  view master of bus_t is
+    addr, wdata : out;
+    rdata : in;
+  end view master;
+end package;
+";
+
+#[test]
+fn ports_with_mode_views() {
+    let mut builder = LibraryBuilder::new();
+    builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        &format!(
+            "{BUS_PKG}
+use work.bus_pkg.all;
+
+entity ent is
+  port (
+    m : view master;
+    s : view master'converse of bus_t
+  );
+end entity;
+
+architecture a of ent is
+begin
+  m.addr <= s.addr;
+  m.wdata <= 0;
+  s.rdata <= m.rdata;
+end architecture;"
+        ),
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn input_elements_of_mode_view_may_not_be_assigned() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        &format!(
+            "{BUS_PKG}
+use work.bus_pkg.all;
+
+entity ent is
+  port (
+    m : view master;
+    s : view master'converse
+  );
+end entity;
+
+architecture a of ent is
+begin
+  m.rdata <= 0;
+  s.addr <= 0;
+  s <= m;
+end architecture;"
+        ),
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("m.rdata"),
+                "Element of signal 'm' with mode in may not be the target of an assignment",
+            ),
+            Diagnostic::error(
+                code.s1("s.addr"),
+                "Element of signal 's' with mode in may not be the target of an assignment",
+            ),
+            Diagnostic::error(
+                code.s1("s <= m").s1("s"),
+                "Element of signal 's' with mode in may not be the target of an assignment",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn mode_view_must_give_each_element_a_mode() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+package pkg is
+  type rec_t is record
+    a, b : natural;
+  end record;
+
+  view v1 of rec_t is
+    a : in;
+  end view;
+
+  view v2 of rec_t is
+    a, b : in;
+    a : out;
+    c : out;
+  end view;
+
+  view v3 of natural is
+  end view;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("v1"),
+                "Mode view 'v1' does not give a mode for element 'b'",
+            ),
+            Diagnostic::error(
+                code.s1("a : out").s1("a"),
+                "Duplicate mode for element 'a' in mode view",
+            ),
+            Diagnostic::error(
+                code.s1("c : out").s1("c"),
+                "No declaration of 'c' within record type 'rec_t'",
+            ),
+            Diagnostic::error(
+                code.s1("of natural").s1("natural"),
+                "The type of a mode view must be a record type, got integer type 'INTEGER'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn nested_mode_views() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+package pkg is
+  type inner_t is record
+    req, ack : bit;
+  end record;
+  type inner_arr_t is array (natural range <>) of inner_t;
+
+  type outer_t is record
+    one : inner_t;
+    many : inner_arr_t(0 to 3);
+    flag : bit;
+  end record;
+
+  view inner_v of inner_t is
+    req : out;
+    ack : in;
+  end view;
+
+  view outer_v of outer_t is
+    one : view inner_v;
+    many : view (inner_v'converse);
+    flag : view inner_v;
+  end view;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("flag : view").s1("flag"),
+            "Record element 'flag' is not of record type 'inner_t'",
+        )],
+    );
+}
+
+#[test]
+fn port_subtype_must_match_mode_view() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        &format!(
+            "{BUS_PKG}
+use work.bus_pkg.all;
+
+entity ent is
+  port (
+    m : view master of natural;
+    n : view natural
+  );
+end entity;"
+        ),
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("of natural").s1("natural"),
+                "subtype 'NATURAL' does not match mode view 'master' of record type 'bus_t'",
+            ),
+            Diagnostic::error(
+                code.s1("view natural").s1("natural"),
+                "Expected mode view, got subtype 'NATURAL'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn converse_is_not_an_expression() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+package pkg is
+  constant c : natural := 0;
+  constant d : natural := c'converse;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("c'converse"),
+            "Attribute 'converse may only be used in a mode view indication",
+        )],
+    );
+}
+
+#[test]
+fn search_mode_view_element() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+package pkg is
+  type rec_t is record
+    elem : bit;
+  end record;
+
+  view v of rec_t is
+    elem : in;
+  end view;
+end package;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("elem", 2).start()),
+        Some(code.s1("elem").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("view v").s("v", 2).start()),
+        Some(code.s1("view v").s("v", 2).pos())
+    );
+}
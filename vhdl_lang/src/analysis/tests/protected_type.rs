@@ -5,6 +5,7 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::VHDLStandard;
 
 #[test]
 fn error_on_missing_protected_body() {
@@ -336,3 +337,45 @@ end package body;
         2
     );
 }
+
+#[test]
+fn shared_variable_must_be_of_protected_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+  type prot_t is protected
+  end protected;
+
+  type prot_t is protected body
+  end protected body;
+
+  shared variable var1 : prot_t;
+  shared variable var2 : natural;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("natural"),
+            "Shared variable must be of a protected type since VHDL-2002",
+        )],
+    );
+}
+
+#[test]
+fn shared_variable_of_any_type_in_vhdl_1993() {
+    let mut builder = LibraryBuilder::new();
+    builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL1993,
+        "
+package pkg is
+  shared variable var : natural;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
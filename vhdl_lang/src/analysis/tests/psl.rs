@@ -0,0 +1,208 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::VHDLStandard;
+
+const DESIGN: &str = "
+entity ent is
+  port (clk, req : in bit; ack : out bit);
+end entity;
+
+architecture a of ent is
+  signal busy : bit;
+begin
+  ack <= req;
+end architecture;
+";
+
+#[test]
+fn vunit_bound_to_architecture_sees_its_declarations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2008,
+        &format!(
+            "{DESIGN}
+vunit v (ent(a)) {{
+  default clock is clk'event and clk = '1';
+  property handshake is always req -> next ack;
+  assert handshake;
+  assert always busy -> eventually! ack report \"stuck\";
+}}"
+        ),
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.search_reference_pos(
+            code.source(),
+            code.s1("assert handshake").s1("handshake").start()
+        ),
+        Some(code.s1("property handshake").s1("handshake").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("always busy").s1("busy").start()),
+        Some(code.s1("signal busy").s1("busy").pos())
+    );
+}
+
+#[test]
+fn unknown_names_in_properties_are_reported() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2008,
+        &format!(
+            "{DESIGN}
+vunit v (ent) {{
+  assert always req -> next missing;
+}}"
+        ),
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("missing"),
+            "No declaration of 'missing'",
+        )],
+    );
+}
+
+#[test]
+fn property_parameters_are_visible_in_the_property() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2008,
+        &format!(
+            "{DESIGN}
+vunit v (ent) {{
+  property follows(boolean a, b) is always a -> next b;
+  assert follows(req, ack);
+}}"
+        ),
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("next b").s1("b").start()),
+        Some(code.s1("b)").s1("b").pos())
+    );
+}
+
+#[test]
+fn psl_comment_directive_in_architecture() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2008,
+        "
+entity ent is
+  port (req : in bit; ack : out bit);
+end entity;
+
+architecture a of ent is
+begin
+  ack <= req;
+  -- psl cover {req;
+  --   ack};
+  -- psl assert never undeclared;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("undeclared"),
+            "No declaration of 'undeclared'",
+        )],
+    );
+}
+
+#[test]
+fn vunit_inherits_other_vunit() {
+    let mut builder = LibraryBuilder::new();
+    builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2008,
+        &format!(
+            "{DESIGN}
+vunit base (ent) {{
+  sequence req_ack is {{req; ack}};
+}}
+
+vunit derived (ent) {{
+  inherit base;
+  cover req_ack;
+}}"
+        ),
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn vunit_binding_must_denote_entity() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2008,
+        "
+package pkg is
+end package;
+
+vunit v (pkg) {
+}
+
+vunit w {
+  inherit pkg;
+}",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("(pkg)").s1("pkg"),
+                "package 'pkg' does not denote an entity",
+            ),
+            Diagnostic::error(
+                code.s1("inherit pkg").s1("pkg"),
+                "package 'pkg' is not a verification unit",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn plain_comments_starting_with_psl_are_not_tokenized() {
+    let mut builder = LibraryBuilder::new();
+    builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2008,
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  -- psl checks are done in the formal flow
+  signal busy : bit;
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
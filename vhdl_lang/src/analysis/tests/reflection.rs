@@ -0,0 +1,153 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::VHDLStandard;
+
+#[test]
+fn reflect_gives_mirror_of_each_type_class() {
+    let mut builder = LibraryBuilder::new();
+    builder.add_std_reflection();
+    builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+use std.reflection.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  type enum_t is (alpha, beta);
+  type rec_t is record
+    elem : natural;
+  end record;
+  type arr_t is array (natural range <>) of bit;
+  type ptr_t is access natural;
+  type file_t is file of natural;
+  type prot_t is protected
+  end protected;
+  type prot_t is protected body
+  end protected body;
+begin
+  process
+    variable enum_v : enum_t;
+    variable int_v : integer;
+    variable float_v : real;
+    variable phys_v : time;
+    variable rec_v : rec_t;
+    variable arr_v : arr_t(0 to 3);
+    variable ptr_v : ptr_t;
+    variable prot_v : prot_t;
+    file file_v : file_t;
+
+    variable enum_s : enumeration_subtype_mirror;
+    variable int_s : integer_subtype_mirror;
+    variable float_s : floating_subtype_mirror;
+    variable phys_s : physical_subtype_mirror;
+    variable rec_s : record_subtype_mirror;
+    variable arr_s : array_subtype_mirror;
+    variable ptr_s : access_subtype_mirror;
+    variable file_s : file_subtype_mirror;
+    variable prot_s : protected_subtype_mirror;
+
+    variable enum_m : enumeration_value_mirror;
+    variable int_m : integer_value_mirror;
+    variable float_m : floating_value_mirror;
+    variable phys_m : physical_value_mirror;
+    variable rec_m : record_value_mirror;
+    variable arr_m : array_value_mirror;
+    variable ptr_m : access_value_mirror;
+    variable file_m : file_value_mirror;
+    variable prot_m : protected_value_mirror;
+  begin
+    enum_s := enum_t'reflect;
+    int_s := natural'reflect;
+    float_s := real'reflect;
+    phys_s := time'reflect;
+    rec_s := rec_t'reflect;
+    arr_s := arr_t'reflect;
+    ptr_s := ptr_t'reflect;
+    file_s := file_t'reflect;
+    prot_s := prot_t'reflect;
+
+    enum_m := enum_v'reflect;
+    int_m := int_v'reflect;
+    float_m := float_v'reflect;
+    phys_m := phys_v'reflect;
+    rec_m := rec_v'reflect;
+    arr_m := arr_v'reflect;
+    ptr_m := ptr_v'reflect;
+    file_m := file_v'reflect;
+    prot_m := prot_v'reflect;
+    wait;
+  end process;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn reflect_of_generic_type_gives_generic_mirror() {
+    let mut builder = LibraryBuilder::new();
+    builder.add_std_reflection();
+    builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+use std.reflection.all;
+
+package pkg is
+  generic (type element_t);
+
+  procedure print(value : element_t);
+end package;
+
+package body pkg is
+  procedure print(value : element_t) is
+    variable s : subtype_mirror := element_t'reflect;
+    variable v : value_mirror := value'reflect;
+  begin
+    report s.simple_name;
+    if v.get_value_class = class_record then
+      report v.to_record.get_subtype_mirror.simple_name;
+    end if;
+  end procedure;
+end package body;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn reflect_result_is_type_checked() {
+    let mut builder = LibraryBuilder::new();
+    builder.add_std_reflection();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+use std.reflection.all;
+
+package pkg is
+  constant c : natural := 0;
+  constant m1 : integer_value_mirror := c'reflect;
+  constant m2 : integer_value_mirror := natural'reflect;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("natural'reflect"),
+            "Expression of access type 'integer_subtype_mirror' does not match access type 'integer_value_mirror'",
+        )],
+    );
+}
@@ -2013,14 +2013,15 @@ entity ent1 is
 end entity ent1;
 
 architecture a1 of ent1 is
+  signal cond : boolean;
 begin
   process
   begin
-    if0: if true then
+    if0: if cond then
     end if if0;
 
     loop0: for i in 0 to 1 loop
-      next loop0;
+      next loop0 when cond;
       exit loop0;
     end loop loop0;
 
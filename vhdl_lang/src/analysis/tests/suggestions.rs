@@ -0,0 +1,176 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn suggests_visible_names_for_missing_declaration() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+    signal counter : natural;
+    signal enable : bit;
+begin
+    enable <= '1' when countr = 0 else '0';
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s1("countr"), "No declaration of 'countr'")
+                .with_suggestions(vec!["counter".to_owned()]),
+        ],
+    );
+}
+
+#[test]
+fn suggestion_is_offered_as_fix_of_the_misspelled_name() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    constant data_width : natural := 8;
+    constant addr_width : natural := data_wdth;
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "No declaration of 'data_wdth', did you mean 'data_width'?"
+    );
+    assert_eq!(
+        diagnostics[0].fixes.as_ref(),
+        [(code.s1("data_wdth").pos(), "data_width".to_owned())]
+    );
+}
+
+#[test]
+fn no_suggestions_for_dissimilar_names() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    constant data_width : natural := 8;
+    constant addr_width : natural := missing;
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("missing"),
+            "No declaration of 'missing'",
+        )],
+    );
+}
+
+#[test]
+fn suggests_record_elements() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    type rec_t is record
+        valid : bit;
+        value : natural;
+    end record;
+    constant rec : rec_t := (valid => '0', value => 0);
+    constant c : natural := rec.valeu;
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("valeu"),
+            "No declaration of 'valeu' within record type 'rec_t'",
+        )
+        .with_suggestions(vec!["value".to_owned()])],
+    );
+}
+
+#[test]
+fn suggests_declarations_within_package() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    constant timeout : natural := 10;
+end package;
+
+use work.pkg.timeuot;
+
+entity ent is
+end entity;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("timeuot"),
+            "No declaration of 'timeuot' within package 'pkg'",
+        )
+        .with_suggestions(vec!["timeout".to_owned()])],
+    );
+}
+
+#[test]
+fn suggests_primary_units_and_libraries() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "mylib",
+        "
+package util_pkg is
+end package;
+        ",
+    );
+    let code = builder.code(
+        "libname",
+        "
+library mylbi;
+library mylib;
+use mylib.utl_pkg.all;
+
+entity ent is
+end entity;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s1("mylbi"), "No such library 'mylbi'")
+                .with_suggestions(vec!["mylib".to_owned()]),
+            Diagnostic::error(
+                code.s1("utl_pkg"),
+                "No primary unit 'utl_pkg' within library 'mylib'",
+            )
+            .with_suggestions(vec!["util_pkg".to_owned()]),
+        ],
+    );
+}
@@ -6,15 +6,18 @@
 
 use crate::analysis::DesignRoot;
 use crate::data::*;
+use crate::standard::VHDLStandard;
 use crate::syntax::test::*;
 use crate::syntax::Symbols;
 use pretty_assertions::assert_eq;
 use std::collections::{hash_map::Entry, HashMap};
+use std::path::Path;
 use std::sync::Arc;
 
 pub struct LibraryBuilder {
     code_builder: CodeBuilder,
     libraries: HashMap<Symbol, Vec<Code>>,
+    standards: HashMap<Symbol, VHDLStandard>,
 }
 
 impl LibraryBuilder {
@@ -22,6 +25,7 @@ impl LibraryBuilder {
         LibraryBuilder {
             code_builder: CodeBuilder::new(),
             libraries: HashMap::default(),
+            standards: HashMap::default(),
         }
     }
 
@@ -43,6 +47,29 @@ impl LibraryBuilder {
         code
     }
 
+    /// Add code with a file name, such as when the order of the files matters
+    pub fn code_with_file_name(&mut self, library_name: &str, file_name: &str, code: &str) -> Code {
+        let code = self
+            .code_builder
+            .code_with_file_name(Path::new(file_name), code);
+        self.add_code(library_name, code.clone());
+        code
+    }
+
+    /// Add code to a library that is analyzed according to another revision than the default
+    pub fn code_with_standard(
+        &mut self,
+        library_name: &str,
+        standard: VHDLStandard,
+        code: &str,
+    ) -> Code {
+        let code = self.code_builder.code(code).with_standard(standard);
+        self.standards
+            .insert(self.code_builder.symbol(library_name), standard);
+        self.add_code(library_name, code.clone());
+        code
+    }
+
     /// Just get a Code object using the same symbol table but without adding it to any library
     pub fn snippet(&mut self, code: &str) -> Code {
         self.code_builder.code(code)
@@ -69,6 +96,11 @@ end architecture;"
         self.add_code("ieee", std_logic_1164);
     }
 
+    pub fn add_std_reflection(&mut self) {
+        let reflection = self.code_builder.code_from_source(reflection_package());
+        self.add_code("std", reflection);
+    }
+
     pub fn get_analyzed_root(&self) -> (DesignRoot, Vec<Diagnostic>) {
         let mut root = DesignRoot::new(self.code_builder.symbols.clone());
         let mut diagnostics = Vec::new();
@@ -80,6 +112,9 @@ end architecture;"
                 root.add_design_file(library_name.clone(), code.design_file());
             }
         }
+        for (library_name, standard) in self.standards.iter() {
+            root.set_standard(library_name.clone(), *standard);
+        }
         root.analyze(&mut diagnostics);
 
         (root, diagnostics)
@@ -127,6 +162,16 @@ fn env_package() -> Source {
     )
 }
 
+fn reflection_package() -> Source {
+    Source::inline(
+        Path::new("reflection.vhd"),
+        &Latin1String::new(include_bytes!(
+            "../../../../vhdl_libraries/std/reflection.vhd"
+        ))
+        .to_string(),
+    )
+}
+
 fn std_logic_1164_package() -> Source {
     Source::inline(
         Path::new("std_logic_1164.vhd"),
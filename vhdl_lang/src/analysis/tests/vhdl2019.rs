@@ -0,0 +1,257 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::VHDLStandard;
+
+#[test]
+fn interface_list_may_end_with_semicolon() {
+    let mut builder = LibraryBuilder::new();
+    builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+entity ent is
+  generic (
+    width : natural;
+  );
+  port (
+    clk : in bit;
+    data : out bit_vector(width - 1 downto 0);
+  );
+end entity;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn empty_record() {
+    let mut builder = LibraryBuilder::new();
+    builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+package pkg is
+  type empty_t is record
+  end record;
+
+  signal s : empty_t;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn conditional_variable_assignment_and_return() {
+    let mut builder = LibraryBuilder::new();
+    builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+package pkg is
+  function max(a, b : integer) return integer;
+end package;
+
+package body pkg is
+  function max(a, b : integer) return integer is
+    variable v : integer;
+  begin
+    v := a when a > b else b;
+    return a when a > b else b;
+  end function;
+end package body;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn conditional_return_is_type_checked() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+package pkg is
+  function fun(a : boolean) return integer;
+end package;
+
+package body pkg is
+  function fun(a : boolean) return integer is
+  begin
+    return 0 when a else 'c';
+  end function;
+end package body;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("'c'"),
+            "character literal does not match integer type 'INTEGER'",
+        )],
+    );
+}
+
+#[test]
+fn image_and_to_string_of_composite_types() {
+    let mut builder = LibraryBuilder::new();
+    builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+package pkg is
+  type rec_t is record
+    a : natural;
+    b : bit;
+  end record;
+  type arr_t is array (natural range <>) of rec_t;
+
+  constant r : rec_t := (a => 0, b => '0');
+  constant a : arr_t(0 to 0) := (others => r);
+  constant s1 : string := rec_t'image(r);
+  constant s2 : string := to_string(r);
+  constant s3 : string := arr_t'image(a);
+  constant s4 : string := to_string(a);
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn image_of_composite_type_requires_vhdl_2019() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2008,
+        "
+package pkg is
+  type rec_t is record
+    a : natural;
+  end record;
+
+  constant r : rec_t := (a => 0);
+  constant s : string := rec_t'image(r);
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("rec_t'image(r)"),
+            "record type 'rec_t' cannot be the the prefix of 'image attribute",
+        )],
+    );
+}
+
+#[test]
+fn return_identifier_denotes_subtype_of_return_value() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+package pkg is
+  function fun(arg : bit_vector) return r of bit_vector;
+end package;
+
+package body pkg is
+  function fun(arg : bit_vector) return r of bit_vector is
+    variable v : r(arg'range);
+  begin
+    return v;
+  end function;
+end package body;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("r(arg").start()),
+        Some(code.s("r of", 2).s1("r").pos())
+    );
+}
+
+#[test]
+fn protected_type_with_generics() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+package pkg is
+  type fifo_t is protected
+    generic (type element_t; constant depth : natural);
+    generic map (element_t => bit, depth => 8);
+    procedure push(value : element_t);
+  end protected;
+end package;
+
+package body pkg is
+  type fifo_t is protected body
+    type storage_t is array (0 to depth - 1) of element_t;
+    variable storage : storage_t;
+
+    procedure push(value : element_t) is
+    begin
+      storage(0) := value;
+    end procedure;
+  end protected body;
+end package body;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("element_t", 2).start()),
+        Some(code.s1("element_t").pos())
+    );
+}
+
+#[test]
+fn sequential_block_statement() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code_with_standard(
+        "libname",
+        VHDLStandard::VHDL2019,
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+    variable outer : natural;
+  begin
+    blk: block is
+      variable inner : natural;
+    begin
+      inner := outer;
+    end block blk;
+    outer := inner;
+    wait;
+  end process;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("outer := inner").s1("inner"),
+            "No declaration of 'inner'",
+        )],
+    );
+}
@@ -204,11 +204,13 @@ end entity;
             Diagnostic::error(
                 code.s("const1", 3),
                 "No declaration of 'const1' within package 'pkg'",
-            ),
+            )
+            .with_suggestions(vec!["const3".to_owned()]),
             Diagnostic::error(
                 code.s("const2", 3),
                 "No declaration of 'const2' within package 'pkg'",
-            ),
+            )
+            .with_suggestions(vec!["const3".to_owned()]),
         ],
     );
 }
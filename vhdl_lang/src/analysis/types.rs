@@ -176,11 +176,34 @@ impl<'a> AnalyzeContext<'a> {
                 scope.add(ptype, diagnostics);
 
                 let region = scope.nested();
+                let mut generics = Vec::new();
+                if let Some(ref mut list) = prot_decl.generic_clause {
+                    for decl in list.iter_mut() {
+                        if let Some(ent) = as_fatal(self.analyze_interface_declaration(
+                            &region,
+                            ptype,
+                            decl,
+                            diagnostics,
+                        ))? {
+                            region.add(ent, diagnostics);
+                            generics.extend(GpkgInterfaceEnt::from_any(ent));
+                        }
+                    }
+                }
+                if let Some(ref mut map) = prot_decl.generic_map {
+                    as_fatal(self.generic_map(
+                        scope,
+                        GpkgRegion::new(generics),
+                        &mut map.list.items,
+                        diagnostics,
+                    ))?;
+                }
+
                 for item in prot_decl.items.iter_mut() {
                     match item {
                         ProtectedTypeDeclarativeItem::Subprogram(ref mut subprogram) => {
                             match as_fatal(self.subprogram_specification(
-                                scope,
+                                &region,
                                 ptype,
                                 &mut subprogram.specification,
                                 Overloaded::SubprogramDecl,
@@ -401,8 +424,6 @@ impl<'a> AnalyzeContext<'a> {
             }
 
             TypeDefinition::Numeric(ref mut range) => {
-                self.range_unknown_typ(scope, range, diagnostics)?;
-
                 let universal_type = if let Some(range_typ) =
                     as_fatal(self.range_type(scope, range, diagnostics))?
                 {
@@ -0,0 +1,230 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Analysis of VHDL-2019 mode views and interface objects with a mode view
+
+use super::analyze::*;
+use super::names::*;
+use super::scope::*;
+use crate::ast::*;
+use crate::data::*;
+use crate::named_entity::*;
+use fnv::FnvHashMap;
+
+impl<'a> AnalyzeContext<'a> {
+    pub fn analyze_view_declaration(
+        &self,
+        scope: &Scope<'a>,
+        parent: EntRef<'a>,
+        view: &mut ModeViewDeclaration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let Some(subtype) =
+            as_fatal(self.resolve_subtype_indication(scope, &mut view.typ, diagnostics))?
+        else {
+            return Ok(());
+        };
+        let typ = subtype.base_type();
+
+        let Type::Record(region) = typ.kind() else {
+            diagnostics.error(
+                &view.typ.type_mark.pos,
+                format!(
+                    "The type of a mode view must be a record type, got {}",
+                    typ.describe()
+                ),
+            );
+            return Ok(());
+        };
+
+        let mut elements = FnvHashMap::default();
+        for def in view.elements.iter_mut() {
+            let mode = match def.mode {
+                ElementModeIndication::Simple(ref mode) => Some(ElementMode::Simple(mode.item)),
+                ElementModeIndication::View(kind, ref mut name) => {
+                    as_fatal(self.resolve_view_name(scope, name, diagnostics))?.map(|view_ref| {
+                        match kind {
+                            ModeViewIndicationKind::Record => ElementMode::View(view_ref),
+                            ModeViewIndicationKind::Array => ElementMode::Array(view_ref),
+                        }
+                    })
+                }
+            };
+
+            for name in def.names.iter_mut() {
+                let designator = Designator::Identifier(name.item.item.clone());
+                let Some(elem) = region.lookup(&designator) else {
+                    diagnostics.push(Diagnostic::no_declaration_within(
+                        &typ,
+                        &name.item.pos,
+                        &designator,
+                    ));
+                    continue;
+                };
+                name.set_unique_reference(&elem);
+
+                if elements.contains_key(&name.item.item) {
+                    diagnostics.error(
+                        &name.item.pos,
+                        format!(
+                            "Duplicate mode for element '{}' in mode view",
+                            name.item.item
+                        ),
+                    );
+                    continue;
+                }
+
+                let Some(mode) = mode else {
+                    continue;
+                };
+                if let Some(err) = nested_view_mismatch(mode, elem.type_mark()) {
+                    diagnostics.error(
+                        &name.item.pos,
+                        format!("Record element '{}' is not {}", name.item.item, err),
+                    );
+                }
+                elements.insert(name.item.item.clone(), mode);
+            }
+        }
+
+        for elem in region.iter() {
+            if let Designator::Identifier(sym) = elem.designator() {
+                if !elements.contains_key(sym) {
+                    diagnostics.error(
+                        &view.ident.tree.pos,
+                        format!(
+                            "Mode view '{}' does not give a mode for element '{}'",
+                            view.ident.tree.item, sym
+                        ),
+                    );
+                }
+            }
+        }
+
+        scope.add(
+            self.arena.define(
+                &mut view.ident,
+                parent,
+                AnyEntKind::View(View { typ, elements }),
+            ),
+            diagnostics,
+        );
+        Ok(())
+    }
+
+    /// Resolve the name of a mode view, `name'converse` refers to the converse of the view
+    pub fn resolve_view_name(
+        &self,
+        scope: &Scope<'a>,
+        name: &mut WithPos<Name>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<ViewRef<'a>> {
+        if let Name::Attribute(ref mut attr) = name.item {
+            if attr.attr.item == AttributeDesignator::Converse
+                && attr.signature.is_none()
+                && attr.expr.is_none()
+            {
+                return Ok(self
+                    .resolve_view_name(scope, &mut attr.name, diagnostics)?
+                    .converse());
+            }
+        }
+
+        let resolved = self.name_resolve(scope, &name.pos, &mut name.item, diagnostics)?;
+        match resolved {
+            ResolvedName::Final(ent) if matches!(ent.actual_kind(), AnyEntKind::View(_)) => {
+                Ok(ViewRef::new(ent))
+            }
+            other => {
+                diagnostics.error(
+                    &name.pos,
+                    format!("Expected mode view, got {}", other.describe()),
+                );
+                Err(EvalError::Unknown)
+            }
+        }
+    }
+
+    /// The subtype of an interface object with a mode view
+    pub fn view_interface_subtype(
+        &self,
+        scope: &Scope<'a>,
+        view_ref: ViewRef<'a>,
+        indication: &mut ModeViewIndication,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<Subtype<'a>> {
+        let Some(ref mut subtype_indication) = indication.subtype_indication else {
+            return Ok(Subtype::new(view_ref.typ()));
+        };
+
+        let subtype = self.resolve_subtype_indication(scope, subtype_indication, diagnostics)?;
+        let typ = match indication.kind {
+            ModeViewIndicationKind::Record => Some(subtype.type_mark()),
+            ModeViewIndicationKind::Array => subtype.type_mark().array_type().map(|(elem, _)| elem),
+        };
+
+        if typ.map(|typ| typ.base_type()) != Some(view_ref.typ()) {
+            diagnostics.error(
+                &subtype_indication.type_mark.pos,
+                format!(
+                    "{} does not match mode view '{}' of {}",
+                    subtype.type_mark().describe(),
+                    view_ref.describe(),
+                    view_ref.typ().describe()
+                ),
+            );
+        }
+        Ok(subtype)
+    }
+
+    /// The mode given by a mode view to a name such as `port.element`,
+    /// `None` when the name does not refer to an interface object with a mode view
+    pub fn view_mode_of_name(&self, name: &Name) -> Option<ElementMode<'a>> {
+        match name {
+            Name::Designator(designator) => {
+                let ent = self.arena.get(designator.reference.get()?);
+                ObjectEnt::from_any(ent)?.object().iface?.view_mode()
+            }
+            Name::Selected(prefix, suffix) => match self.view_mode_of_name(&prefix.item)? {
+                ElementMode::View(view) => match suffix.item.item {
+                    Designator::Identifier(ref sym) => view.element_mode(sym),
+                    _ => None,
+                },
+                ElementMode::Simple(mode) => Some(ElementMode::Simple(mode)),
+                ElementMode::Array(_) => None,
+            },
+            Name::CallOrIndexed(call) => match self.view_mode_of_name(&call.name.item)? {
+                ElementMode::Array(view) => Some(ElementMode::View(view)),
+                ElementMode::Simple(mode) => Some(ElementMode::Simple(mode)),
+                ElementMode::View(_) => None,
+            },
+            Name::Slice(prefix, _) => self.view_mode_of_name(&prefix.item),
+            _ => None,
+        }
+    }
+}
+
+/// Describes the type a record element must have when it is given a nested mode view,
+/// `None` if the element type matches
+fn nested_view_mismatch<'a>(mode: ElementMode<'a>, elem_type: TypeEnt<'a>) -> Option<String> {
+    match mode {
+        ElementMode::Simple(_) => None,
+        ElementMode::View(view) => {
+            if elem_type.base_type() == view.typ() {
+                None
+            } else {
+                Some(format!("of {}", view.typ().describe()))
+            }
+        }
+        ElementMode::Array(view) => {
+            if elem_type.array_type().map(|(elem, _)| elem.base_type()) == Some(view.typ()) {
+                None
+            } else {
+                Some(format!("an array of {}", view.typ().describe()))
+            }
+        }
+    }
+}
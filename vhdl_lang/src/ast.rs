@@ -130,6 +130,8 @@ pub enum AttributeDesignator {
     SimpleName,
     InstanceName,
     PathName,
+    Converse,
+    Reflect,
 }
 
 #[derive(PartialEq, Debug, Copy, Clone, Eq)]
@@ -522,7 +524,6 @@ pub enum EntityName {
 }
 
 /// LRM 7.2 Attribute specification
-// @TODO there are more classes
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum EntityClass {
     Entity,
@@ -540,10 +541,36 @@ pub enum EntityClass {
     Label,
     Literal,
     Units,
-    // Group
+    Group,
     File,
-    // Property
-    // Sequence
+    Property,
+    Sequence,
+    View,
+}
+
+/// LRM 6.9 Group template declarations
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct EntityClassEntry {
+    pub class: EntityClass,
+    /// The entry ends with `<>` and matches any number of constituents
+    pub is_box: bool,
+}
+
+/// LRM 6.9 Group template declarations
+#[with_token_span]
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupTemplateDeclaration {
+    pub ident: WithDecl<Ident>,
+    pub entity_classes: Vec<EntityClassEntry>,
+}
+
+/// LRM 6.10 Group declarations
+#[with_token_span]
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupDeclaration {
+    pub ident: WithDecl<Ident>,
+    pub template_name: WithPos<Name>,
+    pub constituents: Vec<WithPos<Name>>,
 }
 
 /// LRM 7.2 Attribute specification
@@ -566,6 +593,9 @@ pub enum Attribute {
 /// LRM 5.6.2 Protected type declarations
 #[derive(PartialEq, Debug, Clone)]
 pub struct ProtectedTypeDeclaration {
+    // VHDL-2019 protected type header
+    pub generic_clause: Option<Vec<InterfaceDeclaration>>,
+    pub generic_map: Option<MapAspect>,
     pub items: Vec<ProtectedTypeDeclarativeItem>,
 }
 
@@ -598,10 +628,12 @@ pub enum TypeDefinition {
     Enumeration(Vec<WithDecl<WithPos<EnumerationLiteral>>>),
     /// LRM 5.2.3 Integer types
     ///     5.2.5 Floating-point types
+    ///
+    /// Whether this is an integer or floating-point type depends on the
+    /// type of the range bounds and is determined during analysis
     Numeric(Range),
     /// LRM 5.2.4 Physical types
     Physical(PhysicalTypeDeclaration),
-    // @TODO floating
     /// LRM 5.3 Composite Types
     /// LRM 5.3.2 Array types
     Array(Vec<ArrayIndex>, SubtypeIndication),
@@ -645,12 +677,20 @@ pub enum InterfaceType {
     Parameter,
 }
 
+/// LRM 6.4.2.3 Signal declarations, the kind of a guarded signal
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SignalKind {
+    Register,
+    Bus,
+}
+
 #[with_token_span]
 #[derive(PartialEq, Debug, Clone)]
 pub struct ObjectDeclaration {
     pub class: ObjectClass,
     pub ident: WithDecl<Ident>,
     pub subtype_indication: SubtypeIndication,
+    pub signal_kind: Option<SignalKind>,
     pub expression: Option<WithPos<Expression>>,
 }
 
@@ -688,6 +728,8 @@ pub struct FunctionSpecification {
     // The `parameter` token, if such a token exists
     pub param_tok: Option<TokenId>,
     pub parameter_list: Vec<InterfaceDeclaration>,
+    // VHDL-2019 `return identifier of type_mark`
+    pub return_identifier: Option<WithDecl<Ident>>,
     pub return_type: WithPos<TypeMark>,
 }
 
@@ -761,11 +803,73 @@ pub struct InterfaceObjectDeclaration {
     pub list_type: InterfaceType,
     pub class: ObjectClass,
     pub ident: WithDecl<Ident>,
+    pub mode: ModeIndication,
+}
+
+/// LRM 6.5.2 Interface object declarations
+#[derive(PartialEq, Debug, Clone)]
+pub enum ModeIndication {
+    Simple(SimpleModeIndication),
+    View(ModeViewIndication),
+}
+
+impl ModeIndication {
+    /// The mode of a simple mode indication, `None` for a mode view
+    pub fn simple_mode(&self) -> Option<Mode> {
+        match self {
+            ModeIndication::Simple(simple) => Some(simple.mode),
+            ModeIndication::View(_) => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct SimpleModeIndication {
     pub mode: Mode,
     pub subtype_indication: SubtypeIndication,
+    /// A signal port or parameter declared with the `bus` keyword
+    pub bus: bool,
     pub expression: Option<WithPos<Expression>>,
 }
 
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ModeViewIndicationKind {
+    /// view name
+    Record,
+    /// view (name)
+    Array,
+}
+
+/// A port whose element modes are given by a mode view (VHDL-2019)
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModeViewIndication {
+    pub kind: ModeViewIndicationKind,
+    pub name: WithPos<Name>,
+    pub subtype_indication: Option<SubtypeIndication>,
+}
+
+/// LRM 6.5.2 Mode view declarations (VHDL-2019)
+#[with_token_span]
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModeViewDeclaration {
+    pub ident: WithDecl<Ident>,
+    pub typ: SubtypeIndication,
+    pub elements: Vec<ModeViewElementDefinition>,
+    pub end_ident_pos: Option<SrcPos>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModeViewElementDefinition {
+    pub names: Vec<WithRef<Ident>>,
+    pub mode: ElementModeIndication,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum ElementModeIndication {
+    Simple(WithPos<Mode>),
+    View(ModeViewIndicationKind, WithPos<Name>),
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub enum SubprogramDefault {
     Name(WithPos<SelectedName>),
@@ -836,6 +940,11 @@ pub enum Declaration {
     Use(UseClause),
     Package(PackageInstantiation),
     Configuration(ConfigurationSpecification),
+    View(ModeViewDeclaration),
+    Psl(PslDeclaration),
+    Disconnection(DisconnectionSpecification),
+    GroupTemplate(GroupTemplateDeclaration),
+    Group(GroupDeclaration),
 }
 
 /// LRM 10.2 Wait statement
@@ -1004,7 +1113,8 @@ pub struct ExitStatement {
 /// LRM 10.13 Return statement
 #[derive(PartialEq, Debug, Clone)]
 pub struct ReturnStatement {
-    pub expression: Option<WithPos<Expression>>,
+    // VHDL-2019 allows a conditional expression such as `return a when cond else b;`
+    pub expression: Option<AssignmentRightHand<WithPos<Expression>>>,
 }
 
 /// LRM 10. Sequential statements
@@ -1024,9 +1134,18 @@ pub enum SequentialStatement {
     Next(NextStatement),
     Exit(ExitStatement),
     Return(ReturnStatement),
+    SequentialBlock(SequentialBlock),
     Null,
 }
 
+/// LRM 10.16 Sequential block statement (VHDL-2019)
+#[derive(PartialEq, Debug, Clone)]
+pub struct SequentialBlock {
+    pub decl: Vec<Declaration>,
+    pub statements: Vec<LabeledSequentialStatement>,
+    pub end_label_pos: Option<SrcPos>,
+}
+
 /// LRM 10. Sequential statements
 #[derive(PartialEq, Debug, Clone)]
 pub struct LabeledSequentialStatement {
@@ -1083,6 +1202,68 @@ pub struct ConcurrentAssertStatement {
     pub statement: AssertStatement,
 }
 
+/// LRM 15.10 PSL property, sequence or boolean expression (IEEE 1850)
+///
+/// Only the VHDL names that the expression refers to are kept,
+/// the temporal structure of the expression is not represented.
+#[derive(PartialEq, Debug, Clone)]
+pub struct PslExpression {
+    pub names: Vec<WithPos<WithRef<Designator>>>,
+}
+
+/// IEEE 1850 6.1.1 Sequence declaration and 6.2.4 Property declaration
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum PslNamedKind {
+    Property,
+    Sequence,
+}
+
+/// IEEE 1850 6.1.1 Sequence declaration and 6.2.4 Property declaration
+#[with_token_span]
+#[derive(PartialEq, Debug, Clone)]
+pub struct PslNamedDeclaration {
+    pub kind: PslNamedKind,
+    pub ident: WithDecl<Ident>,
+    pub parameters: Vec<WithDecl<Ident>>,
+    pub expression: PslExpression,
+}
+
+/// IEEE 1850 5.3 Default clock declaration
+#[with_token_span]
+#[derive(PartialEq, Debug, Clone)]
+pub struct PslDefaultClock {
+    pub clock: PslExpression,
+}
+
+/// LRM 15.10 PSL declarations
+#[derive(PartialEq, Debug, Clone, TokenSpan)]
+pub enum PslDeclaration {
+    Named(PslNamedDeclaration),
+    DefaultClock(PslDefaultClock),
+}
+
+/// IEEE 1850 7.1 Verification directives
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum PslDirectiveKind {
+    Assert,
+    Assume,
+    AssumeGuarantee,
+    Restrict,
+    RestrictGuarantee,
+    Cover,
+    Fairness,
+    StrongFairness,
+}
+
+/// LRM 11.1 PSL directive as a concurrent statement
+#[derive(PartialEq, Debug, Clone)]
+pub struct PslDirective {
+    pub kind: PslDirectiveKind,
+    pub property: PslExpression,
+    pub report: Option<WithPos<Expression>>,
+    pub severity: Option<WithPos<Expression>>,
+}
+
 /// 11.6 Concurrent signal assignment statements
 #[derive(PartialEq, Debug, Clone)]
 pub struct ConcurrentSignalAssignment {
@@ -1189,6 +1370,7 @@ pub enum ConcurrentStatement {
     ForGenerate(ForGenerateStatement),
     IfGenerate(IfGenerateStatement),
     CaseGenerate(CaseGenerateStatement),
+    Psl(PslDirective),
 }
 
 /// LRM 11. Concurrent statements
@@ -1278,6 +1460,23 @@ pub struct PackageInstantiation {
     pub generic_map: Option<MapAspect>,
 }
 
+/// LRM 7.4 Disconnection specification
+#[derive(PartialEq, Debug, Clone)]
+pub enum GuardedSignalList {
+    Names(Vec<WithPos<Name>>),
+    Others,
+    All,
+}
+
+/// LRM 7.4 Disconnection specification
+#[with_token_span]
+#[derive(PartialEq, Debug, Clone)]
+pub struct DisconnectionSpecification {
+    pub signals: GuardedSignalList,
+    pub type_mark: WithPos<TypeMark>,
+    pub after: WithPos<Expression>,
+}
+
 /// LRM 7.3 Configuration specification
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum InstantiationList {
@@ -1329,7 +1528,7 @@ pub struct ConfigurationSpecification {
 pub enum ConfigurationDeclarativeItem {
     Use(UseClause),
     // @TODO attribute
-    // @TODO group
+    Group(GroupDeclaration),
 }
 /// LRM 3.4 Configuration declarations
 #[derive(PartialEq, Debug, Clone)]
@@ -1414,6 +1613,34 @@ pub struct PackageBody {
     pub end_ident_pos: Option<SrcPos>,
 }
 
+/// IEEE 1850 7.2 Verification units
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum VerificationUnitKind {
+    Vunit,
+    Vprop,
+    Vmode,
+}
+
+/// IEEE 1850 7.2 Verification units, bound to `entity_name(architecture_name)`
+#[derive(PartialEq, Debug, Clone)]
+pub struct VerificationUnitBinding {
+    pub entity_name: WithRef<Ident>,
+    pub architecture_name: Option<WithRef<Ident>>,
+}
+
+/// IEEE 1850 7.2 Verification units
+#[with_token_span]
+#[derive(PartialEq, Debug, Clone)]
+pub struct VerificationUnit {
+    pub context_clause: ContextClause,
+    pub kind: VerificationUnitKind,
+    pub ident: WithDecl<Ident>,
+    pub binding: Option<VerificationUnitBinding>,
+    pub inherits: Vec<WithRef<Ident>>,
+    pub decl: Vec<Declaration>,
+    pub statements: Vec<LabeledConcurrentStatement>,
+}
+
 /// LRM 13.1 Design units
 #[derive(PartialEq, Debug, Clone, TokenSpan)]
 pub enum AnyPrimaryUnit {
@@ -1431,6 +1658,9 @@ pub enum AnyPrimaryUnit {
 
     /// LRM 13.4 Context clauses
     Context(ContextDeclaration),
+
+    /// IEEE 1850 7.2 Verification units
+    VerificationUnit(VerificationUnit),
 }
 
 /// LRM 13.1 Design units
@@ -120,6 +120,8 @@ impl Display for AttributeDesignator {
             AttributeDesignator::SimpleName => write!(f, "simple_name"),
             AttributeDesignator::InstanceName => write!(f, "instance_name"),
             AttributeDesignator::PathName => write!(f, "path_name"),
+            AttributeDesignator::Converse => write!(f, "converse"),
+            AttributeDesignator::Reflect => write!(f, "reflect"),
         }
     }
 }
@@ -165,7 +167,7 @@ impl Display for EntityClass {
         match self {
             EntityClass::Entity => write!(f, "entity"),
             EntityClass::Architecture => write!(f, "architecture"),
-            EntityClass::Configuration => write!(f, "ponfiguration"),
+            EntityClass::Configuration => write!(f, "configuration"),
             EntityClass::Procedure => write!(f, "procedure"),
             EntityClass::Function => write!(f, "function"),
             EntityClass::Package => write!(f, "package"),
@@ -179,6 +181,10 @@ impl Display for EntityClass {
             EntityClass::Literal => write!(f, "literal"),
             EntityClass::Units => write!(f, "units"),
             EntityClass::File => write!(f, "file"),
+            EntityClass::Property => write!(f, "property"),
+            EntityClass::Sequence => write!(f, "sequence"),
+            EntityClass::Group => write!(f, "group"),
+            EntityClass::View => write!(f, "view"),
         }
     }
 }
@@ -264,6 +270,42 @@ impl Display for AttributeDeclaration {
     }
 }
 
+impl Display for EntityClassEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.is_box {
+            write!(f, "{} <>", self.class)
+        } else {
+            write!(f, "{}", self.class)
+        }
+    }
+}
+
+impl Display for GroupTemplateDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "group {} is (", self.ident)?;
+        for (i, entry) in self.entity_classes.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{entry}")?;
+        }
+        write!(f, ");")
+    }
+}
+
+impl Display for GroupDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "group {} : {} (", self.ident, self.template_name)?;
+        for (i, constituent) in self.constituents.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{constituent}")?;
+        }
+        write!(f, ");")
+    }
+}
+
 impl Display for Choice {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -762,6 +804,15 @@ impl Display for ObjectClass {
     }
 }
 
+impl Display for SignalKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            SignalKind::Register => write!(f, "register"),
+            SignalKind::Bus => write!(f, "bus"),
+        }
+    }
+}
+
 impl Display for ObjectDeclaration {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(
@@ -769,6 +820,9 @@ impl Display for ObjectDeclaration {
             "{} {} : {}",
             self.class, self.ident, self.subtype_indication,
         )?;
+        if let Some(signal_kind) = self.signal_kind {
+            write!(f, " {signal_kind}")?;
+        }
         match self.expression {
             Some(ref expr) => write!(f, " := {expr};"),
             None => write!(f, ";"),
@@ -836,7 +890,11 @@ impl Display for FunctionSpecification {
         if !first {
             write!(f, "\n)")?;
         }
-        write!(f, " return {}", self.return_type)
+        write!(f, " return ")?;
+        if let Some(ref ident) = self.return_identifier {
+            write!(f, "{ident} of ")?;
+        }
+        write!(f, "{}", self.return_type)
     }
 }
 
@@ -937,32 +995,89 @@ impl Display for InterfaceFileDeclaration {
 
 impl Display for InterfaceObjectDeclaration {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mode = match self.mode {
+            ModeIndication::Simple(ref mode) => mode,
+            ModeIndication::View(ref view) => {
+                if self.list_type == InterfaceType::Parameter {
+                    write!(f, "{} ", self.class)?;
+                }
+                return write!(f, "{} : {}", self.ident, view);
+            }
+        };
+
         match self.list_type {
             InterfaceType::Port => {
                 write!(
                     f,
                     "{} : {} {}",
-                    self.ident, self.mode, self.subtype_indication
+                    self.ident, mode.mode, mode.subtype_indication
                 )?;
             }
             InterfaceType::Generic => {
-                write!(f, "{} : {}", self.ident, self.subtype_indication)?;
+                write!(f, "{} : {}", self.ident, mode.subtype_indication)?;
             }
             InterfaceType::Parameter => {
                 write!(
                     f,
                     "{} {} : {} {}",
-                    self.class, self.ident, self.mode, self.subtype_indication,
+                    self.class, self.ident, mode.mode, mode.subtype_indication,
                 )?;
             }
         }
-        match self.expression {
+        if mode.bus {
+            write!(f, " bus")?;
+        }
+        match mode.expression {
             Some(ref expr) => write!(f, " := {expr}"),
             None => Ok(()),
         }
     }
 }
 
+impl Display for ModeViewIndication {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.kind {
+            ModeViewIndicationKind::Record => write!(f, "view {}", self.name)?,
+            ModeViewIndicationKind::Array => write!(f, "view ({})", self.name)?,
+        }
+        match self.subtype_indication {
+            Some(ref subtype_indication) => write!(f, " of {subtype_indication}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for ElementModeIndication {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ElementModeIndication::Simple(mode) => write!(f, "{mode}"),
+            ElementModeIndication::View(ModeViewIndicationKind::Record, name) => {
+                write!(f, "view {name}")
+            }
+            ElementModeIndication::View(ModeViewIndicationKind::Array, name) => {
+                write!(f, "view ({name})")
+            }
+        }
+    }
+}
+
+impl Display for ModeViewDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "view {} of {} is", self.ident, self.typ)?;
+        for element in self.elements.iter() {
+            write!(f, "\n  ")?;
+            for (i, name) in element.names.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", name.item)?;
+            }
+            write!(f, " : {};", element.mode)?;
+        }
+        write!(f, "\nend view;")
+    }
+}
+
 impl Display for SubprogramDefault {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -1080,6 +1195,54 @@ impl Display for ContextDeclaration {
     }
 }
 
+impl Display for VerificationUnitKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            VerificationUnitKind::Vunit => write!(f, "vunit"),
+            VerificationUnitKind::Vprop => write!(f, "vprop"),
+            VerificationUnitKind::Vmode => write!(f, "vmode"),
+        }
+    }
+}
+
+impl Display for VerificationUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        // Not used: context_clause, inherits, decl, statements
+        write!(f, "{} {}", self.kind, self.ident)?;
+        if let Some(ref binding) = self.binding {
+            write!(f, " ({}", binding.entity_name)?;
+            if let Some(ref architecture_name) = binding.architecture_name {
+                write!(f, "({architecture_name})")?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for PslNamedDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        // Not used: expression
+        match self.kind {
+            PslNamedKind::Property => write!(f, "property {}", self.ident)?,
+            PslNamedKind::Sequence => write!(f, "sequence {}", self.ident)?,
+        }
+        let mut first = true;
+        for parameter in &self.parameters {
+            if first {
+                write!(f, "({parameter}")?;
+            } else {
+                write!(f, ", {parameter}")?;
+            }
+            first = false;
+        }
+        if !first {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
 impl Display for PackageInstantiation {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         // Not used: context_clause
@@ -1806,6 +1969,14 @@ end units;",
         assert_format("constant foo : natural := 0;", Code::object_decl);
     }
 
+    #[test]
+    fn test_object_declaration_guarded_signal() {
+        assert_format(
+            "signal foo : resolved_bit register := '0';",
+            Code::object_decl,
+        );
+    }
+
     #[test]
     fn test_file_declaration() {
         assert_format("file foo : text;", Code::file_decl);
@@ -1930,6 +2101,11 @@ end units;",
         assert_format("foo : in natural := bar(0)", Code::port);
     }
 
+    #[test]
+    fn test_interface_declaration_object_bus_port() {
+        assert_format("foo : inout resolved_bit bus", Code::port);
+    }
+
     #[test]
     fn test_interface_declaration_file() {
         assert_format("file foo : text", Code::parameter);
@@ -2263,4 +2439,24 @@ end package;",
             },
         );
     }
+
+    #[test]
+    fn group_template_declaration() {
+        assert_format("group pin2pin is (signal, label <>);", |code| {
+            assert_matches!(
+                code.declarative_part().remove(0),
+                Declaration::GroupTemplate(template) => template
+            )
+        });
+    }
+
+    #[test]
+    fn group_declaration() {
+        assert_format("group path : pin2pin (clk, lbl, '1');", |code| {
+            assert_matches!(
+                code.declarative_part().remove(0),
+                Declaration::Group(group) => group
+            )
+        });
+    }
 }
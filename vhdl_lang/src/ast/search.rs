@@ -60,9 +60,15 @@ pub enum FoundDeclaration<'a> {
     PackageBody(&'a PackageBody),
     PackageInstance(&'a PackageInstantiation),
     Configuration(&'a ConfigurationDeclaration),
+    View(&'a ModeViewDeclaration),
+    GroupTemplate(&'a GroupTemplateDeclaration),
+    Group(&'a GroupDeclaration),
     Entity(&'a EntityDeclaration),
     Architecture(&'a ArchitectureBody),
     Context(&'a ContextDeclaration),
+    VerificationUnit(&'a VerificationUnit),
+    PslDeclaration(&'a PslNamedDeclaration),
+    PslParameter(&'a WithDecl<Ident>),
     ForIndex(&'a WithDecl<Ident>, &'a DiscreteRange),
     ForGenerateIndex(Option<&'a Ident>, &'a ForGenerateStatement),
     GenerateBody(&'a WithDecl<Ident>),
@@ -284,7 +290,23 @@ impl Search for LabeledSequentialStatement {
         match self.statement.item {
             SequentialStatement::Return(ref ret) => {
                 let ReturnStatement { ref expression } = ret;
-                return_if_found!(expression.search(ctx, searcher));
+                match expression {
+                    Some(AssignmentRightHand::Simple(expr)) => {
+                        return_if_found!(expr.search(ctx, searcher));
+                    }
+                    Some(AssignmentRightHand::Conditional(conditionals)) => {
+                        return_if_found!(search_conditionals(conditionals, true, searcher, ctx));
+                    }
+                    Some(AssignmentRightHand::Selected(selection)) => {
+                        let Selection {
+                            expression,
+                            alternatives,
+                        } = selection;
+                        return_if_found!(expression.search(ctx, searcher));
+                        return_if_found!(search_alternatives(alternatives, true, searcher, ctx));
+                    }
+                    None => {}
+                }
             }
             SequentialStatement::ProcedureCall(ref pcall) => {
                 return_if_finished!(searcher.search_with_pos(ctx, &pcall.pos));
@@ -392,6 +414,15 @@ impl Search for LabeledSequentialStatement {
                 } = assign;
                 return_if_found!(target.search(ctx, searcher));
             }
+            SequentialStatement::SequentialBlock(ref block) => {
+                let SequentialBlock {
+                    decl,
+                    statements,
+                    end_label_pos: _,
+                } = block;
+                return_if_found!(decl.search(ctx, searcher));
+                return_if_found!(statements.search(ctx, searcher));
+            }
             SequentialStatement::Null => {}
         }
 
@@ -478,7 +509,7 @@ impl Search for LabeledConcurrentStatement {
             .or_not_found());
         match self.statement.item {
             ConcurrentStatement::Block(ref block) => {
-                // @TODO guard condition
+                return_if_found!(block.guard_condition.search(ctx, searcher));
                 return_if_found!(block.decl.search(ctx, searcher));
                 return_if_found!(block.statements.search(ctx, searcher));
             }
@@ -545,6 +576,17 @@ impl Search for LabeledConcurrentStatement {
                 return_if_found!(report.search(ctx, searcher));
                 return_if_found!(severity.search(ctx, searcher));
             }
+            ConcurrentStatement::Psl(ref directive) => {
+                let PslDirective {
+                    kind: _,
+                    property,
+                    report,
+                    severity,
+                } = directive;
+                return_if_found!(property.search(ctx, searcher));
+                return_if_found!(report.search(ctx, searcher));
+                return_if_found!(severity.search(ctx, searcher));
+            }
         };
 
         if let Some(end_label_pos) = self.statement.item.end_label_pos() {
@@ -755,6 +797,8 @@ impl Search for TypeDeclaration {
                 return_if_found!(body.decl.search(ctx, searcher));
             }
             TypeDefinition::Protected(ref prot_decl) => {
+                return_if_found!(prot_decl.generic_clause.search(ctx, searcher));
+                return_if_found!(prot_decl.generic_map.search(ctx, searcher));
                 for item in prot_decl.items.iter() {
                     match item {
                         ProtectedTypeDeclarativeItem::Subprogram(ref subprogram) => {
@@ -1087,6 +1131,58 @@ impl Search for Declaration {
             Declaration::Configuration(_) => {
                 // @TODO
             }
+
+            Declaration::Psl(psl) => {
+                return_if_found!(psl.search(ctx, searcher));
+            }
+
+            Declaration::Disconnection(disconnection) => {
+                let DisconnectionSpecification {
+                    signals,
+                    type_mark,
+                    after,
+                    span: _,
+                } = disconnection;
+                if let GuardedSignalList::Names(names) = signals {
+                    return_if_found!(names.search(ctx, searcher));
+                }
+                return_if_found!(type_mark.search(ctx, searcher));
+                return_if_found!(after.search(ctx, searcher));
+            }
+
+            Declaration::GroupTemplate(template) => {
+                return_if_found!(searcher
+                    .search_decl(ctx, FoundDeclaration::GroupTemplate(template))
+                    .or_not_found());
+            }
+            Declaration::Group(group) => {
+                return_if_found!(searcher
+                    .search_decl(ctx, FoundDeclaration::Group(group))
+                    .or_not_found());
+                return_if_found!(group.template_name.search(ctx, searcher));
+                return_if_found!(group.constituents.search(ctx, searcher));
+            }
+            Declaration::View(view) => {
+                return_if_found!(searcher
+                    .search_decl(ctx, FoundDeclaration::View(view))
+                    .or_not_found());
+                let ModeViewDeclaration {
+                    ident: _,
+                    typ,
+                    elements,
+                    end_ident_pos: _,
+                    span: _,
+                } = view;
+                return_if_found!(typ.search(ctx, searcher));
+                for element in elements.iter() {
+                    for name in element.names.iter() {
+                        return_if_found!(searcher.search_ident_ref(ctx, name).or_not_found());
+                    }
+                    if let ElementModeIndication::View(_, ref name) = element.mode {
+                        return_if_found!(name.search(ctx, searcher));
+                    }
+                }
+            }
         }
         NotFound
     }
@@ -1099,8 +1195,16 @@ impl Search for InterfaceDeclaration {
                 return_if_found!(searcher
                     .search_decl(ctx, FoundDeclaration::InterfaceObject(decl))
                     .or_not_found());
-                return_if_found!(decl.subtype_indication.search(ctx, searcher));
-                return_if_found!(decl.expression.search(ctx, searcher));
+                match decl.mode {
+                    ModeIndication::Simple(ref mode) => {
+                        return_if_found!(mode.subtype_indication.search(ctx, searcher));
+                        return_if_found!(mode.expression.search(ctx, searcher));
+                    }
+                    ModeIndication::View(ref view) => {
+                        return_if_found!(view.name.search(ctx, searcher));
+                        return_if_found!(view.subtype_indication.search(ctx, searcher));
+                    }
+                }
             }
             InterfaceDeclaration::Subprogram(ref spec, ref subpgm_default) => {
                 return_if_found!(searcher
@@ -1244,6 +1348,62 @@ impl Search for EntityDeclaration {
     }
 }
 
+impl Search for VerificationUnit {
+    fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        return_if_found!(self.context_clause.search(ctx, searcher));
+        return_if_found!(searcher
+            .search_decl(ctx, FoundDeclaration::VerificationUnit(self))
+            .or_not_found());
+        if let Some(ref binding) = self.binding {
+            return_if_found!(searcher
+                .search_ident_ref(ctx, &binding.entity_name)
+                .or_not_found());
+            if let Some(ref architecture_name) = binding.architecture_name {
+                return_if_found!(searcher
+                    .search_ident_ref(ctx, architecture_name)
+                    .or_not_found());
+            }
+        }
+        for inherit in self.inherits.iter() {
+            return_if_found!(searcher.search_ident_ref(ctx, inherit).or_not_found());
+        }
+        return_if_found!(self.decl.search(ctx, searcher));
+        self.statements.search(ctx, searcher)
+    }
+}
+
+impl Search for PslExpression {
+    fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        for name in self.names.iter() {
+            return_if_found!(searcher
+                .search_designator_ref(ctx, &name.pos, &name.item)
+                .or_not_found());
+        }
+        NotFound
+    }
+}
+
+impl Search for PslDeclaration {
+    fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        match self {
+            PslDeclaration::Named(decl) => {
+                return_if_found!(searcher
+                    .search_decl(ctx, FoundDeclaration::PslDeclaration(decl))
+                    .or_not_found());
+                for parameter in decl.parameters.iter() {
+                    return_if_found!(searcher
+                        .search_decl(ctx, FoundDeclaration::PslParameter(parameter))
+                        .or_not_found());
+                }
+                decl.expression.search(ctx, searcher)
+            }
+            PslDeclaration::DefaultClock(default_clock) => {
+                default_clock.clock.search(ctx, searcher)
+            }
+        }
+    }
+}
+
 impl Search for ArchitectureBody {
     fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
         return_if_found!(self.context_clause.search(ctx, searcher));
@@ -1641,9 +1801,15 @@ impl<'a> FoundDeclaration<'a> {
             FoundDeclaration::PackageBody(value) => value.end_ident_pos.as_ref(),
             FoundDeclaration::PackageInstance(..) => None,
             FoundDeclaration::Configuration(value) => value.end_ident_pos.as_ref(),
+            FoundDeclaration::View(value) => value.end_ident_pos.as_ref(),
+            FoundDeclaration::GroupTemplate(..) => None,
+            FoundDeclaration::Group(..) => None,
             FoundDeclaration::Entity(value) => value.end_ident_pos.as_ref(),
             FoundDeclaration::Architecture(value) => value.end_ident_pos.as_ref(),
             FoundDeclaration::Context(value) => value.end_ident_pos.as_ref(),
+            FoundDeclaration::VerificationUnit(..) => None,
+            FoundDeclaration::PslDeclaration(..) => None,
+            FoundDeclaration::PslParameter(..) => None,
             FoundDeclaration::GenerateBody(..) => None,
             FoundDeclaration::ConcurrentStatement(..) => None,
             FoundDeclaration::SequentialStatement(..) => None,
@@ -1676,9 +1842,15 @@ impl<'a> FoundDeclaration<'a> {
             FoundDeclaration::PackageBody(value) => &value.ident.decl,
             FoundDeclaration::PackageInstance(value) => &value.ident.decl,
             FoundDeclaration::Configuration(value) => &value.ident.decl,
+            FoundDeclaration::View(value) => &value.ident.decl,
+            FoundDeclaration::GroupTemplate(value) => &value.ident.decl,
+            FoundDeclaration::Group(value) => &value.ident.decl,
             FoundDeclaration::Entity(value) => &value.ident.decl,
             FoundDeclaration::Architecture(value) => &value.ident.decl,
             FoundDeclaration::Context(value) => &value.ident.decl,
+            FoundDeclaration::VerificationUnit(value) => &value.ident.decl,
+            FoundDeclaration::PslDeclaration(value) => &value.ident.decl,
+            FoundDeclaration::PslParameter(value) => &value.decl,
             FoundDeclaration::GenerateBody(value) => &value.decl,
             FoundDeclaration::ConcurrentStatement(value) => &value.label.decl,
             FoundDeclaration::SequentialStatement(value) => &value.label.decl,
@@ -1777,6 +1949,15 @@ impl std::fmt::Display for FoundDeclaration<'_> {
             FoundDeclaration::Configuration(ref value) => {
                 write!(f, "{value}")
             }
+            FoundDeclaration::View(ref value) => {
+                write!(f, "{value}")
+            }
+            FoundDeclaration::GroupTemplate(ref value) => {
+                write!(f, "{value}")
+            }
+            FoundDeclaration::Group(ref value) => {
+                write!(f, "{value}")
+            }
             FoundDeclaration::Entity(ref value) => {
                 write!(f, "{value}")
             }
@@ -1786,6 +1967,15 @@ impl std::fmt::Display for FoundDeclaration<'_> {
             FoundDeclaration::Context(ref value) => {
                 write!(f, "{value}")
             }
+            FoundDeclaration::VerificationUnit(ref value) => {
+                write!(f, "{value}")
+            }
+            FoundDeclaration::PslDeclaration(ref value) => {
+                write!(f, "{value}")
+            }
+            FoundDeclaration::PslParameter(ref value) => {
+                write!(f, "{value}")
+            }
             FoundDeclaration::GenerateBody(value) => {
                 write!(f, "{value}")
             }
@@ -190,6 +190,12 @@ impl HasIdent for ConfigurationDeclaration {
     }
 }
 
+impl HasIdent for VerificationUnit {
+    fn ident(&self) -> &Ident {
+        self.ident.ident()
+    }
+}
+
 impl HasIdent for AnyPrimaryUnit {
     fn ident(&self) -> &Ident {
         match self {
@@ -198,6 +204,7 @@ impl HasIdent for AnyPrimaryUnit {
             AnyPrimaryUnit::Package(ref unit) => unit.ident(),
             AnyPrimaryUnit::PackageInstance(ref unit) => unit.ident(),
             AnyPrimaryUnit::Context(ref unit) => unit.ident(),
+            AnyPrimaryUnit::VerificationUnit(ref unit) => unit.ident(),
         }
     }
 }
@@ -505,6 +512,7 @@ impl ConcurrentStatement {
             Assignment(_) => None,
             Instance(_) => Some(Concurrent::Instance),
             ForGenerate(_) | IfGenerate(_) | CaseGenerate(_) => Some(Concurrent::Generate),
+            Psl(_) => None,
         }
     }
 
@@ -521,6 +529,7 @@ impl ConcurrentStatement {
             ForGenerate(value) => value.end_label_pos.as_ref(),
             IfGenerate(value) => value.end_label_pos.as_ref(),
             CaseGenerate(value) => value.end_label_pos.as_ref(),
+            Psl(_) => None,
         }
     }
 
@@ -547,6 +556,7 @@ impl SequentialStatement {
             Next(_) => None,
             Exit(_) => None,
             Return(_) => None,
+            SequentialBlock(_) => Some(Sequential::Block),
             Null => None,
         }
     }
@@ -568,6 +578,7 @@ impl SequentialStatement {
             Next(_) => None,
             Exit(_) => None,
             Return(_) => None,
+            SequentialBlock(value) => value.end_label_pos.as_ref(),
             Null => None,
         }
     }
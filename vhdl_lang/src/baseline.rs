@@ -0,0 +1,283 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Diagnostics that were accepted at some point, such that only new diagnostics are reported.
+//!
+//! A diagnostic is identified by its rule, the design unit it is within and its message
+//! without suggestions rather than by its line, such that it still matches after code is
+//! moved around or similar names are declared.
+
+use crate::data::Diagnostic;
+use crate::project::Project;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const VERSION: u32 = 1;
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+struct Fingerprint {
+    rule: String,
+    /// The enclosing design unit as `library.unit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unit: Option<String>,
+    /// The file of a diagnostic outside of any design unit, relative to the baseline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    message: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineEntry {
+    #[serde(flatten)]
+    fingerprint: Fingerprint,
+    count: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    diagnostics: Vec<BaselineEntry>,
+}
+
+/// The number of occurrences of each accepted diagnostic
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Baseline {
+    /// File names of diagnostics outside of design units are relative to this folder
+    root: PathBuf,
+    counts: BTreeMap<Fingerprint, usize>,
+}
+
+impl Baseline {
+    /// An empty baseline, typically `root` is the folder of the baseline file
+    pub fn new(root: &Path) -> Baseline {
+        Baseline {
+            root: root.to_owned(),
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// Accept the diagnostics of an analyzed project
+    pub fn record(&mut self, project: &Project, diagnostics: &[Diagnostic]) {
+        for diagnostic in diagnostics {
+            *self
+                .counts
+                .entry(self.fingerprint(project, diagnostic))
+                .or_default() += 1;
+        }
+    }
+
+    /// The diagnostics that are not part of the baseline.
+    /// A diagnostic that occurs more often than it was recorded is reported for the extra occurrences
+    pub fn filter(&self, project: &Project, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        let mut remaining = self.counts.clone();
+        diagnostics
+            .into_iter()
+            .filter(
+                |diagnostic| match remaining.get_mut(&self.fingerprint(project, diagnostic)) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                },
+            )
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn to_json(&self) -> String {
+        let file = BaselineFile {
+            version: VERSION,
+            diagnostics: self
+                .counts
+                .iter()
+                .map(|(fingerprint, count)| BaselineEntry {
+                    fingerprint: fingerprint.clone(),
+                    count: *count,
+                })
+                .collect(),
+        };
+        // Serializing strings and numbers cannot fail
+        serde_json::to_string_pretty(&file).unwrap()
+    }
+
+    pub fn from_json(json: &str, root: &Path) -> Result<Baseline, String> {
+        let file: BaselineFile = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        if file.version != VERSION {
+            return Err(format!(
+                "Unsupported baseline version {}, expected {VERSION}",
+                file.version
+            ));
+        }
+
+        let mut baseline = Baseline::new(root);
+        for entry in file.diagnostics {
+            *baseline.counts.entry(entry.fingerprint).or_default() += entry.count;
+        }
+        Ok(baseline)
+    }
+
+    /// Read a baseline file, file names within it are relative to its folder
+    pub fn read_file(file_name: &Path) -> Result<Baseline, String> {
+        let json = std::fs::read_to_string(file_name)
+            .map_err(|err| format!("Could not read baseline {}: {err}", file_name.display()))?;
+        Baseline::from_json(&json, parent_of(file_name))
+            .map_err(|err| format!("Could not parse baseline {}: {err}", file_name.display()))
+    }
+
+    pub fn write_file(&self, file_name: &Path) -> Result<(), String> {
+        std::fs::write(file_name, self.to_json() + "\n")
+            .map_err(|err| format!("Could not write baseline {}: {err}", file_name.display()))
+    }
+
+    fn fingerprint(&self, project: &Project, diagnostic: &Diagnostic) -> Fingerprint {
+        let unit = project.enclosing_unit_name(&diagnostic.pos);
+        let file = if unit.is_none() {
+            let file_name = diagnostic.pos.file_name();
+            Some(
+                file_name
+                    .strip_prefix(&self.root)
+                    .unwrap_or(file_name)
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+            )
+        } else {
+            None
+        };
+        Fingerprint {
+            rule: diagnostic.code.as_str().to_owned(),
+            unit,
+            file,
+            message: diagnostic.message_without_suggestions().to_owned(),
+        }
+    }
+}
+
+/// The folder of a file, which is the current folder for a relative file name without a folder
+fn parent_of(file_name: &Path) -> &Path {
+    file_name.parent().unwrap_or_else(|| Path::new(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Source;
+    use pretty_assertions::assert_eq;
+
+    const CODE: &str = "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : missing;
+begin
+end architecture;
+";
+
+    fn analyse(code: &str) -> (Project, Vec<Diagnostic>) {
+        let mut project = Project::new();
+        project.update_source(&Source::inline(Path::new("/project/src/ent.vhd"), code));
+        let diagnostics = project.analyse();
+        (project, diagnostics)
+    }
+
+    #[test]
+    fn diagnostics_are_matched_after_lines_move() {
+        let (project, diagnostics) = analyse(CODE);
+        assert_eq!(diagnostics.len(), 1);
+        let mut baseline = Baseline::new(Path::new("/project"));
+        baseline.record(&project, &diagnostics);
+
+        let (project, diagnostics) = analyse(&format!("\n\n\n{CODE}"));
+        assert_eq!(baseline.filter(&project, diagnostics), vec![]);
+    }
+
+    #[test]
+    fn new_occurrences_are_reported() {
+        let (project, diagnostics) = analyse(CODE);
+        let mut baseline = Baseline::new(Path::new("/project"));
+        baseline.record(&project, &diagnostics);
+
+        let code = CODE.replace(
+            "  signal sig : missing;",
+            "  signal sig : missing;\n  signal sig2 : missing;",
+        );
+        let (project, diagnostics) = analyse(&code);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            baseline.filter(&project, diagnostics.clone()),
+            vec![diagnostics[1].clone()]
+        );
+    }
+
+    #[test]
+    fn diagnostics_are_matched_when_suggestions_change() {
+        let (project, diagnostics) = analyse(CODE);
+        let mut baseline = Baseline::new(Path::new("/project"));
+        baseline.record(&project, &diagnostics);
+
+        let code = CODE.replace(
+            "  signal sig : missing;",
+            "  component missin is\n  end component;\n  signal sig : missing;",
+        );
+        let (project, diagnostics) = analyse(&code);
+        assert_eq!(
+            diagnostics[0].message,
+            "No declaration of 'missing', did you mean 'missin'?"
+        );
+        assert_eq!(baseline.filter(&project, diagnostics), vec![]);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let (project, diagnostics) = analyse(CODE);
+        let mut baseline = Baseline::new(Path::new("/project"));
+        baseline.record(&project, &diagnostics);
+
+        let json = baseline.to_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "version": 1,
+                "diagnostics": [{
+                    "rule": "analysis",
+                    "unit": "work.ent(a)",
+                    "message": "No declaration of 'missing'",
+                    "count": 1,
+                }],
+            })
+        );
+        assert_eq!(
+            Baseline::from_json(&json, Path::new("/project")),
+            Ok(baseline)
+        );
+    }
+
+    #[test]
+    fn diagnostics_outside_of_units_are_matched_by_file() {
+        let (project, diagnostics) = analyse("entity ent is\nend entity;\n\nsyntax error");
+        assert!(!diagnostics.is_empty());
+        let mut baseline = Baseline::new(Path::new("/project"));
+        baseline.record(&project, &diagnostics);
+
+        let value: serde_json::Value = serde_json::from_str(&baseline.to_json()).unwrap();
+        assert_eq!(value["diagnostics"][0]["file"], "src/ent.vhd");
+        assert_eq!(baseline.filter(&project, diagnostics), vec![]);
+    }
+
+    #[test]
+    fn unsupported_version_is_an_error() {
+        assert_eq!(
+            Baseline::from_json(r#"{"version": 2, "diagnostics": []}"#, Path::new("")),
+            Err("Unsupported baseline version 2, expected 1".to_owned())
+        );
+    }
+}
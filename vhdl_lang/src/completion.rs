@@ -7,7 +7,7 @@ use crate::data::{ContentReader, Symbol};
 use crate::named_entity::{self, AsUnique, HasEntityId, NamedEntities, Region};
 use crate::syntax::Kind::*;
 use crate::syntax::{Kind, Symbols, Token, TokenAccess, Tokenizer, Value};
-use crate::{AnyEntKind, Design, EntRef, EntityId, Overloaded, Position, Source};
+use crate::{AnyEntKind, Design, EntRef, EntityId, Overloaded, Position, Source, VHDLStandard};
 use std::collections::HashSet;
 use std::default::Default;
 use std::iter::once;
@@ -221,10 +221,16 @@ impl<'a> Searcher for AutocompletionSearcher<'a> {
 ///                                   ^ cursor position
 /// `tokenize_input(input)` -> {USE, ieee, DOT, std_logic_1164, DOT, a}
 ///
+/// Keywords are those of the `standard` that the source is parsed according to.
 /// On error, or if the source is empty, returns an empty vector.
-fn tokenize_input(symbols: &Symbols, source: &Source, cursor: Position) -> Vec<Token> {
+fn tokenize_input(
+    symbols: &Symbols,
+    source: &Source,
+    cursor: Position,
+    standard: VHDLStandard,
+) -> Vec<Token> {
     let contents = source.contents();
-    let mut tokenizer = Tokenizer::new(symbols, source, ContentReader::new(&contents));
+    let mut tokenizer = Tokenizer::new(symbols, source, ContentReader::new(&contents), standard);
     let mut tokens = Vec::new();
     loop {
         match tokenizer.pop() {
@@ -305,14 +311,15 @@ fn list_available_declarations<'a>(
     }
 }
 
-/// Main entry point for completion. Given a source-file and a cursor position,
-/// lists available completion options at the cursor position.
+/// Main entry point for completion. Given a source-file, the revision it is parsed according to
+/// and a cursor position, lists available completion options at the cursor position.
 pub fn list_completion_options<'a>(
     root: &'a DesignRoot,
     source: &Source,
     cursor: Position,
+    standard: VHDLStandard,
 ) -> Vec<CompletionItem<'a>> {
-    let tokens = tokenize_input(root.symbols(), source, cursor);
+    let tokens = tokenize_input(root.symbols(), source, cursor, standard);
     match &tokens[..] {
         [.., kind!(Library)] | [.., kind!(Use)] | [.., kind!(Use), kind!(Identifier)] => {
             list_all_libraries(root)
@@ -347,27 +354,85 @@ mod test {
     #[test]
     fn tokenizing_an_empty_input() {
         let input = Code::new("");
-        let tokens = tokenize_input(&input.symbols, input.source(), Position::new(0, 0));
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            Position::new(0, 0),
+            VHDLStandard::default(),
+        );
         assert_eq!(tokens.len(), 0);
     }
 
+    #[test]
+    fn tokenizing_uses_the_keywords_of_the_standard() {
+        let input = Code::new("use work.context.");
+        let cursor = input.s1("context.").pos().end();
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::VHDL1993,
+        );
+        assert_matches!(
+            tokens[..],
+            [
+                kind!(Use),
+                kind!(Identifier),
+                kind!(Dot),
+                kind!(Identifier),
+                kind!(Dot)
+            ]
+        );
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::VHDL2008,
+        );
+        assert_matches!(
+            tokens[..],
+            [
+                kind!(Use),
+                kind!(Identifier),
+                kind!(Dot),
+                kind!(Context),
+                kind!(Dot)
+            ]
+        );
+    }
+
     #[test]
     fn tokenizing_stops_at_the_cursors_position() {
         let input = Code::new("use ieee.std_logic_1164.all");
         let mut cursor = input.s1("std_logic_11").pos().end();
-        let tokens = tokenize_input(&input.symbols, input.source(), cursor);
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::default(),
+        );
         assert_matches!(
             tokens[..],
             [kind!(Use), kind!(Identifier), kind!(Dot), kind!(Identifier)]
         );
         cursor = input.s1("std_logic_1164").pos().end();
-        let tokens = tokenize_input(&input.symbols, input.source(), cursor);
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::default(),
+        );
         assert_matches!(
             tokens[..],
             [kind!(Use), kind!(Identifier), kind!(Dot), kind!(Identifier)]
         );
         cursor = input.s1("std_logic_1164.").pos().end();
-        let tokens = tokenize_input(&input.symbols, input.source(), cursor);
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::default(),
+        );
         assert_matches!(
             tokens[..],
             [
@@ -379,7 +444,12 @@ mod test {
             ]
         );
         cursor = input.s1("std_logic_1164.all").pos().end();
-        let tokens = tokenize_input(&input.symbols, input.source(), cursor);
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::default(),
+        );
         assert_matches!(
             tokens[..],
             [
@@ -399,7 +469,8 @@ mod test {
         let code = Code::new("library ");
         let (root, _) = input.get_analyzed_root();
         let cursor = code.s1("library ").pos().end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options =
+            list_completion_options(&root, code.source(), cursor, VHDLStandard::default());
         assert_eq!(options, list_all_libraries(&root))
     }
 
@@ -408,7 +479,8 @@ mod test {
         let (root, _) = LibraryBuilder::new().get_analyzed_root();
         let code = Code::new("use std.");
         let cursor = code.pos().end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options =
+            list_completion_options(&root, code.source(), cursor, VHDLStandard::default());
         assert!(options.contains(&CompletionItem::Simple(root.find_textio_pkg())));
         assert!(options.contains(&CompletionItem::Simple(root.find_standard_pkg())));
         assert!(options.contains(&CompletionItem::Simple(root.find_env_pkg())));
@@ -416,7 +488,8 @@ mod test {
 
         let code = Code::new("use std.t");
         let cursor = code.pos().end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options =
+            list_completion_options(&root, code.source(), cursor, VHDLStandard::default());
         // Note that the filtering only happens at client side
         assert!(options.contains(&CompletionItem::Simple(root.find_textio_pkg())));
         assert!(options.contains(&CompletionItem::Simple(root.find_standard_pkg())));
@@ -430,7 +503,8 @@ mod test {
         let code = Code::new("use std.env.");
         let (root, _) = input.get_analyzed_root();
         let cursor = code.pos().end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options =
+            list_completion_options(&root, code.source(), cursor, VHDLStandard::default());
 
         assert!(options.contains(&CompletionItem::Overloaded(
             Designator::Identifier(root.symbol_utf8("stop")),
@@ -482,7 +556,8 @@ mod test {
         );
         let (root, _) = input.get_analyzed_root();
         let cursor = code.s1("generic map (").pos().end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options =
+            list_completion_options(&root, code.source(), cursor, VHDLStandard::default());
         let ent = root
             .search_reference(code.source(), code.s1("B").start())
             .unwrap();
@@ -497,7 +572,8 @@ mod test {
             .unwrap();
 
         let cursor = code.s1("port map (").pos().end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options =
+            list_completion_options(&root, code.source(), cursor, VHDLStandard::default());
         assert!(options.contains(&CompletionItem::Formal(rst)));
         assert!(options.contains(&CompletionItem::Formal(dout)));
         assert_eq!(options.len(), 2);
@@ -506,14 +582,16 @@ mod test {
             clk =>")
             .pos()
             .end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options =
+            list_completion_options(&root, code.source(), cursor, VHDLStandard::default());
         assert_eq!(options.len(), 0);
         let cursor = code
             .s1("port map (
             clk => c")
             .pos()
             .end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options =
+            list_completion_options(&root, code.source(), cursor, VHDLStandard::default());
         assert_eq!(options.len(), 0);
     }
 
@@ -549,7 +627,8 @@ mod test {
             .search_reference(code.source(), code.s1("type T").s1("T").start())
             .unwrap();
         let cursor = code.s1("generic map (").pos().end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options =
+            list_completion_options(&root, code.source(), cursor, VHDLStandard::default());
         assert!(options.contains(&CompletionItem::Formal(bar_func)));
         assert!(options.contains(&CompletionItem::Formal(x)));
         assert!(options.contains(&CompletionItem::Formal(t)));
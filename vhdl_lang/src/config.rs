@@ -6,26 +6,159 @@
 
 //! Configuration of the design hierarchy and other settings
 
+mod file_list;
+mod fusesoc;
+mod import;
+mod modelsim;
+mod quartus;
+mod vivado;
+
 use crate::data::*;
+use crate::standard::VHDLStandard;
+use crate::syntax::ConditionalIdentifiers;
+use file_list::{is_file_list, read_file_list};
 use fnv::FnvHashMap;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
-use std::path::Path;
-use toml::Value;
+use std::path::{Component, Path};
+use toml::{Spanned, Value};
 
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
 pub struct Config {
     // A map from library name to file name
     libraries: FnvHashMap<String, LibraryConfig>,
+    // Revision of the language for files that are not part of any library
+    standard: Option<VHDLStandard>,
+    // Library of files that do not match the patterns of any library
+    default_library: Option<String>,
+    // User defined values of conditional analysis identifiers
+    conditional_analysis: ConditionalIdentifiers,
+    // File of diagnostics that are not reported by the language server
+    baseline: Option<PathBuf>,
+    // Name patterns of the signals that are recognized as synchronous resets
+    reset_signals: Option<Vec<String>>,
+    // Configuration files given by `include`, directly or within another included file
+    included_files: Vec<PathBuf>,
 }
 
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
 pub struct LibraryConfig {
     name: String,
-    patterns: Vec<String>,
+    patterns: Vec<FilePattern>,
+    exclude: Vec<FilePattern>,
     pub(crate) is_third_party: bool,
+    pub(crate) is_synthesizable: bool,
+    standard: Option<VHDLStandard>,
+}
+
+/// The configuration file and line that a setting was read from
+#[derive(Clone, Default, Debug)]
+struct Origin {
+    file_name: Option<PathBuf>,
+    line: Option<usize>,
+}
+
+impl Origin {
+    fn new(file_name: Option<&Path>, line: Option<usize>) -> Origin {
+        Origin {
+            file_name: file_name.map(Path::to_owned),
+            line,
+        }
+    }
+
+    /// Point the message at the origin, settings that were not read from a file have no location
+    fn locate(&self, message: Message) -> Message {
+        match self.file_name {
+            Some(ref file_name) => message.in_file(file_name, self.line),
+            None => message,
+        }
+    }
+}
+
+/// A file name or glob pattern with environment variables expanded
+#[derive(Clone, Debug)]
+struct FilePattern {
+    pattern: String,
+    origin: Origin,
+}
+
+// The origin only locates messages, the same pattern given in another place is still equal
+impl PartialEq for FilePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl Eq for FilePattern {}
+
+impl FilePattern {
+    /// Returns true if the file name matches the pattern without accessing the file system
+    fn matches(&self, file_name: &Path) -> bool {
+        let pattern = normalize_path(Path::new(strip_verbatim_prefix(&self.pattern)));
+        let Some(pattern) = pattern.to_str() else {
+            return false;
+        };
+
+        if is_literal(pattern) {
+            Path::new(pattern) == normalize_path(file_name)
+        } else {
+            glob::Pattern::new(pattern)
+                .map(|pattern| pattern.matches_path(&normalize_path(file_name)))
+                .unwrap_or(false)
+        }
+    }
+}
+
+/// An error in a configuration file
+struct ConfigError {
+    message: String,
+    origin: Origin,
+}
+
+impl ConfigError {
+    fn new(message: impl Into<String>, origin: &Origin) -> ConfigError {
+        ConfigError {
+            message: message.into(),
+            origin: origin.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let located = self.origin.locate(Message::error(self.message.as_str()));
+        write!(f, "{}", located.message)
+    }
+}
+
+/// The position of the settings within a configuration file.
+/// Settings of the wrong type are reported when parsing the configuration,
+/// in which case the locations are simply not known
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct ConfigLocations {
+    include: Vec<Spanned<IgnoredAny>>,
+    standard: Option<Spanned<IgnoredAny>>,
+    default_library: Option<Spanned<IgnoredAny>>,
+    baseline: Option<Spanned<IgnoredAny>>,
+    reset_signals: Option<Spanned<IgnoredAny>>,
+    conditional_analysis: BTreeMap<String, Spanned<IgnoredAny>>,
+    libraries: BTreeMap<String, LibraryLocations>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct LibraryLocations {
+    files: Vec<Spanned<IgnoredAny>>,
+    exclude: Vec<Spanned<IgnoredAny>>,
+    is_third_party: Option<Spanned<IgnoredAny>>,
+    synthesizable: Option<Spanned<IgnoredAny>>,
+    standard: Option<Spanned<IgnoredAny>>,
 }
 
 impl LibraryConfig {
@@ -34,51 +167,145 @@ impl LibraryConfig {
     /// Files that do not exist produce a warning message
     pub fn file_names(&self, messages: &mut dyn MessageHandler) -> Vec<PathBuf> {
         let mut result = Vec::new();
+        let mut file_lists = Vec::new();
         for pattern in self.patterns.iter() {
-            let stripped_pattern = if cfg!(windows) {
-                pattern.strip_prefix("\\\\?\\").unwrap_or(pattern.as_str())
-            } else {
-                pattern.as_str()
-            };
+            Self::add_file_names(pattern, &mut file_lists, &mut result, messages);
+        }
+        result.retain(|file_name| !self.is_excluded(file_name));
+        Self::remove_duplicates(result)
+    }
 
-            if is_literal(stripped_pattern) {
-                let file_path = Path::new(pattern).to_owned();
+    fn add_file_names(
+        file_pattern: &FilePattern,
+        file_lists: &mut Vec<PathBuf>,
+        result: &mut Vec<PathBuf>,
+        messages: &mut dyn MessageHandler,
+    ) {
+        let FilePattern { pattern, origin } = file_pattern;
+        let stripped_pattern = strip_verbatim_prefix(pattern);
+
+        if is_literal(stripped_pattern) {
+            let file_path = Path::new(pattern).to_owned();
+
+            if !file_path.exists() {
+                messages.push(
+                    origin.locate(Message::warning(format! {"File {pattern} does not exist"})),
+                );
+            } else if is_file_list(&file_path) {
+                let normalized = normalize_path(&file_path);
+                if file_lists.contains(&normalized) {
+                    messages.push(origin.locate(Message::error(format!(
+                        "File list {pattern} includes itself"
+                    ))));
+                    return;
+                }
 
-                if file_path.exists() {
-                    result.push(file_path);
-                } else {
-                    messages.push(Message::warning(format! {"File {pattern} does not exist"}));
+                file_lists.push(normalized);
+                for pattern in read_file_list(&file_path, origin, &mut Vec::new(), messages) {
+                    Self::add_file_names(&pattern, file_lists, result, messages);
                 }
+                file_lists.pop();
             } else {
-                match glob::glob(stripped_pattern) {
-                    Ok(paths) => {
-                        let mut empty_pattern = true;
-
-                        for file_path_or_error in paths {
-                            empty_pattern = false;
-                            match file_path_or_error {
-                                Ok(file_path) => {
-                                    result.push(file_path);
-                                }
-                                Err(err) => {
-                                    messages.push(Message::error(err.to_string()));
-                                }
+                result.push(file_path);
+            }
+        } else {
+            match glob::glob(stripped_pattern) {
+                Ok(paths) => {
+                    let mut empty_pattern = true;
+
+                    for file_path_or_error in paths {
+                        empty_pattern = false;
+                        match file_path_or_error {
+                            Ok(file_path) => {
+                                result.push(file_path);
+                            }
+                            Err(err) => {
+                                messages.push(origin.locate(Message::error(err.to_string())));
                             }
                         }
+                    }
 
-                        if empty_pattern {
-                            messages.push(Message::warning(format!(
-                                "Pattern '{stripped_pattern}' did not match any file"
-                            )));
-                        }
+                    if empty_pattern {
+                        messages.push(origin.locate(Message::warning(format!(
+                            "Pattern '{stripped_pattern}' did not match any file"
+                        ))));
                     }
-                    Err(err) => {
-                        messages.push(Message::error(format!("Invalid pattern '{pattern}' {err}")));
+                }
+                Err(err) => {
+                    messages.push(
+                        origin.locate(Message::error(format!("Invalid pattern '{pattern}' {err}"))),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns true if the file name matches any of the file patterns of the library.
+    /// The file does not need to exist, so files that are created after the project
+    /// was loaded may be mapped as well
+    pub fn matches(&self, file_name: &Path) -> bool {
+        /// Guards against file lists that include each other
+        const MAX_FILE_LIST_DEPTH: usize = 16;
+
+        fn matches_any(patterns: &[FilePattern], file_name: &Path, depth: usize) -> bool {
+            patterns.iter().any(|pattern| {
+                if is_file_list(Path::new(&pattern.pattern)) && depth < MAX_FILE_LIST_DEPTH {
+                    let file_list = read_file_list(
+                        Path::new(&pattern.pattern),
+                        &pattern.origin,
+                        &mut Vec::new(),
+                        &mut NullMessages,
+                    );
+                    matches_any(&file_list, file_name, depth + 1)
+                } else {
+                    pattern.matches(file_name)
+                }
+            })
+        }
+
+        matches_any(&self.patterns, file_name, 0) && !self.is_excluded(file_name)
+    }
+
+    /// The file lists that the files of the library are read from, including nested lists.
+    /// Modifying one of them may change the files of the library
+    pub fn file_lists(&self) -> Vec<PathBuf> {
+        fn add_file_lists(patterns: &[FilePattern], result: &mut Vec<PathBuf>) {
+            for pattern in patterns {
+                let file_name = Path::new(&pattern.pattern);
+                if !is_file_list(file_name)
+                    || !file_name.exists()
+                    || result.contains(&normalize_path(file_name))
+                {
+                    continue;
+                }
+
+                let mut read_lists = Vec::new();
+                let file_list = read_file_list(
+                    file_name,
+                    &pattern.origin,
+                    &mut read_lists,
+                    &mut NullMessages,
+                );
+                for read_list in read_lists {
+                    let read_list = normalize_path(&read_list);
+                    if !result.contains(&read_list) {
+                        result.push(read_list);
                     }
                 }
+                add_file_lists(&file_list, result);
             }
         }
-        Self::remove_duplicates(result)
+
+        let mut result = Vec::new();
+        add_file_lists(&self.patterns, &mut result);
+        result
+    }
+
+    /// Returns true if the file name matches any of the exclude patterns of the library
+    fn is_excluded(&self, file_name: &Path) -> bool {
+        self.exclude
+            .iter()
+            .any(|pattern| pattern.matches(file_name))
     }
 
     /// Remove duplicate file names from the result
@@ -98,69 +325,291 @@ impl LibraryConfig {
     pub fn name(&self) -> &str {
         self.name.as_str()
     }
+
+    /// The revision of the language that the files of the library are written in
+    pub fn standard(&self) -> VHDLStandard {
+        self.standard.unwrap_or_default()
+    }
 }
 
 impl Config {
     pub fn from_str(string: &str, parent: &Path) -> Result<Config, String> {
-        let config = string.parse::<Value>().map_err(|err| err.to_string())?;
+        Config::parse(string, parent, None, &mut Vec::new()).map_err(|err| err.to_string())
+    }
+
+    /// Parse a configuration, `file_name` is the file that the configuration was read from if any.
+    /// `including` are the configuration files that are currently being included
+    fn parse(
+        string: &str,
+        parent: &Path,
+        file_name: Option<&Path>,
+        including: &mut Vec<PathBuf>,
+    ) -> Result<Config, ConfigError> {
+        let file_origin = Origin::new(file_name, None);
+        let config = string
+            .parse::<Value>()
+            .map_err(|err| ConfigError::new(err.to_string(), &file_origin))?;
+        let locations: ConfigLocations = toml::from_str(string).unwrap_or_default();
+        let origin_of = |location: Option<&Spanned<IgnoredAny>>| {
+            Origin::new(
+                file_name,
+                location.map(|location| line_of(string, location.span().start)),
+            )
+        };
+
+        // Settings of included files are overridden by the settings of the including file
+        let mut result = Config::default();
+        if let Some(include) = config.get("include") {
+            let include = include
+                .as_array()
+                .ok_or_else(|| ConfigError::new("include must be an array", &file_origin))?;
+            for (idx, include_file) in include.iter().enumerate() {
+                let origin = origin_of(locations.include.get(idx));
+                let include_file = include_file.as_str().ok_or_else(|| {
+                    ConfigError::new(format!("not a string {include_file}"), &origin)
+                })?;
+                let include_file = resolve_path(include_file, parent)
+                    .map_err(|err| ConfigError::new(err, &origin))?;
+                let included =
+                    Config::parse_included(Path::new(&include_file), &origin, including)?;
+                result.append(&included, &mut NullMessages);
+                result.included_files.push(PathBuf::from(include_file));
+            }
+        }
+
         let mut libraries = FnvHashMap::default();
 
+        let standard = config
+            .get("standard")
+            .map(|value| {
+                parse_standard(value).map_err(|err| {
+                    ConfigError::new(
+                        format!("{err} for standard"),
+                        &origin_of(locations.standard.as_ref()),
+                    )
+                })
+            })
+            .transpose()?;
+
+        let default_library = config
+            .get("default_library")
+            .map(|value| {
+                value.as_str().map(str::to_owned).ok_or_else(|| {
+                    ConfigError::new(
+                        format!(
+                            "Expected string but got {} for default_library",
+                            value.type_str()
+                        ),
+                        &origin_of(locations.default_library.as_ref()),
+                    )
+                })
+            })
+            .transpose()?;
+
+        let baseline = config
+            .get("baseline")
+            .map(|value| {
+                let origin = origin_of(locations.baseline.as_ref());
+                let file_name = value.as_str().ok_or_else(|| {
+                    ConfigError::new(
+                        format!("Expected string but got {} for baseline", value.type_str()),
+                        &origin,
+                    )
+                })?;
+                resolve_path(file_name, parent)
+                    .map(PathBuf::from)
+                    .map_err(|err| ConfigError::new(err, &origin))
+            })
+            .transpose()?;
+
+        let reset_signals = config
+            .get("reset_signals")
+            .map(|value| {
+                let origin = origin_of(locations.reset_signals.as_ref());
+                let expected_strings = || {
+                    ConfigError::new(
+                        format!(
+                            "Expected array of strings but got {} for reset_signals",
+                            value.type_str()
+                        ),
+                        &origin,
+                    )
+                };
+                let patterns = value.as_array().ok_or_else(expected_strings)?;
+                patterns
+                    .iter()
+                    .map(|pattern| {
+                        let pattern = pattern.as_str().ok_or_else(expected_strings)?;
+                        glob::Pattern::new(pattern).map_err(|err| {
+                            ConfigError::new(
+                                format!("Invalid pattern '{pattern}' for reset_signals: {err}"),
+                                &origin,
+                            )
+                        })?;
+                        Ok(pattern.to_owned())
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        let mut conditional_analysis = ConditionalIdentifiers::default();
+        if let Some(identifiers) = config.get("conditional_analysis") {
+            let identifiers = identifiers.as_table().ok_or_else(|| {
+                ConfigError::new("conditional_analysis must be a table", &file_origin)
+            })?;
+            for (name, value) in identifiers.iter() {
+                let value = value.as_str().ok_or_else(|| {
+                    ConfigError::new(
+                        format!("Expected string value for conditional analysis identifier {name}"),
+                        &origin_of(locations.conditional_analysis.get(name)),
+                    )
+                })?;
+                conditional_analysis.set(name, value);
+            }
+        }
+
         let libs = config
             .get("libraries")
-            .ok_or("missing field libraries")?
+            .ok_or_else(|| ConfigError::new("missing field libraries", &file_origin))?
             .as_table()
-            .ok_or("libraries must be a table")?;
+            .ok_or_else(|| ConfigError::new("libraries must be a table", &file_origin))?;
 
+        let no_locations = LibraryLocations::default();
         for (name, lib) in libs.iter() {
+            let lib_locations = locations.libraries.get(name).unwrap_or(&no_locations);
+
             if name.to_lowercase() == "work" {
-                return Err(format!(
+                return Err(ConfigError::new(format!(
                     "The '{}' library is not a valid library.\nHint: To use a library that contains all files, use a common name for all libraries, i.e., 'defaultlib'",
                     name
-                ));
+                ), &origin_of(lib_locations.files.first())));
             }
 
             let file_arr = lib
                 .get("files")
-                .ok_or_else(|| format!("missing field files for library {name}"))?
+                .ok_or_else(|| {
+                    ConfigError::new(
+                        format!("missing field files for library {name}"),
+                        &file_origin,
+                    )
+                })?
                 .as_array()
-                .ok_or_else(|| format!("files for library {name} is not array"))?;
-
-            let mut patterns = Vec::new();
-            for file in file_arr.iter() {
-                let file = file
-                    .as_str()
-                    .ok_or_else(|| format!("not a string {file}"))?;
-
-                let path = parent.join(file);
-                let path = path
-                    .to_str()
-                    .ok_or_else(|| format!("Could not convert {path:?} to string"))?
-                    .to_owned();
-                patterns.push(path);
-            }
+                .ok_or_else(|| {
+                    ConfigError::new(
+                        format!("files for library {name} is not array"),
+                        &file_origin,
+                    )
+                })?;
+            let patterns = parse_file_patterns(file_arr, &lib_locations.files, parent, &origin_of)?;
+
+            let exclude = match lib.get("exclude") {
+                Some(exclude) => {
+                    let exclude_arr = exclude.as_array().ok_or_else(|| {
+                        ConfigError::new(
+                            format!("exclude for library {name} is not array"),
+                            &file_origin,
+                        )
+                    })?;
+                    parse_file_patterns(exclude_arr, &lib_locations.exclude, parent, &origin_of)?
+                }
+                None => Vec::new(),
+            };
 
             let mut is_third_party = false;
             if let Some(opt) = lib.get("is_third_party") {
                 if let Some(opt) = opt.as_bool() {
                     is_third_party = opt;
                 } else {
-                    return Err(format!(
-                        "Expected is_third_party to be boolean for library {name}"
+                    return Err(ConfigError::new(
+                        format!("Expected is_third_party to be boolean for library {name}"),
+                        &origin_of(lib_locations.is_third_party.as_ref()),
                     ));
                 }
             }
 
+            let mut is_synthesizable = false;
+            if let Some(opt) = lib.get("synthesizable") {
+                if let Some(opt) = opt.as_bool() {
+                    is_synthesizable = opt;
+                } else {
+                    return Err(ConfigError::new(
+                        format!("Expected synthesizable to be boolean for library {name}"),
+                        &origin_of(lib_locations.synthesizable.as_ref()),
+                    ));
+                }
+            }
+
+            let library_standard = lib
+                .get("standard")
+                .map(|value| {
+                    parse_standard(value).map_err(|err| {
+                        ConfigError::new(
+                            format!("{err} for standard of library {name}"),
+                            &origin_of(lib_locations.standard.as_ref()),
+                        )
+                    })
+                })
+                .transpose()?;
+
             libraries.insert(
                 name.to_owned(),
                 LibraryConfig {
                     name: name.to_owned(),
                     patterns,
+                    exclude,
                     is_third_party,
+                    is_synthesizable,
+                    standard: library_standard.or(standard),
                 },
             );
         }
 
-        Ok(Config { libraries })
+        result.append(
+            &Config {
+                libraries,
+                standard,
+                default_library,
+                conditional_analysis,
+                baseline,
+                reset_signals,
+                included_files: Vec::new(),
+            },
+            &mut NullMessages,
+        );
+        Ok(result)
+    }
+
+    /// Parse a configuration file given by an `include` directive
+    fn parse_included(
+        file_name: &Path,
+        origin: &Origin,
+        including: &mut Vec<PathBuf>,
+    ) -> Result<Config, ConfigError> {
+        let canonical = dunce::canonicalize(file_name).map_err(|err| {
+            ConfigError::new(
+                format!("Could not include {}: {err}", file_name.to_string_lossy()),
+                origin,
+            )
+        })?;
+        if including.contains(&canonical) {
+            return Err(ConfigError::new(
+                format!("Circular include of {}", file_name.to_string_lossy()),
+                origin,
+            ));
+        }
+
+        let contents = std::fs::read_to_string(&canonical).map_err(|err| {
+            ConfigError::new(
+                format!("Could not include {}: {err}", file_name.to_string_lossy()),
+                origin,
+            )
+        })?;
+        let parent = file_name.parent().unwrap_or(Path::new(""));
+
+        including.push(canonical);
+        let config = Config::parse(&contents, parent, Some(file_name), including);
+        including.pop();
+        config
     }
 
     pub fn read_file_path(file_name: &Path) -> io::Result<Config> {
@@ -169,8 +618,10 @@ impl Config {
         file.read_to_string(&mut contents)?;
 
         let parent = file_name.parent().unwrap();
+        let mut including: Vec<PathBuf> = dunce::canonicalize(file_name).into_iter().collect();
 
-        Config::from_str(&contents, parent).map_err(|msg| io::Error::new(io::ErrorKind::Other, msg))
+        Config::parse(&contents, parent, Some(file_name), &mut including)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))
     }
 
     pub fn get_library<'a>(&'a self, name: &str) -> Option<&'a LibraryConfig> {
@@ -181,10 +632,70 @@ impl Config {
         self.libraries.values()
     }
 
+    /// The revision of the language used for the library.
+    /// Libraries that are not configured use the top level setting.
+    pub fn standard_of(&self, library_name: &str) -> VHDLStandard {
+        match self.get_library(library_name) {
+            Some(library) => library.standard(),
+            None => self.standard.unwrap_or_default(),
+        }
+    }
+
+    /// The libraries whose file patterns match the file name
+    pub fn libraries_of_file<'a>(
+        &'a self,
+        file_name: &'a Path,
+    ) -> impl Iterator<Item = &'a LibraryConfig> + 'a {
+        self.iter_libraries()
+            .filter(move |library| library.matches(file_name))
+    }
+
+    /// The library of files that are not matched by any library, if configured
+    pub fn default_library(&self) -> Option<&str> {
+        self.default_library.as_deref()
+    }
+
+    /// The baseline file of diagnostics that should not be reported, if configured
+    pub fn baseline(&self) -> Option<&Path> {
+        self.baseline.as_deref()
+    }
+
+    /// The name patterns of signals that are recognized as synchronous resets, if configured
+    pub fn reset_signals(&self) -> Option<&[String]> {
+        self.reset_signals.as_deref()
+    }
+
+    /// The values of conditional analysis identifiers given by the user
+    pub fn conditional_analysis(&self) -> &ConditionalIdentifiers {
+        &self.conditional_analysis
+    }
+
+    /// The configuration files that were included, directly or within another included file
+    pub fn included_files(&self) -> &[PathBuf] {
+        &self.included_files
+    }
+
     /// Append another config to self
     ///
     /// In case of conflict the appended config takes precedence
     pub fn append(&mut self, config: &Config, messages: &mut dyn MessageHandler) {
+        if config.standard.is_some() {
+            self.standard = config.standard;
+        }
+        if config.default_library.is_some() {
+            self.default_library = config.default_library.clone();
+        }
+        if config.baseline.is_some() {
+            self.baseline = config.baseline.clone();
+        }
+        if config.reset_signals.is_some() {
+            self.reset_signals = config.reset_signals.clone();
+        }
+        self.conditional_analysis
+            .append(&config.conditional_analysis);
+        self.included_files
+            .extend(config.included_files.iter().cloned());
+
         for library in config.iter_libraries() {
             if let Some(parent_library) = self.libraries.get_mut(&library.name) {
                 *parent_library = library.clone();
@@ -199,6 +710,84 @@ impl Config {
         }
     }
 
+    /// The configuration in the format of `vhdl_ls.toml`.
+    /// File names below `parent` are written relative to it
+    pub fn to_toml(&self, parent: &Path) -> String {
+        let patterns = |patterns: &[FilePattern]| {
+            Value::Array(
+                patterns
+                    .iter()
+                    .map(|pattern| {
+                        let path = Path::new(&pattern.pattern);
+                        let path = path.strip_prefix(parent).unwrap_or(path);
+                        Value::String(path.to_string_lossy().into_owned())
+                    })
+                    .collect(),
+            )
+        };
+
+        let mut root = toml::Table::new();
+        if let Some(standard) = self.standard {
+            root.insert(
+                "standard".to_owned(),
+                Value::String(standard.year().to_owned()),
+            );
+        }
+        if let Some(ref default_library) = self.default_library {
+            root.insert(
+                "default_library".to_owned(),
+                Value::String(default_library.clone()),
+            );
+        }
+        if let Some(ref baseline) = self.baseline {
+            let baseline = baseline.strip_prefix(parent).unwrap_or(baseline);
+            root.insert(
+                "baseline".to_owned(),
+                Value::String(baseline.to_string_lossy().into_owned()),
+            );
+        }
+        if let Some(ref reset_signals) = self.reset_signals {
+            root.insert(
+                "reset_signals".to_owned(),
+                Value::Array(reset_signals.iter().cloned().map(Value::String).collect()),
+            );
+        }
+
+        let identifiers: toml::Table = self
+            .conditional_analysis
+            .iter()
+            .map(|(name, value)| (name.to_owned(), Value::String(value.to_owned())))
+            .collect();
+        if !identifiers.is_empty() {
+            root.insert("conditional_analysis".to_owned(), Value::Table(identifiers));
+        }
+
+        let mut libraries = toml::Table::new();
+        for library in self.iter_libraries() {
+            let mut table = toml::Table::new();
+            table.insert("files".to_owned(), patterns(&library.patterns));
+            if !library.exclude.is_empty() {
+                table.insert("exclude".to_owned(), patterns(&library.exclude));
+            }
+            if library.is_third_party {
+                table.insert("is_third_party".to_owned(), Value::Boolean(true));
+            }
+            if library.is_synthesizable {
+                table.insert("synthesizable".to_owned(), Value::Boolean(true));
+            }
+            if let Some(standard) = library.standard.filter(|std| Some(*std) != self.standard) {
+                table.insert(
+                    "standard".to_owned(),
+                    Value::String(standard.year().to_owned()),
+                );
+            }
+            libraries.insert(library.name.clone(), Value::Table(table));
+        }
+        root.insert("libraries".to_owned(), Value::Table(libraries));
+
+        toml::to_string_pretty(&root).expect("A toml table can always be serialized")
+    }
+
     /// Load configuration file from installation folder
     fn load_installed_config(&mut self, messages: &mut dyn MessageHandler) {
         let search_paths = [
@@ -277,6 +866,41 @@ impl Config {
     }
 }
 
+/// The standard may be given both as a string and as an integer, i.e `standard = 1993`
+fn parse_standard(value: &Value) -> Result<VHDLStandard, String> {
+    match value {
+        Value::String(year) => year.parse(),
+        Value::Integer(year) => year.to_string().parse(),
+        _ => Err(format!("Expected string but got {}", value.type_str())),
+    }
+}
+
+/// Windows paths may be given in the verbatim form that glob does not support
+fn strip_verbatim_prefix(pattern: &str) -> &str {
+    if cfg!(windows) {
+        pattern.strip_prefix("\\\\?\\").unwrap_or(pattern)
+    } else {
+        pattern
+    }
+}
+
+/// Lexically remove '.' and '..' components of a path without accessing the file system
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !result.pop() {
+                    result.push(component);
+                }
+            }
+            _ => result.push(component),
+        }
+    }
+    result
+}
+
 /// Returns true if the pattern is a plain file name and not a glob pattern
 fn is_literal(pattern: &str) -> bool {
     for chr in pattern.chars() {
@@ -290,6 +914,68 @@ fn is_literal(pattern: &str) -> bool {
     true
 }
 
+/// The line number of a byte offset into a configuration file
+fn line_of(string: &str, offset: usize) -> usize {
+    string[..offset].matches('\n').count() + 1
+}
+
+/// Parse an array of file names or patterns, `locations` are the positions of the entries
+fn parse_file_patterns(
+    values: &[Value],
+    locations: &[Spanned<IgnoredAny>],
+    parent: &Path,
+    origin_of: &dyn Fn(Option<&Spanned<IgnoredAny>>) -> Origin,
+) -> Result<Vec<FilePattern>, ConfigError> {
+    let mut patterns = Vec::with_capacity(values.len());
+    for (idx, value) in values.iter().enumerate() {
+        let origin = origin_of(locations.get(idx));
+        let file = value
+            .as_str()
+            .ok_or_else(|| ConfigError::new(format!("not a string {value}"), &origin))?;
+        let pattern = resolve_path(file, parent).map_err(|err| ConfigError::new(err, &origin))?;
+        patterns.push(FilePattern { pattern, origin });
+    }
+    Ok(patterns)
+}
+
+/// Expand environment variables and the home folder in a path and make it relative to `parent`
+fn resolve_path(path: &str, parent: &Path) -> Result<String, String> {
+    let path = parent.join(expand_path(path)?);
+    path.to_str()
+        .map(str::to_owned)
+        .ok_or_else(|| format!("Could not convert {path:?} to string"))
+}
+
+/// Replace `${NAME}` with the value of the environment variable and a leading `~` with the home folder
+fn expand_path(path: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(path.len());
+    let mut rest = path;
+
+    if let Some(after_tilde) = rest.strip_prefix('~') {
+        if after_tilde.is_empty() || after_tilde.starts_with(['/', '\\']) {
+            let home_dir =
+                dirs::home_dir().ok_or_else(|| format!("Could not find home folder for {path}"))?;
+            result.push_str(&home_dir.to_string_lossy());
+            rest = after_tilde;
+        }
+    }
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after_start = &rest[start + 2..];
+        let end = after_start
+            .find('}')
+            .ok_or_else(|| format!("Missing '}}' after '${{' in {path}"))?;
+        let name = &after_start[..end];
+        let value = env::var(name)
+            .map_err(|_| format!("Environment variable '{name}' is not set in {path}"))?;
+        result.push_str(&value);
+        rest = &after_start[end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -366,6 +1052,168 @@ lib1.files = [
         assert_eq!(messages, vec![]);
     }
 
+    #[test]
+    fn synthesizable_libraries() {
+        let config = Config::from_str(
+            "
+[libraries]
+rtl.files = []
+rtl.synthesizable = true
+tb.files = []
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        assert!(config.get_library("rtl").unwrap().is_synthesizable);
+        assert!(!config.get_library("tb").unwrap().is_synthesizable);
+
+        assert_eq!(
+            Config::from_str(
+                "
+[libraries]
+rtl.files = []
+rtl.synthesizable = 'yes'
+",
+                Path::new(""),
+            ),
+            Err("Expected synthesizable to be boolean for library rtl".to_owned())
+        );
+    }
+
+    #[test]
+    fn language_standard() {
+        let config = Config::from_str(
+            "
+standard = '2002'
+[libraries]
+legacy.files = []
+legacy.standard = 1993
+rtl.files = []
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        assert_eq!(config.standard_of("legacy"), VHDLStandard::VHDL1993);
+        assert_eq!(config.standard_of("rtl"), VHDLStandard::VHDL2002);
+        assert_eq!(config.standard_of("work"), VHDLStandard::VHDL2002);
+        assert_eq!(Config::default().standard_of("rtl"), VHDLStandard::VHDL2008);
+
+        assert_eq!(
+            Config::from_str(
+                "
+[libraries]
+rtl.files = []
+rtl.standard = '1987'
+",
+                Path::new(""),
+            ),
+            Err("Unknown VHDL standard '1987', expected one of 1993, 2002, 2008 or 2019 for standard of library rtl".to_owned())
+        );
+    }
+
+    #[test]
+    fn default_library() {
+        let config = Config::from_str(
+            "
+default_library = 'scratch'
+[libraries]
+",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(config.default_library(), Some("scratch"));
+        assert_eq!(Config::default().default_library(), None);
+
+        let mut appended = Config::default();
+        appended.append(&config, &mut Vec::new());
+        assert_eq!(appended.default_library(), Some("scratch"));
+
+        assert_eq!(
+            Config::from_str(
+                "
+default_library = 1
+[libraries]
+",
+                Path::new(""),
+            ),
+            Err("Expected string but got integer for default_library".to_owned())
+        );
+    }
+
+    #[test]
+    fn libraries_of_file_match_patterns_of_files_that_do_not_exist() {
+        let parent = Path::new("/project/sub");
+        let config = Config::from_str(
+            "
+[libraries]
+rtl.files = ['rtl/*.vhd', 'common/pkg.vhd']
+tb.files = ['../tb/**/*.vhd', 'common/pkg.vhd']
+",
+            parent,
+        )
+        .unwrap();
+
+        let libraries_of = |file_name: &str| {
+            let mut names: Vec<String> = config
+                .libraries_of_file(Path::new(file_name))
+                .map(|library| library.name().to_owned())
+                .collect();
+            names.sort_unstable();
+            names
+        };
+
+        assert_eq!(libraries_of("/project/sub/rtl/new.vhd"), vec!["rtl"]);
+        assert_eq!(libraries_of("/project/tb/unit/new_tb.vhd"), vec!["tb"]);
+        assert_eq!(
+            libraries_of("/project/sub/./common/pkg.vhd"),
+            vec!["rtl", "tb"]
+        );
+        assert!(libraries_of("/project/sub/other/new.vhd").is_empty());
+    }
+
+    #[test]
+    fn conditional_analysis_identifiers() {
+        let config = Config::from_str(
+            "
+[conditional_analysis]
+TOOL_TYPE = 'SYNTHESIS'
+my_define = '1'
+[libraries]
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        let identifiers = config.conditional_analysis();
+        assert_eq!(
+            identifiers.get("tool_type", VHDLStandard::VHDL2019),
+            Some("SYNTHESIS".to_owned())
+        );
+        assert_eq!(
+            identifiers.get("MY_DEFINE", VHDLStandard::VHDL2019),
+            Some("1".to_owned())
+        );
+        assert_eq!(
+            identifiers.get("VHDL_VERSION", VHDLStandard::VHDL2019),
+            Some("2019".to_owned())
+        );
+        assert_eq!(identifiers.get("UNDEFINED", VHDLStandard::VHDL2019), None);
+
+        assert_eq!(
+            Config::from_str(
+                "
+[conditional_analysis]
+my_define = 1
+[libraries]
+",
+                Path::new(""),
+            ),
+            Err("Expected string value for conditional analysis identifier my_define".to_owned())
+        );
+    }
+
     #[test]
     fn test_append_config() {
         let parent0 = Path::new("parent_folder0");
@@ -540,4 +1388,338 @@ work.files = [
         );
         assert_eq!(config.expect_err("Expected erroneous config"), "The 'work' library is not a valid library.\nHint: To use a library that contains all files, use a common name for all libraries, i.e., 'defaultlib'")
     }
+
+    /// Utility function to create a file with contents in parent folder
+    fn write(parent: &Path, file_name: &str, contents: &str) -> PathBuf {
+        let path = parent.join(file_name);
+        std::fs::write(&path, contents).expect("Assume file can be written");
+        path
+    }
+
+    #[test]
+    fn exclude_patterns() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = ['*.vhd']
+lib.exclude = ['*_tb.vhd']
+",
+            parent,
+        )
+        .unwrap();
+
+        let pkg_path = touch(parent, "pkg.vhd");
+        touch(parent, "pkg_tb.vhd");
+
+        let lib = config.get_library("lib").unwrap();
+        let mut messages = vec![];
+        assert_files_eq(
+            &lib.file_names(&mut messages),
+            std::slice::from_ref(&pkg_path),
+        );
+        assert_eq!(messages, vec![]);
+
+        assert!(lib.matches(&pkg_path));
+        assert!(!lib.matches(&parent.join("pkg_tb.vhd")));
+    }
+
+    #[test]
+    fn expands_environment_variables_and_home_folder() {
+        env::set_var("VHDL_LS_TEST_SRC_DIR", "src");
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = ['${VHDL_LS_TEST_SRC_DIR}/pkg.vhd', '~/home.vhd']
+",
+            parent,
+        )
+        .unwrap();
+
+        let lib = config.get_library("lib").unwrap();
+        assert!(lib.matches(&parent.join("src").join("pkg.vhd")));
+        if let Some(home_dir) = dirs::home_dir() {
+            assert!(lib.matches(&home_dir.join("home.vhd")));
+        }
+    }
+
+    #[test]
+    fn errors_refer_to_file_and_line() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        let config_path = write(
+            parent,
+            "vhdl_ls.toml",
+            "
+[libraries]
+lib.files = [
+  'pkg.vhd',
+  '${VHDL_LS_TEST_UNSET_VARIABLE}/ent.vhd',
+]
+",
+        );
+
+        let err = Config::read_file_path(&config_path).expect_err("Expected erroneous config");
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Environment variable 'VHDL_LS_TEST_UNSET_VARIABLE' is not set in ${{VHDL_LS_TEST_UNSET_VARIABLE}}/ent.vhd (In file {}:5)",
+                config_path.to_string_lossy()
+            )
+        );
+    }
+
+    #[test]
+    fn warnings_refer_to_file_and_line() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        let config_path = write(
+            parent,
+            "vhdl_ls.toml",
+            "
+[libraries]
+lib.files = [
+  'missing.vhd',
+]
+",
+        );
+
+        let config = Config::read_file_path(&config_path).unwrap();
+        let mut messages = vec![];
+        config.get_library("lib").unwrap().file_names(&mut messages);
+        assert_eq!(
+            messages,
+            vec![Message::warning(format!(
+                "File {} does not exist (In file {}:4)",
+                parent.join("missing.vhd").to_string_lossy(),
+                config_path.to_string_lossy()
+            ))]
+        );
+    }
+
+    #[test]
+    fn included_configs_are_overridden() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        std::fs::create_dir(parent.join("other")).unwrap();
+        let included_path = write(
+            parent,
+            "other/vhdl_ls.toml",
+            "
+standard = '1993'
+[libraries]
+lib.files = ['lib.vhd']
+other.files = ['other.vhd']
+",
+        );
+        let config_path = write(
+            parent,
+            "vhdl_ls.toml",
+            "
+include = ['other/vhdl_ls.toml']
+[libraries]
+lib.files = ['own.vhd']
+",
+        );
+
+        let config = Config::read_file_path(&config_path).unwrap();
+        assert_eq!(config.standard_of("other"), VHDLStandard::VHDL1993);
+        assert_eq!(config.standard_of("unknown"), VHDLStandard::VHDL1993);
+        assert!(config
+            .get_library("other")
+            .unwrap()
+            .matches(&parent.join("other").join("other.vhd")));
+        let lib = config.get_library("lib").unwrap();
+        assert!(lib.matches(&parent.join("own.vhd")));
+        assert!(!lib.matches(&parent.join("other").join("lib.vhd")));
+        assert_eq!(config.included_files(), &[included_path]);
+    }
+
+    #[test]
+    fn circular_include_is_an_error() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        let first = write(
+            parent,
+            "first.toml",
+            "
+include = ['second.toml']
+[libraries]
+",
+        );
+        let second = write(
+            parent,
+            "second.toml",
+            "
+# Includes the including file
+include = ['first.toml']
+[libraries]
+",
+        );
+
+        let err = Config::read_file_path(&first).expect_err("Expected erroneous config");
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Circular include of {} (In file {}:3)",
+                first.to_string_lossy(),
+                second.to_string_lossy()
+            )
+        );
+    }
+
+    #[test]
+    fn file_lists() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        std::fs::create_dir(parent.join("ip")).unwrap();
+        let pkg_path = touch(parent, "pkg.vhd");
+        let ent_path = touch(&parent.join("ip"), "ent.vhd");
+        let nested = write(
+            parent,
+            "ip/nested.f",
+            "
+// Relative to the folder of the file list
+ent.vhd
+missing.vhd
+",
+        );
+        let file_list = write(
+            parent,
+            "files.f",
+            "
++incdir+include
+pkg.vhd # a comment
+-f ip/nested.f
+",
+        );
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = ['files.f']
+",
+            parent,
+        )
+        .unwrap();
+
+        let lib = config.get_library("lib").unwrap();
+        let mut messages = vec![];
+        assert_files_eq(
+            &lib.file_names(&mut messages),
+            &[pkg_path.clone(), ent_path.clone()],
+        );
+        assert_eq!(
+            messages,
+            vec![Message::warning(format!(
+                "File {} does not exist (In file {}:4)",
+                parent.join("ip").join("missing.vhd").to_string_lossy(),
+                nested.to_string_lossy()
+            ))]
+        );
+
+        assert!(lib.matches(&pkg_path));
+        assert!(lib.matches(&ent_path));
+        assert!(!lib.matches(&file_list));
+        assert_files_eq(&lib.file_lists(), &[file_list, nested]);
+    }
+
+    #[test]
+    fn baseline() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+baseline = 'baseline.json'
+[libraries]
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(
+            config.baseline(),
+            Some(parent.join("baseline.json").as_path())
+        );
+        assert_eq!(Config::default().baseline(), None);
+
+        let mut appended = Config::default();
+        appended.append(&config, &mut Vec::new());
+        assert_eq!(appended.baseline(), config.baseline());
+
+        assert_eq!(
+            Config::from_str(
+                "
+baseline = true
+[libraries]
+",
+                parent,
+            ),
+            Err("Expected string but got boolean for baseline".to_owned())
+        );
+    }
+
+    #[test]
+    fn reset_signals() {
+        let config = Config::from_str(
+            "
+reset_signals = ['rst', 'sys_*']
+[libraries]
+",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(
+            config.reset_signals(),
+            Some(["rst".to_owned(), "sys_*".to_owned()].as_slice())
+        );
+        assert_eq!(Config::default().reset_signals(), None);
+
+        assert_eq!(
+            Config::from_str(
+                "
+reset_signals = 'rst'
+[libraries]
+",
+                Path::new(""),
+            ),
+            Err("Expected array of strings but got string for reset_signals".to_owned())
+        );
+        assert!(Config::from_str(
+            "
+reset_signals = ['[rst']
+[libraries]
+",
+            Path::new(""),
+        )
+        .unwrap_err()
+        .starts_with("Invalid pattern '[rst' for reset_signals"));
+    }
+
+    #[test]
+    fn to_toml_round_trip() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+standard = '2019'
+default_library = 'scratch'
+baseline = 'baseline.json'
+reset_signals = ['rst', '*_rst_n']
+[conditional_analysis]
+TOOL_TYPE = 'SYNTHESIS'
+[libraries]
+lib.files = ['src/*.vhd']
+lib.exclude = ['src/*_tb.vhd']
+lib.synthesizable = true
+legacy.files = ['legacy.vhd']
+legacy.standard = '1993'
+legacy.is_third_party = true
+",
+            parent,
+        )
+        .unwrap();
+
+        let toml = config.to_toml(parent);
+        assert!(toml.contains("\"src/*.vhd\""), "{toml}");
+        assert_eq!(Config::from_str(&toml, parent).unwrap(), config);
+    }
 }
@@ -0,0 +1,124 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Simulator style `.f` file lists
+//!
+//! Each line holds file names separated by whitespace, `//` and `#` start comments.
+//! Nested lists are given with `-f` or `-F`, other options such as `+incdir+` are ignored.
+//! Relative file names are relative to the folder of the file list.
+
+use super::{expand_path, FilePattern, Origin};
+use crate::data::*;
+use std::path::{Path, PathBuf};
+
+/// Returns true if the file name refers to a file list rather than a source file
+pub(super) fn is_file_list(file_name: &Path) -> bool {
+    file_name.extension().is_some_and(|ext| ext == "f")
+}
+
+/// Read the file names of a file list, `origin` is where the file list was referenced.
+/// The file list and the nested lists that were read are added to `read_lists`
+pub(super) fn read_file_list(
+    file_name: &Path,
+    origin: &Origin,
+    read_lists: &mut Vec<PathBuf>,
+    messages: &mut dyn MessageHandler,
+) -> Vec<FilePattern> {
+    let mut result = Vec::new();
+    let mut reading = Vec::new();
+    read_nested(
+        file_name,
+        origin,
+        &mut reading,
+        read_lists,
+        &mut result,
+        messages,
+    );
+    result
+}
+
+fn read_nested(
+    file_name: &Path,
+    origin: &Origin,
+    reading: &mut Vec<PathBuf>,
+    read_lists: &mut Vec<PathBuf>,
+    result: &mut Vec<FilePattern>,
+    messages: &mut dyn MessageHandler,
+) {
+    let canonical = dunce::canonicalize(file_name).unwrap_or_else(|_| file_name.to_owned());
+    if reading.contains(&canonical) {
+        messages.push(origin.locate(Message::error(format!(
+            "File list {} includes itself",
+            file_name.to_string_lossy()
+        ))));
+        return;
+    }
+
+    let contents = match std::fs::read_to_string(file_name) {
+        Ok(contents) => contents,
+        Err(err) => {
+            messages.push(origin.locate(Message::error(format!(
+                "Could not read file list {}: {err}",
+                file_name.to_string_lossy()
+            ))));
+            return;
+        }
+    };
+    let parent = file_name.parent().unwrap_or(Path::new(""));
+
+    read_lists.push(file_name.to_owned());
+    reading.push(canonical);
+    for (idx, line) in contents.lines().enumerate() {
+        let line_origin = Origin::new(Some(file_name), Some(idx + 1));
+        let mut words = strip_comment(line).split_whitespace();
+
+        while let Some(word) = words.next() {
+            let (word, is_nested) = if word == "-f" || word == "-F" {
+                match words.next() {
+                    Some(nested) => (nested, true),
+                    None => {
+                        messages.push(
+                            line_origin
+                                .locate(Message::error(format!("Missing file name after {word}"))),
+                        );
+                        break;
+                    }
+                }
+            } else if word.starts_with(['-', '+']) {
+                continue;
+            } else {
+                (word, false)
+            };
+
+            let path = match expand_path(word) {
+                Ok(path) => parent.join(path),
+                Err(err) => {
+                    messages.push(line_origin.locate(Message::error(err)));
+                    continue;
+                }
+            };
+
+            if is_nested {
+                read_nested(&path, &line_origin, reading, read_lists, result, messages);
+            } else {
+                result.push(FilePattern {
+                    pattern: path.to_string_lossy().into_owned(),
+                    origin: line_origin.clone(),
+                });
+            }
+        }
+    }
+    reading.pop();
+}
+
+fn strip_comment(line: &str) -> &str {
+    let end = [line.find("//"), line.find('#')]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(line.len());
+    &line[..end]
+}
@@ -0,0 +1,441 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Import of libraries from FuseSoC core files (CAPI2)
+//!
+//! The filesets of the `default` target are used, or all filesets if the core has no such target.
+//! Filesets and dependencies that depend on use flags, i.e `flag ? (name)`, are ignored
+//! since the flags are only known by the tool flow.
+
+use super::import::{library_name, ImportedFile};
+use super::{Config, Origin};
+use crate::data::*;
+use crate::standard::VHDLStandard;
+use fnv::FnvHashMap;
+use serde_yaml::{Mapping, Value};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// Vendor, library and name of a core, the version is not used to identify a core
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+struct CoreName {
+    vendor: String,
+    library: String,
+    name: String,
+}
+
+impl CoreName {
+    /// Parse a name such as `vendor:library:name:1.0`, a dependency may be
+    /// prefixed by a version constraint such as `>=` that is ignored
+    fn parse(vlnv: &str) -> CoreName {
+        let vlnv = vlnv.trim_start_matches(['>', '<', '=', '^', '~', '!']);
+        let mut parts = vlnv.split(':');
+        let mut next = || parts.next().unwrap_or_default().to_owned();
+        let first = next();
+        let second = next();
+        let third = next();
+
+        if second.is_empty() && third.is_empty() {
+            // Only the name was given
+            CoreName {
+                vendor: String::new(),
+                library: String::new(),
+                name: first,
+            }
+        } else {
+            CoreName {
+                vendor: first,
+                library: second,
+                name: third,
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CoreName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.vendor, self.library, self.name)
+    }
+}
+
+struct Core {
+    file_name: PathBuf,
+    name: CoreName,
+    files: Vec<ImportedFile>,
+    depend: Vec<CoreName>,
+}
+
+impl Core {
+    fn read(file_name: &Path, messages: &mut dyn MessageHandler) -> Option<Core> {
+        let contents = match std::fs::read_to_string(file_name) {
+            Ok(contents) => contents,
+            Err(err) => {
+                messages.push(Message::file_error(
+                    format!("Could not read core file: {err}"),
+                    file_name,
+                ));
+                return None;
+            }
+        };
+
+        match Core::parse(&contents, file_name) {
+            Ok(core) => Some(core),
+            Err((err, line)) => {
+                messages.push(Message::error(err).in_file(file_name, line));
+                None
+            }
+        }
+    }
+
+    fn parse(contents: &str, file_name: &Path) -> Result<Core, (String, Option<usize>)> {
+        let core: Value = serde_yaml::from_str(contents).map_err(|err| {
+            let line = err.location().map(|location| location.line());
+            (format!("Invalid core file: {err}"), line)
+        })?;
+        let no_location = |err: String| (err, None);
+
+        let name = core
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| no_location("Core has no name".to_owned()))?;
+        let parent = file_name.parent().unwrap_or(Path::new(""));
+
+        let filesets = match core.get("filesets") {
+            Some(filesets) => filesets
+                .as_mapping()
+                .ok_or_else(|| no_location("filesets must be a mapping".to_owned()))?
+                .clone(),
+            None => Mapping::new(),
+        };
+
+        let used_filesets: Vec<String> = match core
+            .get("targets")
+            .and_then(|targets| targets.get("default"))
+        {
+            Some(target) => string_list(target.get("filesets"))
+                .map_err(|err| no_location(format!("{err} for filesets of target default")))?
+                .into_iter()
+                .filter(|name| !name.contains('?'))
+                .collect(),
+            None => filesets
+                .keys()
+                .filter_map(Value::as_str)
+                .map(str::to_owned)
+                .collect(),
+        };
+
+        let mut files = Vec::new();
+        let mut depend = Vec::new();
+        for fileset_name in used_filesets {
+            let Some(fileset) = filesets.get(fileset_name.as_str()) else {
+                return Err(no_location(format!("No such fileset '{fileset_name}'")));
+            };
+
+            let file_type = fileset.get("file_type").and_then(Value::as_str);
+            let logical_name = fileset.get("logical_name").and_then(Value::as_str);
+
+            for file in fileset
+                .get("files")
+                .and_then(Value::as_sequence)
+                .into_iter()
+                .flatten()
+            {
+                let (path, attributes) = match file {
+                    Value::String(path) => (path.as_str(), None),
+                    Value::Mapping(mapping) if mapping.len() == 1 => {
+                        let (path, attributes) = mapping.iter().next().unwrap();
+                        let path = path.as_str().ok_or_else(|| {
+                            no_location(format!("Invalid file in fileset '{fileset_name}'"))
+                        })?;
+                        (path, Some(attributes))
+                    }
+                    _ => {
+                        return Err(no_location(format!(
+                            "Invalid file in fileset '{fileset_name}'"
+                        )))
+                    }
+                };
+                let attribute = |name: &str| {
+                    attributes
+                        .and_then(|attributes| attributes.get(name))
+                        .and_then(Value::as_str)
+                };
+
+                let Some(file_type) = attribute("file_type").or(file_type) else {
+                    continue;
+                };
+                let Some(standard) = vhdl_standard_of(file_type) else {
+                    continue;
+                };
+                files.push(ImportedFile {
+                    file_name: parent.join(path),
+                    library: library_name(attribute("logical_name").or(logical_name)),
+                    standard,
+                    origin: Origin::new(Some(file_name), None),
+                });
+            }
+
+            depend.extend(
+                string_list(fileset.get("depend"))
+                    .map_err(|err| no_location(format!("{err} for depend of '{fileset_name}'")))?
+                    .into_iter()
+                    .filter(|name| !name.contains('?'))
+                    .map(|name| CoreName::parse(&name)),
+            );
+        }
+
+        Ok(Core {
+            file_name: file_name.to_owned(),
+            name: CoreName::parse(name),
+            files,
+            depend,
+        })
+    }
+}
+
+/// The revision of a VHDL file type, `Some(None)` if the type does not give the revision
+/// and `None` if it is not a VHDL file
+fn vhdl_standard_of(file_type: &str) -> Option<Option<VHDLStandard>> {
+    match file_type {
+        "vhdlSource" => Some(None),
+        "vhdlSource-87" | "vhdlSource-93" => Some(Some(VHDLStandard::VHDL1993)),
+        "vhdlSource-2008" => Some(Some(VHDLStandard::VHDL2008)),
+        "vhdlSource-2019" => Some(Some(VHDLStandard::VHDL2019)),
+        _ => None,
+    }
+}
+
+fn string_list(value: Option<&Value>) -> Result<Vec<String>, String> {
+    let Some(value) = value else {
+        return Ok(Vec::new());
+    };
+    value
+        .as_sequence()
+        .ok_or_else(|| "Expected a list".to_owned())?
+        .iter()
+        .map(|item| {
+            item.as_str()
+                .map(str::to_owned)
+                .ok_or_else(|| "Expected a list of strings".to_owned())
+        })
+        .collect()
+}
+
+/// Find the core files within the core library folders
+fn find_cores(library_dirs: &[PathBuf], messages: &mut dyn MessageHandler) -> Vec<Core> {
+    let mut cores = Vec::new();
+    for dir in library_dirs {
+        let dir = dunce::canonicalize(dir).unwrap_or_else(|_| dir.clone());
+        let pattern = dir.join("**").join("*.core");
+        let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+            messages.push(Message::error(format!(
+                "Invalid core library folder {}",
+                dir.to_string_lossy()
+            )));
+            continue;
+        };
+        cores.extend(
+            paths
+                .flatten()
+                .filter_map(|file_name| Core::read(&file_name, messages)),
+        );
+    }
+    cores
+}
+
+impl Config {
+    /// Create a configuration with the VHDL files of FuseSoC cores and the cores they depend on.
+    /// Dependencies are searched for within the core library folders
+    pub fn from_fusesoc_cores(
+        core_files: &[PathBuf],
+        library_dirs: &[PathBuf],
+        messages: &mut dyn MessageHandler,
+    ) -> Config {
+        let mut available: FnvHashMap<CoreName, Core> = FnvHashMap::default();
+        for core in find_cores(library_dirs, messages) {
+            available.entry(core.name.clone()).or_insert(core);
+        }
+
+        let mut pending = VecDeque::new();
+        for file_name in core_files {
+            let file_name = dunce::canonicalize(file_name).unwrap_or_else(|_| file_name.clone());
+            if let Some(core) = Core::read(&file_name, messages) {
+                pending.push_back(core);
+            }
+        }
+
+        let mut used: Vec<Core> = Vec::new();
+        while let Some(core) = pending.pop_front() {
+            if used.iter().any(|other| other.name == core.name) {
+                continue;
+            }
+
+            for name in core.depend.iter() {
+                let is_known = used.iter().chain(pending.iter()).any(|c| &c.name == name);
+                if is_known {
+                    continue;
+                }
+                match available.remove(name) {
+                    Some(dependency) => pending.push_back(dependency),
+                    None => messages.push(Message::file_error(
+                        format!("Could not find core {name} that {} depends on", core.name),
+                        &core.file_name,
+                    )),
+                }
+            }
+            used.push(core);
+        }
+
+        let files = used.into_iter().flat_map(|core| core.files).collect();
+        Config::from_imported_files(files, messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::import::DEFAULT_LIBRARY;
+
+    #[test]
+    fn parse_core_names() {
+        assert_eq!(
+            CoreName::parse(">=acme:ip:uart:1.2.0"),
+            CoreName {
+                vendor: "acme".to_owned(),
+                library: "ip".to_owned(),
+                name: "uart".to_owned(),
+            }
+        );
+        assert_eq!(CoreName::parse("::fifo:0").to_string(), "::fifo");
+        assert_eq!(CoreName::parse("fifo").to_string(), "::fifo");
+    }
+
+    #[test]
+    fn files_of_default_target() {
+        let core = Core::parse(
+            "
+CAPI=2:
+name: acme:ip:uart:1.0
+filesets:
+  rtl:
+    file_type: vhdlSource-2008
+    logical_name: uart_lib
+    files:
+      - rtl/uart.vhd
+      - rtl/legacy.vhd: {file_type: vhdlSource-93}
+      - rtl/tx.v: {file_type: verilogSource}
+      - rtl/pkg.vhd: {logical_name: work}
+    depend:
+      - '>=acme:ip:fifo:1.0'
+      - 'sim ? (acme:ip:sim_utils)'
+  tb:
+    file_type: vhdlSource
+    files: [tb/tb_uart.vhd]
+targets:
+  default:
+    filesets: [rtl, 'sim ? (tb)']
+",
+            Path::new("ip/uart.core"),
+        )
+        .map_err(|(err, _)| err)
+        .unwrap();
+
+        assert_eq!(core.name.to_string(), "acme:ip:uart");
+        let files: Vec<_> = core
+            .files
+            .iter()
+            .map(|file| (file.file_name.clone(), file.library.as_str(), file.standard))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                (
+                    PathBuf::from("ip/rtl/uart.vhd"),
+                    "uart_lib",
+                    Some(VHDLStandard::VHDL2008)
+                ),
+                (
+                    PathBuf::from("ip/rtl/legacy.vhd"),
+                    "uart_lib",
+                    Some(VHDLStandard::VHDL1993)
+                ),
+                (
+                    PathBuf::from("ip/rtl/pkg.vhd"),
+                    DEFAULT_LIBRARY,
+                    Some(VHDLStandard::VHDL2008)
+                ),
+            ]
+        );
+        assert_eq!(core.depend, vec![CoreName::parse("acme:ip:fifo")]);
+    }
+
+    #[test]
+    fn syntax_errors_refer_to_the_line() {
+        let err = Core::parse("name: [a\nfilesets: {", Path::new("bad.core"))
+            .map(|_| ())
+            .unwrap_err();
+        assert!(err.1.is_some());
+    }
+
+    #[test]
+    fn resolve_dependencies_from_core_libraries() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(tempdir.path()).unwrap();
+        let library_dir = root.join("cores");
+        std::fs::create_dir_all(library_dir.join("fifo")).unwrap();
+        std::fs::write(
+            library_dir.join("fifo").join("fifo.core"),
+            "
+CAPI=2:
+name: acme:ip:fifo:1.0
+filesets:
+  rtl:
+    file_type: vhdlSource-2008
+    logical_name: fifo_lib
+    files: [fifo.vhd]
+",
+        )
+        .unwrap();
+        let top = root.join("top.core");
+        std::fs::write(
+            &top,
+            "
+CAPI=2:
+name: acme:ip:top:1.0
+filesets:
+  rtl:
+    file_type: vhdlSource-93
+    logical_name: top_lib
+    files: [top.vhd]
+    depend: ['acme:ip:fifo', 'acme:ip:missing']
+",
+        )
+        .unwrap();
+
+        let mut messages = Vec::new();
+        let config = Config::from_fusesoc_cores(
+            std::slice::from_ref(&top),
+            std::slice::from_ref(&library_dir),
+            &mut messages,
+        );
+
+        assert_eq!(
+            messages,
+            vec![Message::file_error(
+                "Could not find core acme:ip:missing that acme:ip:top depends on",
+                &top
+            )]
+        );
+
+        let top_lib = config.get_library("top_lib").unwrap();
+        assert!(top_lib.matches(&root.join("top.vhd")));
+        assert_eq!(top_lib.standard(), VHDLStandard::VHDL1993);
+
+        let fifo_lib = config.get_library("fifo_lib").unwrap();
+        assert!(fifo_lib.matches(&library_dir.join("fifo").join("fifo.vhd")));
+        assert_eq!(fifo_lib.standard(), VHDLStandard::VHDL2008);
+    }
+}
@@ -0,0 +1,222 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Utilities shared by the importers of the project files of other tools
+
+use super::{normalize_path, Config, FilePattern, LibraryConfig, Origin};
+use crate::data::*;
+use crate::standard::VHDLStandard;
+use std::path::{Path, PathBuf};
+
+/// The library of VHDL files compiled into `work`, which is not a valid library name
+pub(super) const DEFAULT_LIBRARY: &str = "defaultlib";
+
+/// A VHDL file listed in the project file of another tool
+#[derive(Debug)]
+pub(super) struct ImportedFile {
+    pub file_name: PathBuf,
+    pub library: String,
+    pub standard: Option<VHDLStandard>,
+    pub origin: Origin,
+}
+
+/// The library name to use for a library of another tool, `None` if the tool uses its default library
+pub(super) fn library_name(name: Option<&str>) -> String {
+    match name {
+        Some(name) if !name.eq_ignore_ascii_case("work") => name.to_owned(),
+        _ => DEFAULT_LIBRARY.to_owned(),
+    }
+}
+
+/// Returns true if the file extension is that of a VHDL file
+pub(super) fn is_vhdl_file(file_name: &Path) -> bool {
+    file_name
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("vhd") || ext.eq_ignore_ascii_case("vhdl"))
+}
+
+impl Config {
+    /// Create a configuration where each library contains the files imported into it.
+    /// The revision of a library is only set when all of its files agree
+    pub(super) fn from_imported_files(
+        files: Vec<ImportedFile>,
+        messages: &mut dyn MessageHandler,
+    ) -> Config {
+        let mut libraries: Vec<(LibraryConfig, Vec<VHDLStandard>)> = Vec::new();
+        for file in files {
+            let idx = match libraries
+                .iter()
+                .position(|(library, _)| library.name == file.library)
+            {
+                Some(idx) => idx,
+                None => {
+                    let library = LibraryConfig {
+                        name: file.library.clone(),
+                        ..LibraryConfig::default()
+                    };
+                    libraries.push((library, Vec::new()));
+                    libraries.len() - 1
+                }
+            };
+
+            // Project files are relative to the working folder when imported,
+            // but not necessarily when the configuration is used
+            let file_name = match std::env::current_dir() {
+                Ok(dir) => normalize_path(&dir.join(&file.file_name)),
+                Err(_) => file.file_name,
+            };
+
+            let (library, standards) = &mut libraries[idx];
+            library.patterns.push(FilePattern {
+                pattern: file_name.to_string_lossy().into_owned(),
+                origin: file.origin,
+            });
+            if let Some(standard) = file.standard {
+                if !standards.contains(&standard) {
+                    standards.push(standard);
+                }
+            }
+        }
+
+        let mut config = Config::default();
+        for (mut library, standards) in libraries {
+            match standards.as_slice() {
+                [] => {}
+                [standard] => library.standard = Some(*standard),
+                _ => messages.push(Message::warning(format!(
+                    "Library {} mixes files of different VHDL revisions, using the default revision",
+                    library.name
+                ))),
+            }
+            config.libraries.insert(library.name.clone(), library);
+        }
+        config
+    }
+}
+
+/// Split a Tcl script into commands, each with the line it starts on and its words.
+/// Quotes and braces group words, a backslash at the end of a line continues the command.
+/// Substitutions are not performed
+pub(super) fn tcl_commands(script: &str) -> Vec<(usize, Vec<String>)> {
+    let mut commands = Vec::new();
+    let mut words = Vec::new();
+    let mut start_line = 1;
+
+    for (idx, line) in script.lines().enumerate() {
+        if words.is_empty() {
+            start_line = idx + 1;
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+        }
+
+        let (line, continued) = match line.trim_end().strip_suffix('\\') {
+            Some(line) => (line, true),
+            None => (line, false),
+        };
+        let line_commands: Vec<&str> = line.split(';').collect();
+        for (cmd_idx, command) in line_commands.iter().enumerate() {
+            words.extend(tcl_words(command));
+            let is_last = cmd_idx + 1 == line_commands.len();
+            if (!is_last || !continued) && !words.is_empty() {
+                commands.push((start_line, std::mem::take(&mut words)));
+            }
+        }
+    }
+    if !words.is_empty() {
+        commands.push((start_line, words));
+    }
+    commands
+}
+
+fn tcl_words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut chars = command.chars().peekable();
+
+    while let Some(&chr) = chars.peek() {
+        if chr.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut word = String::new();
+        match chr {
+            '"' => {
+                chars.next();
+                for chr in chars.by_ref() {
+                    if chr == '"' {
+                        break;
+                    }
+                    word.push(chr);
+                }
+            }
+            '{' => {
+                chars.next();
+                let mut depth = 1;
+                for chr in chars.by_ref() {
+                    match chr {
+                        '{' => depth += 1,
+                        '}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    word.push(chr);
+                }
+            }
+            _ => {
+                while let Some(&chr) = chars.peek() {
+                    if chr.is_whitespace() {
+                        break;
+                    }
+                    word.push(chr);
+                    chars.next();
+                }
+            }
+        }
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn split_tcl_commands() {
+        assert_eq!(
+            tcl_commands(
+                "
+# A comment
+vlib lib; vmap lib ./lib
+vcom -work lib \\
+  \"a b.vhd\" {c d.vhd}
+"
+            ),
+            vec![
+                (3, words(&["vlib", "lib"])),
+                (3, words(&["vmap", "lib", "./lib"])),
+                (4, words(&["vcom", "-work", "lib", "a b.vhd", "c d.vhd"])),
+            ]
+        );
+    }
+
+    #[test]
+    fn work_is_mapped_to_the_default_library() {
+        assert_eq!(library_name(Some("WORK")), DEFAULT_LIBRARY);
+        assert_eq!(library_name(None), DEFAULT_LIBRARY);
+        assert_eq!(library_name(Some("lib")), "lib");
+    }
+}
@@ -0,0 +1,346 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Import of libraries from ModelSim and Questa compile scripts and project files
+//!
+//! In `.do` scripts the files of `vcom` commands are imported into the library given by `-work`,
+//! which may be a folder mapped to a library by `vmap`. Nested scripts are followed by `do`.
+//!
+//! Files in the ini format, i.e `modelsim.ini` or `.mpf` projects, list files in the
+//! `[Project]` section with the library given by `compile_to` in the file properties.
+
+use super::import::{library_name, tcl_commands, ImportedFile};
+use super::{normalize_path, Config, Origin};
+use crate::data::*;
+use crate::standard::VHDLStandard;
+use fnv::FnvHashMap;
+use std::path::{Path, PathBuf};
+
+impl Config {
+    /// Create a configuration with the VHDL files of a ModelSim `.do` script,
+    /// `modelsim.ini` or `.mpf` project file
+    pub fn from_modelsim(file_name: &Path, messages: &mut dyn MessageHandler) -> Config {
+        let is_script = file_name.extension().is_some_and(|ext| ext == "do");
+        let files = if is_script {
+            let mut script = Script::default();
+            script.read(file_name, &Origin::default(), messages);
+            script.files
+        } else {
+            read_ini(file_name, messages)
+        };
+        Config::from_imported_files(files, messages)
+    }
+}
+
+#[derive(Default)]
+struct Script {
+    /// Library folders mapped by `vmap`
+    mapping: FnvHashMap<PathBuf, String>,
+    /// The scripts that are currently being read
+    reading: Vec<PathBuf>,
+    files: Vec<ImportedFile>,
+}
+
+impl Script {
+    fn read(&mut self, file_name: &Path, origin: &Origin, messages: &mut dyn MessageHandler) {
+        let normalized = normalize_path(file_name);
+        if self.reading.contains(&normalized) {
+            messages.push(origin.locate(Message::error(format!(
+                "Script {} runs itself",
+                file_name.to_string_lossy()
+            ))));
+            return;
+        }
+
+        let contents = match std::fs::read_to_string(file_name) {
+            Ok(contents) => contents,
+            Err(err) => {
+                messages.push(origin.locate(Message::error(format!(
+                    "Could not read script {}: {err}",
+                    file_name.to_string_lossy()
+                ))));
+                return;
+            }
+        };
+        let parent = file_name.parent().unwrap_or(Path::new(""));
+
+        self.reading.push(normalized);
+        for (line, words) in tcl_commands(&contents) {
+            let origin = Origin::new(Some(file_name), Some(line));
+            match words[0].as_str() {
+                "vmap" => {
+                    if let [_, library, folder] = words.as_slice() {
+                        self.mapping
+                            .insert(normalize_path(&parent.join(folder)), library.clone());
+                    }
+                }
+                "vcom" => self.vcom(&words[1..], parent, origin, messages),
+                "do" => {
+                    if let Some(script) = words.get(1) {
+                        self.read(&parent.join(script), &origin, messages);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.reading.pop();
+    }
+
+    fn vcom(
+        &mut self,
+        args: &[String],
+        parent: &Path,
+        origin: Origin,
+        messages: &mut dyn MessageHandler,
+    ) {
+        let mut work = None;
+        let mut standard = None;
+        let mut file_names = Vec::new();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-work" => work = args.next(),
+                "-87" | "-93" => standard = Some(VHDLStandard::VHDL1993),
+                "-2002" => standard = Some(VHDLStandard::VHDL2002),
+                "-2008" => standard = Some(VHDLStandard::VHDL2008),
+                "-2019" => standard = Some(VHDLStandard::VHDL2019),
+                "-f" | "-modelsimini" | "-suppress" | "-note" | "-warning" | "-error"
+                | "-fatal" | "-l" => {
+                    args.next();
+                }
+                _ if arg.starts_with('-') => {}
+                _ if arg.contains('$') => {
+                    messages.push(origin.locate(Message::warning(format!(
+                        "Variables are not supported, ignoring {arg}"
+                    ))));
+                }
+                _ => file_names.push(arg),
+            }
+        }
+
+        let library = work.map(|work| {
+            let folder = normalize_path(&parent.join(work));
+            match self.mapping.get(&folder) {
+                Some(library) => library.clone(),
+                // A folder that is not mapped is named after the library
+                None => folder
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| work.clone()),
+            }
+        });
+
+        for file_name in file_names {
+            self.files.push(ImportedFile {
+                file_name: parent.join(file_name),
+                library: library_name(library.as_deref()),
+                standard,
+                origin: origin.clone(),
+            });
+        }
+    }
+}
+
+fn read_ini(file_name: &Path, messages: &mut dyn MessageHandler) -> Vec<ImportedFile> {
+    let contents = match std::fs::read_to_string(file_name) {
+        Ok(contents) => contents,
+        Err(err) => {
+            messages.push(Message::file_error(
+                format!("Could not read ModelSim project: {err}"),
+                file_name,
+            ));
+            return Vec::new();
+        }
+    };
+    let files = parse_ini(&contents, file_name);
+    if files.is_empty() {
+        messages.push(Message::file_error(
+            "No VHDL files in the [Project] section",
+            file_name,
+        ));
+    }
+    files
+}
+
+fn parse_ini(contents: &str, file_name: &Path) -> Vec<ImportedFile> {
+    let parent = file_name.parent().unwrap_or(Path::new(""));
+    let mut section = String::new();
+    let mut project_files: Vec<(usize, &str, &str)> = Vec::new();
+    let mut properties: FnvHashMap<&str, &str> = FnvHashMap::default();
+
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with(';') || line.is_empty() {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_owned();
+            continue;
+        }
+        if section != "Project" {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if let Some(number) = key.strip_prefix("Project_File_P_") {
+            properties.insert(number, value);
+        } else if let Some(number) = key.strip_prefix("Project_File_") {
+            project_files.push((idx + 1, number, value));
+        }
+    }
+
+    let mut files = Vec::new();
+    for (line, number, path) in project_files {
+        let mut library = None;
+        let mut standard = None;
+        let mut file_type = None;
+        if let Some(properties) = properties.get(number) {
+            let words = tcl_commands(properties)
+                .into_iter()
+                .flat_map(|(_, words)| words);
+            let words: Vec<String> = words.collect();
+            for pair in words.chunks_exact(2) {
+                match pair[0].as_str() {
+                    "compile_to" => library = Some(pair[1].clone()),
+                    "file_type" => file_type = Some(pair[1].clone()),
+                    "vhdl_use93" => standard = parse_use93(&pair[1]),
+                    _ => {}
+                }
+            }
+        }
+
+        if file_type.is_some_and(|file_type| file_type != "vhdl") {
+            continue;
+        }
+        files.push(ImportedFile {
+            file_name: parent.join(path),
+            library: library_name(library.as_deref()),
+            standard,
+            origin: Origin::new(Some(file_name), Some(line)),
+        });
+    }
+    files
+}
+
+/// The `vhdl_use93` property of a project file, `0` and `1` select VHDL-87 and VHDL-93
+fn parse_use93(value: &str) -> Option<VHDLStandard> {
+    match value {
+        "0" | "1" | "87" | "93" => Some(VHDLStandard::VHDL1993),
+        "2002" => Some(VHDLStandard::VHDL2002),
+        "2008" => Some(VHDLStandard::VHDL2008),
+        "2019" => Some(VHDLStandard::VHDL2019),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(files: &[ImportedFile]) -> Vec<(PathBuf, &str, Option<VHDLStandard>)> {
+        files
+            .iter()
+            .map(|file| (file.file_name.clone(), file.library.as_str(), file.standard))
+            .collect()
+    }
+
+    #[test]
+    fn files_of_do_scripts() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        std::fs::write(
+            root.join("compile.do"),
+            "
+vlib libs/common
+vmap common_lib libs/common
+vcom -2008 -work libs/common src/pkg.vhd
+vcom -93 -quiet src/top.vhd \\
+  src/$name.vhd
+do tb.do
+",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("tb.do"),
+            "vcom -work tb_lib tb/tb.vhd\ndo compile.do\n",
+        )
+        .unwrap();
+
+        let mut messages = Vec::new();
+        let mut script = Script::default();
+        script.read(&root.join("compile.do"), &Origin::default(), &mut messages);
+
+        assert_eq!(
+            summary(&script.files),
+            vec![
+                (
+                    root.join("src/pkg.vhd"),
+                    "common_lib",
+                    Some(VHDLStandard::VHDL2008)
+                ),
+                (
+                    root.join("src/top.vhd"),
+                    "defaultlib",
+                    Some(VHDLStandard::VHDL1993)
+                ),
+                (root.join("tb/tb.vhd"), "tb_lib", None),
+            ]
+        );
+        assert_eq!(
+            messages,
+            vec![
+                Message::warning("Variables are not supported, ignoring src/$name.vhd")
+                    .in_file(&root.join("compile.do"), Some(5)),
+                Message::error(format!(
+                    "Script {} runs itself",
+                    root.join("compile.do").to_string_lossy()
+                ))
+                .in_file(&root.join("tb.do"), Some(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn files_of_project_file() {
+        let file_name = Path::new("sim").join("proj.mpf");
+        let files = parse_ini(
+            "
+[Library]
+common_lib = ./common_lib
+; Project files
+[Project]
+Project_Files_Count = 3
+Project_File_0 = ../src/pkg.vhd
+Project_File_P_0 = vhdl_novitalcheck 0 file_type vhdl folder {Top Level} compile_to common_lib vhdl_use93 2008
+Project_File_1 = ../src/core.v
+Project_File_P_1 = file_type verilog compile_to work
+Project_File_2 = ../src/top.vhd
+Project_File_P_2 = file_type vhdl compile_to work vhdl_use93 2002
+",
+            &file_name,
+        );
+
+        assert_eq!(
+            summary(&files),
+            vec![
+                (
+                    file_name.parent().unwrap().join("../src/pkg.vhd"),
+                    "common_lib",
+                    Some(VHDLStandard::VHDL2008)
+                ),
+                (
+                    file_name.parent().unwrap().join("../src/top.vhd"),
+                    "defaultlib",
+                    Some(VHDLStandard::VHDL2002)
+                ),
+            ]
+        );
+        assert_eq!(files[0].origin.line, Some(7));
+    }
+}
@@ -0,0 +1,183 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Import of libraries from Intel Quartus `.qsf` settings files
+//!
+//! VHDL files are given by `set_global_assignment -name VHDL_FILE <file> [-library <name>]`
+//! and the revision of all files by the `VHDL_INPUT_VERSION` assignment,
+//! or per file by `-hdl_version`.
+
+use super::import::{library_name, tcl_commands, ImportedFile};
+use super::{Config, Origin};
+use crate::data::*;
+use crate::standard::VHDLStandard;
+use std::path::Path;
+
+impl Config {
+    /// Create a configuration with the VHDL files of a Quartus settings file
+    pub fn from_quartus_settings(file_name: &Path, messages: &mut dyn MessageHandler) -> Config {
+        match std::fs::read_to_string(file_name) {
+            Ok(contents) => {
+                let files = parse_settings(&contents, file_name, messages);
+                Config::from_imported_files(files, messages)
+            }
+            Err(err) => {
+                messages.push(Message::file_error(
+                    format!("Could not read Quartus settings: {err}"),
+                    file_name,
+                ));
+                Config::default()
+            }
+        }
+    }
+}
+
+fn parse_settings(
+    contents: &str,
+    file_name: &Path,
+    messages: &mut dyn MessageHandler,
+) -> Vec<ImportedFile> {
+    let parent = file_name.parent().unwrap_or(Path::new(""));
+    let mut files = Vec::new();
+    let mut input_version = None;
+
+    for (line, words) in tcl_commands(contents) {
+        if words[0] != "set_global_assignment" {
+            continue;
+        }
+
+        let mut name = None;
+        let mut library = None;
+        let mut hdl_version = None;
+        let mut values = Vec::new();
+        let mut args = words[1..].iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-name" => name = args.next(),
+                "-library" => library = args.next(),
+                "-hdl_version" => hdl_version = args.next(),
+                "-section_id" | "-entity" | "-tag" => {
+                    args.next();
+                }
+                _ if arg.starts_with('-') => {}
+                _ => values.push(arg),
+            }
+        }
+
+        let origin = Origin::new(Some(file_name), Some(line));
+        let standard_of = |version: &str, messages: &mut dyn MessageHandler| {
+            let standard = parse_hdl_version(version);
+            if standard.is_none() {
+                messages.push(
+                    origin.locate(Message::warning(format!("Unknown VHDL version {version}"))),
+                );
+            }
+            standard
+        };
+
+        match (
+            name.map(|name| name.to_uppercase()).as_deref(),
+            values.first(),
+        ) {
+            (Some("VHDL_INPUT_VERSION"), Some(version)) => {
+                input_version = standard_of(version, messages);
+            }
+            (Some("VHDL_FILE"), Some(file)) => {
+                let standard = hdl_version.and_then(|version| standard_of(version, messages));
+                files.push(ImportedFile {
+                    file_name: parent.join(file),
+                    library: library_name(library.map(String::as_str)),
+                    standard,
+                    origin,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    // The input version applies to every file regardless of where it is assigned
+    let default_standard = input_version.unwrap_or(VHDLStandard::VHDL1993);
+    for file in files.iter_mut() {
+        file.standard.get_or_insert(default_standard);
+    }
+    files
+}
+
+/// Parse versions such as `VHDL_2008`
+fn parse_hdl_version(version: &str) -> Option<VHDLStandard> {
+    let year = version.to_uppercase();
+    let year = year.strip_prefix("VHDL_").unwrap_or(&year);
+    year.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_of_quartus_settings() {
+        let mut messages = Vec::new();
+        let file_name = Path::new("proj").join("top.qsf");
+        let files = parse_settings(
+            "
+set_global_assignment -name FAMILY \"Cyclone V\"
+set_global_assignment -name VHDL_FILE ../src/top.vhd
+set_global_assignment -name VHDL_FILE \"../src/my pkg.vhd\" -library pkg_lib
+set_global_assignment -library legacy -name VHDL_FILE ../src/old.vhd -hdl_version VHDL_1993
+set_global_assignment -name VERILOG_FILE ../src/core.v
+set_global_assignment -name VHDL_FILE ../src/new.vhd -hdl_version VHDL_2042
+set_global_assignment -name VHDL_INPUT_VERSION VHDL_2008
+",
+            &file_name,
+            &mut messages,
+        );
+
+        let files: Vec<_> = files
+            .iter()
+            .map(|file| {
+                (
+                    file.file_name.clone(),
+                    file.library.as_str(),
+                    file.standard,
+                    file.origin.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                (
+                    Path::new("proj").join("../src/top.vhd"),
+                    "defaultlib",
+                    Some(VHDLStandard::VHDL2008),
+                    Some(3)
+                ),
+                (
+                    Path::new("proj").join("../src/my pkg.vhd"),
+                    "pkg_lib",
+                    Some(VHDLStandard::VHDL2008),
+                    Some(4)
+                ),
+                (
+                    Path::new("proj").join("../src/old.vhd"),
+                    "legacy",
+                    Some(VHDLStandard::VHDL1993),
+                    Some(5)
+                ),
+                (
+                    Path::new("proj").join("../src/new.vhd"),
+                    "defaultlib",
+                    Some(VHDLStandard::VHDL2008),
+                    Some(7)
+                ),
+            ]
+        );
+        assert_eq!(
+            messages,
+            vec![Message::warning("Unknown VHDL version VHDL_2042").in_file(&file_name, Some(7))]
+        );
+    }
+}
@@ -0,0 +1,196 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Import of libraries from Xilinx Vivado `.xpr` project files
+//!
+//! The VHDL files of all file sets are imported. The library of a file is given by its
+//! `Library` attribute and the revision by the `SFType` of its file info.
+
+use super::import::{is_vhdl_file, ImportedFile};
+use super::{Config, Origin};
+use crate::data::*;
+use crate::standard::VHDLStandard;
+use std::path::{Path, PathBuf};
+
+/// The library that Vivado compiles files without a `Library` attribute into
+const VIVADO_DEFAULT_LIBRARY: &str = "xil_defaultlib";
+
+impl Config {
+    /// Create a configuration with the VHDL files of a Vivado project
+    pub fn from_vivado_project(file_name: &Path, messages: &mut dyn MessageHandler) -> Config {
+        let contents = match std::fs::read_to_string(file_name) {
+            Ok(contents) => contents,
+            Err(err) => {
+                messages.push(Message::file_error(
+                    format!("Could not read Vivado project: {err}"),
+                    file_name,
+                ));
+                return Config::default();
+            }
+        };
+
+        match parse_project(&contents, file_name) {
+            Ok(files) => Config::from_imported_files(files, messages),
+            Err((err, line)) => {
+                messages.push(Message::error(err).in_file(file_name, line));
+                Config::default()
+            }
+        }
+    }
+}
+
+fn parse_project(
+    contents: &str,
+    file_name: &Path,
+) -> Result<Vec<ImportedFile>, (String, Option<usize>)> {
+    let document = roxmltree::Document::parse(contents).map_err(|err| {
+        let line = err.pos().row as usize;
+        (format!("Invalid Vivado project: {err}"), Some(line))
+    })?;
+
+    let project_dir = file_name.parent().unwrap_or(Path::new(""));
+    let project_name = file_name
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut files = Vec::new();
+    for file in document
+        .descendants()
+        .filter(|node| node.has_tag_name("FileSet"))
+        .flat_map(|file_set| file_set.children())
+        .filter(|node| node.has_tag_name("File"))
+    {
+        let Some(path) = file.attribute("Path") else {
+            continue;
+        };
+        let path = expand_project_variables(path, project_dir, &project_name);
+        if !is_vhdl_file(&path) {
+            continue;
+        }
+
+        let file_info = file.children().find(|node| node.has_tag_name("FileInfo"));
+        let library = file_info
+            .iter()
+            .flat_map(|info| info.children())
+            .find(|attr| attr.has_tag_name("Attr") && attr.attribute("Name") == Some("Library"))
+            .and_then(|attr| attr.attribute("Val"))
+            .unwrap_or(VIVADO_DEFAULT_LIBRARY);
+        let standard = match file_info.and_then(|info| info.attribute("SFType")) {
+            Some("VHDL2008") => Some(VHDLStandard::VHDL2008),
+            Some("VHDL2019") => Some(VHDLStandard::VHDL2019),
+            _ => Some(VHDLStandard::VHDL1993),
+        };
+        let line = document.text_pos_at(file.range().start).row as usize;
+
+        files.push(ImportedFile {
+            file_name: path,
+            library: library.to_owned(),
+            standard,
+            origin: Origin::new(Some(file_name), Some(line)),
+        });
+    }
+    Ok(files)
+}
+
+/// Replace the variables that Vivado uses for folders of the project
+fn expand_project_variables(path: &str, project_dir: &Path, project_name: &str) -> PathBuf {
+    let relative_to = |dir: PathBuf, rest: &str| dir.join(rest.trim_start_matches(['/', '\\']));
+
+    if let Some(rest) = path.strip_prefix("$PPRDIR") {
+        relative_to(project_dir.to_owned(), rest)
+    } else if let Some(rest) = path.strip_prefix("$PSRCDIR") {
+        relative_to(project_dir.join(format!("{project_name}.srcs")), rest)
+    } else {
+        project_dir.join(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_of_vivado_project() {
+        let project = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Project Version="7" Minor="61" Path="/home/user/proj/proj.xpr">
+  <FileSets Version="1" Minor="31">
+    <FileSet Name="sources_1" Type="DesignSrcs" RelSrcDir="$PSRCDIR/sources_1">
+      <Filter Type="Srcs"/>
+      <File Path="$PPRDIR/../src/top.vhd">
+        <FileInfo SFType="VHDL2008">
+          <Attr Name="Library" Val="top_lib"/>
+          <Attr Name="UsedIn" Val="synthesis"/>
+        </FileInfo>
+      </File>
+      <File Path="$PSRCDIR/sources_1/new/pkg.vhd">
+        <FileInfo>
+          <Attr Name="UsedIn" Val="synthesis"/>
+        </FileInfo>
+      </File>
+      <File Path="$PPRDIR/../src/core.v">
+        <FileInfo>
+          <Attr Name="Library" Val="top_lib"/>
+        </FileInfo>
+      </File>
+    </FileSet>
+  </FileSets>
+</Project>
+"#;
+        let file_name = Path::new("proj").join("proj.xpr");
+        let files = parse_project(project, &file_name).unwrap();
+
+        let files: Vec<_> = files
+            .iter()
+            .map(|file| {
+                (
+                    file.file_name.clone(),
+                    file.library.as_str(),
+                    file.standard,
+                    file.origin.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                (
+                    Path::new("proj").join("../src/top.vhd"),
+                    "top_lib",
+                    Some(VHDLStandard::VHDL2008),
+                    Some(6)
+                ),
+                (
+                    Path::new("proj")
+                        .join("proj.srcs")
+                        .join("sources_1/new/pkg.vhd"),
+                    VIVADO_DEFAULT_LIBRARY,
+                    Some(VHDLStandard::VHDL1993),
+                    Some(12)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_project_refers_to_the_line() {
+        let mut messages = Vec::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let file_name = tempdir.path().join("proj.xpr");
+        std::fs::write(&file_name, "<Project>\n<FileSets>\n</Project>").unwrap();
+
+        let config = Config::from_vivado_project(&file_name, &mut messages);
+        assert_eq!(config, Config::default());
+        assert_eq!(messages.len(), 1);
+        assert!(
+            messages[0]
+                .message
+                .ends_with(&format!("(In file {}:3)", file_name.to_string_lossy())),
+            "{}",
+            messages[0].message
+        );
+    }
+}
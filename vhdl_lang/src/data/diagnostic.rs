@@ -7,7 +7,7 @@
 use super::SrcPos;
 use std::convert::{AsRef, Into};
 
-#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash)]
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, PartialOrd, Ord)]
 pub enum Severity {
     Hint,
     Info,
@@ -15,13 +15,89 @@ pub enum Severity {
     Error,
 }
 
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+            Severity::Hint => "hint",
+        }
+    }
+}
+
+/// Additional metadata that an editor may use to render a diagnostic
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash)]
+pub enum DiagnosticTag {
+    /// The code is unused, unreachable or inactive and may be rendered faded out
+    Unnecessary,
+}
+
+/// The check that reported a diagnostic
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, Default)]
+pub enum DiagnosticCode {
+    Syntax,
+    #[default]
+    Analysis,
+    Unused,
+    Unreachable,
+    ConstantCondition,
+    ClockDomainCrossing,
+    Synthesis,
+}
+
+impl DiagnosticCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticCode::Syntax => "syntax",
+            DiagnosticCode::Analysis => "analysis",
+            DiagnosticCode::Unused => "unused",
+            DiagnosticCode::Unreachable => "unreachable",
+            DiagnosticCode::ConstantCondition => "constant_condition",
+            DiagnosticCode::ClockDomainCrossing => "clock_domain_crossing",
+            DiagnosticCode::Synthesis => "synthesis",
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            DiagnosticCode::Syntax => "Syntax errors",
+            DiagnosticCode::Analysis => "Semantic errors found by analysis",
+            DiagnosticCode::Unused => "Unused declarations",
+            DiagnosticCode::Unreachable => "Unreachable code and inactive generate branches",
+            DiagnosticCode::ConstantCondition => "Conditions that are always true or false",
+            DiagnosticCode::ClockDomainCrossing => {
+                "Signals read in another clock domain without synchronization"
+            }
+            DiagnosticCode::Synthesis => "Constructs that are not synthesizable",
+        }
+    }
+
+    pub fn all() -> [DiagnosticCode; 7] {
+        [
+            DiagnosticCode::Syntax,
+            DiagnosticCode::Analysis,
+            DiagnosticCode::Unused,
+            DiagnosticCode::Unreachable,
+            DiagnosticCode::ConstantCondition,
+            DiagnosticCode::ClockDomainCrossing,
+            DiagnosticCode::Synthesis,
+        ]
+    }
+}
+
 #[must_use]
 #[derive(PartialEq, Debug, Clone, Eq, Hash)]
 pub struct Diagnostic {
     pub pos: SrcPos,
     pub message: String,
     pub severity: Severity,
+    pub code: DiagnosticCode,
     pub related: Vec<(SrcPos, String)>,
+    pub tags: Vec<DiagnosticTag>,
+    /// Replacements of the text at a position that an editor may offer as quick fixes.
+    /// Boxed since diagnostics are returned as errors and should stay small
+    pub fixes: Box<[(SrcPos, String)]>,
 }
 
 impl Diagnostic {
@@ -30,7 +106,10 @@ impl Diagnostic {
             pos: item.as_ref().clone(),
             message: msg.into(),
             severity,
+            code: DiagnosticCode::default(),
             related: vec![],
+            tags: vec![],
+            fixes: Box::default(),
         }
     }
 
@@ -55,10 +134,23 @@ impl Diagnostic {
             message: format!("{}, when {}", &self.message, message.as_ref()),
             pos: self.pos,
             severity: self.severity,
+            code: self.code,
             related: vec![],
+            tags: self.tags,
+            fixes: self.fixes,
         }
     }
 
+    pub fn with_code(mut self, code: DiagnosticCode) -> Diagnostic {
+        self.code = code;
+        self
+    }
+
+    pub fn with_tag(mut self, tag: DiagnosticTag) -> Diagnostic {
+        self.tags.push(tag);
+        self
+    }
+
     pub fn related(self, item: impl AsRef<SrcPos>, message: impl Into<String>) -> Diagnostic {
         let mut diagnostic = self;
         diagnostic.add_related(item, message);
@@ -82,6 +174,12 @@ impl Diagnostic {
             .push((item.as_ref().to_owned(), message.into()));
     }
 
+    pub fn add_fix(&mut self, item: impl AsRef<SrcPos>, replacement: impl Into<String>) {
+        let mut fixes = std::mem::take(&mut self.fixes).into_vec();
+        fixes.push((item.as_ref().to_owned(), replacement.into()));
+        self.fixes = fixes.into_boxed_slice();
+    }
+
     pub fn drain_related(&mut self) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::with_capacity(self.related.len());
         let related = std::mem::take(&mut self.related);
@@ -101,13 +199,11 @@ impl Diagnostic {
             result.push_str(&pos.show(&format!("related: {message}")));
             result.push('\n');
         }
-        let severity = match self.severity {
-            Severity::Error => &"error",
-            Severity::Warning => &"warning",
-            Severity::Info => &"info",
-            Severity::Hint => &"hint",
-        };
-        result.push_str(&self.pos.show(&format!("{}: {}", severity, self.message)));
+        result.push_str(
+            &self
+                .pos
+                .show(&format!("{}: {}", self.severity.as_str(), self.message)),
+        );
         result
     }
 }
@@ -0,0 +1,350 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Diagnostics in formats read by continuous integration systems.
+//!
+//! File names below the root folder are written relative to it,
+//! lines and columns start at 1.
+
+use super::{Diagnostic, DiagnosticCode, Severity, SrcPos};
+use fnv::FnvHasher;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+impl Diagnostic {
+    /// The diagnostic as `file:line:column: severity: message [rule]` as printed by GCC,
+    /// followed by a note for each related location
+    pub fn show_gcc(&self, root: &Path) -> String {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info | Severity::Hint => "note",
+        };
+        let mut result = format!(
+            "{}: {}: {} [{}]",
+            gcc_location(&self.pos, root),
+            severity,
+            self.message,
+            self.code.as_str()
+        );
+        for (pos, message) in self.related.iter() {
+            result.push_str(&format!("\n{}: note: {}", gcc_location(pos, root), message));
+        }
+        result
+    }
+}
+
+/// A JSON array with an object for each diagnostic.
+/// The objects are also issues of the GitLab code quality report
+pub fn diagnostics_to_json(diagnostics: &[Diagnostic], root: &Path) -> serde_json::Value {
+    let diagnostics: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let related: Vec<_> = diagnostic
+                .related
+                .iter()
+                .map(|(pos, message)| {
+                    json!({
+                        "file": relative_path(pos, root),
+                        "range": range_to_json(pos),
+                        "message": message,
+                    })
+                })
+                .collect();
+
+            let gitlab_severity = match diagnostic.severity {
+                Severity::Error => "major",
+                Severity::Warning => "minor",
+                Severity::Info | Severity::Hint => "info",
+            };
+            json!({
+                "rule": diagnostic.code.as_str(),
+                "level": diagnostic.severity.as_str(),
+                "file": relative_path(&diagnostic.pos, root),
+                "range": range_to_json(&diagnostic.pos),
+                "message": diagnostic.message,
+                "related": related,
+                "check_name": diagnostic.code.as_str(),
+                "description": diagnostic.message,
+                "severity": gitlab_severity,
+                "fingerprint": fingerprint(diagnostic, root),
+                "location": {
+                    "path": relative_path(&diagnostic.pos, root),
+                    "lines": {
+                        "begin": diagnostic.pos.start().line + 1,
+                        "end": diagnostic.pos.end().line + 1,
+                    },
+                },
+            })
+        })
+        .collect();
+    json!(diagnostics)
+}
+
+/// A SARIF 2.1.0 log with a single run, as read by GitHub code scanning
+pub fn diagnostics_to_sarif(diagnostics: &[Diagnostic], root: &Path) -> serde_json::Value {
+    let rules: Vec<_> = DiagnosticCode::all()
+        .iter()
+        .map(|code| {
+            json!({
+                "id": code.as_str(),
+                "shortDescription": { "text": code.describe() },
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let related: Vec<_> = diagnostic
+                .related
+                .iter()
+                .enumerate()
+                .map(|(idx, (pos, message))| {
+                    json!({
+                        "id": idx,
+                        "physicalLocation": sarif_location(pos, root),
+                        "message": { "text": message },
+                    })
+                })
+                .collect();
+
+            let level = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info | Severity::Hint => "note",
+            };
+            json!({
+                "ruleId": diagnostic.code.as_str(),
+                "ruleIndex": DiagnosticCode::all()
+                    .iter()
+                    .position(|code| *code == diagnostic.code),
+                "level": level,
+                "message": { "text": diagnostic.message },
+                "locations": [{ "physicalLocation": sarif_location(&diagnostic.pos, root) }],
+                "relatedLocations": related,
+                "partialFingerprints": { "primaryLocationLineHash": fingerprint(diagnostic, root) },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "vhdl_lang",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/VHDL-LS/rust_hdl",
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// A JUnit XML report with a test suite for each file and a failed test case for each diagnostic
+pub fn diagnostics_to_junit(diagnostics: &[Diagnostic], root: &Path) -> String {
+    let mut files: BTreeMap<String, Vec<&Diagnostic>> = BTreeMap::new();
+    for diagnostic in diagnostics {
+        files
+            .entry(relative_path(&diagnostic.pos, root))
+            .or_default()
+            .push(diagnostic);
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"vhdl_lang\" tests=\"{0}\" failures=\"{0}\">\n",
+        diagnostics.len()
+    ));
+    for (file_name, diagnostics) in files {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{1}\" failures=\"{1}\" errors=\"0\">\n",
+            xml_escape(&file_name),
+            diagnostics.len()
+        ));
+        for diagnostic in diagnostics {
+            let location = gcc_location(&diagnostic.pos, root);
+            xml.push_str(&format!(
+                "    <testcase name=\"{} {}\" classname=\"{}\">\n",
+                xml_escape(&location),
+                diagnostic.code.as_str(),
+                xml_escape(&file_name)
+            ));
+            xml.push_str(&format!(
+                "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                diagnostic.severity.as_str(),
+                xml_escape(&diagnostic.message),
+                xml_escape(&diagnostic.show_gcc(root))
+            ));
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// The file name of a position relative to the root folder if it is below it
+fn relative_path(pos: &SrcPos, root: &Path) -> String {
+    let file_name = pos.file_name();
+    file_name
+        .strip_prefix(root)
+        .unwrap_or(file_name)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn gcc_location(pos: &SrcPos, root: &Path) -> String {
+    format!(
+        "{}:{}:{}",
+        relative_path(pos, root),
+        pos.start().line + 1,
+        pos.start().character + 1
+    )
+}
+
+fn range_to_json(pos: &SrcPos) -> serde_json::Value {
+    json!({
+        "start": { "line": pos.start().line + 1, "column": pos.start().character + 1 },
+        "end": { "line": pos.end().line + 1, "column": pos.end().character + 1 },
+    })
+}
+
+fn sarif_location(pos: &SrcPos, root: &Path) -> serde_json::Value {
+    json!({
+        "artifactLocation": { "uri": relative_path(pos, root) },
+        "region": {
+            "startLine": pos.start().line + 1,
+            "startColumn": pos.start().character + 1,
+            "endLine": pos.end().line + 1,
+            "endColumn": pos.end().character + 1,
+        },
+    })
+}
+
+/// Identifies a diagnostic between runs, as long as the line it is on does not move
+fn fingerprint(diagnostic: &Diagnostic, root: &Path) -> String {
+    let mut hasher = FnvHasher::default();
+    relative_path(&diagnostic.pos, root).hash(&mut hasher);
+    diagnostic.pos.start().line.hash(&mut hasher);
+    diagnostic.code.as_str().hash(&mut hasher);
+    diagnostic.message.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for chr in text.chars() {
+        match chr {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(chr),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+    use pretty_assertions::assert_eq;
+
+    fn diagnostic() -> (Diagnostic, &'static Path) {
+        let root = Path::new("/project");
+        let code = Code::new_with_file_name(
+            &root.join("src").join("file.vhd"),
+            "signal sig_a : bit;\nsignal sig_b : bit;\n",
+        );
+        let diagnostic =
+            Diagnostic::warning(code.s1("sig_b"), "Unused declaration of signal 'sig_b'")
+                .with_code(DiagnosticCode::Unused)
+                .related(code.s1("sig_a"), "Similar <signal>");
+        (diagnostic, root)
+    }
+
+    #[test]
+    fn gcc_format() {
+        let (diagnostic, root) = diagnostic();
+        assert_eq!(
+            diagnostic.show_gcc(root),
+            "\
+src/file.vhd:2:8: warning: Unused declaration of signal 'sig_b' [unused]
+src/file.vhd:1:8: note: Similar <signal>"
+        );
+    }
+
+    #[test]
+    fn json_format() {
+        let (diagnostic, root) = diagnostic();
+        let json = diagnostics_to_json(&[diagnostic], root);
+        let issue = &json[0];
+        assert_eq!(issue["rule"], "unused");
+        assert_eq!(issue["level"], "warning");
+        assert_eq!(issue["file"], "src/file.vhd");
+        assert_eq!(
+            issue["range"],
+            json!({ "start": { "line": 2, "column": 8 }, "end": { "line": 2, "column": 13 } })
+        );
+        assert_eq!(issue["related"][0]["message"], "Similar <signal>");
+        assert_eq!(issue["check_name"], "unused");
+        assert_eq!(issue["severity"], "minor");
+        assert_eq!(issue["location"]["lines"]["begin"], 2);
+        assert_eq!(issue["fingerprint"].as_str().map(str::len), Some(16));
+    }
+
+    #[test]
+    fn sarif_format() {
+        let (diagnostic, root) = diagnostic();
+        let sarif = diagnostics_to_sarif(&[diagnostic], root);
+        let run = &sarif["runs"][0];
+        let result = &run["results"][0];
+        let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(run["tool"]["driver"]["rules"][rule_index]["id"], "unused");
+        assert_eq!(result["ruleId"], "unused");
+        assert_eq!(result["level"], "warning");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "src/file.vhd" },
+                "region": { "startLine": 2, "startColumn": 8, "endLine": 2, "endColumn": 13 },
+            })
+        );
+        assert_eq!(
+            result["relatedLocations"][0]["message"]["text"],
+            "Similar <signal>"
+        );
+    }
+
+    #[test]
+    fn junit_format() {
+        let (diagnostic, root) = diagnostic();
+        assert_eq!(
+            diagnostics_to_junit(&[diagnostic], root),
+            "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<testsuites name=\"vhdl_lang\" tests=\"1\" failures=\"1\">
+  <testsuite name=\"src/file.vhd\" tests=\"1\" failures=\"1\" errors=\"0\">
+    <testcase name=\"src/file.vhd:2:8 unused\" classname=\"src/file.vhd\">
+      <failure type=\"warning\" message=\"Unused declaration of signal &apos;sig_b&apos;\">\
+src/file.vhd:2:8: warning: Unused declaration of signal &apos;sig_b&apos; [unused]
+src/file.vhd:1:8: note: Similar &lt;signal&gt;</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"
+        );
+    }
+}
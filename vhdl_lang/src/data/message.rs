@@ -51,13 +51,18 @@ impl Message {
     }
 
     pub fn file_error(message: impl Into<String>, file_name: &Path) -> Message {
+        Message::error(message).in_file(file_name, None)
+    }
+
+    /// Refer to the file, and optionally the line, that the message is about
+    pub fn in_file(self, file_name: &Path, line: Option<usize>) -> Message {
+        let location = match line {
+            Some(line) => format!("{}:{line}", file_name.to_string_lossy()),
+            None => file_name.to_string_lossy().into_owned(),
+        };
         Message {
-            message_type: MessageType::Error,
-            message: format!(
-                "{} (In file {})",
-                message.into(),
-                file_name.to_string_lossy()
-            ),
+            message_type: self.message_type,
+            message: format!("{} (In file {location})", self.message),
         }
     }
 }
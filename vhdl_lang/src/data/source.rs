@@ -24,11 +24,10 @@ struct FileId {
 
 impl FileId {
     fn new(name: &Path) -> FileId {
-        let hash = hash(name);
-        Self {
-            name: FilePath::new(name),
-            hash,
-        }
+        // Hash the absolute path, such that the hash is equal for equal names
+        let name = FilePath::new(name);
+        let hash = hash(&name);
+        Self { name, hash }
     }
 }
 
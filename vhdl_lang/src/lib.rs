@@ -16,30 +16,43 @@ extern crate self as vhdl_lang;
 pub mod ast;
 #[macro_use]
 mod analysis;
+mod baseline;
 mod config;
 mod data;
 mod lint;
 mod named_entity;
 mod project;
+mod standard;
 mod syntax;
 
 mod completion;
 
+pub use crate::baseline::Baseline;
 pub use crate::config::Config;
 pub use crate::data::{
-    Diagnostic, Latin1String, Message, MessageHandler, MessagePrinter, MessageType,
+    diagnostics_to_json, diagnostics_to_junit, diagnostics_to_sarif, Diagnostic, DiagnosticCode,
+    DiagnosticTag, Latin1String, Message, MessageHandler, MessagePrinter, MessageType,
     NullDiagnostics, NullMessages, Position, Range, Severity, Source, SrcPos,
 };
 
-pub use crate::analysis::EntHierarchy;
+pub use crate::analysis::{
+    CompileOrderFile, CompileOrderScope, DependencyEdge, DependencyGraph, DependencyKind,
+    DependencyNode, EntHierarchy,
+};
 pub use crate::named_entity::{
-    AnyEnt, AnyEntKind, Concurrent, Design, EntRef, EntityId, HasEntityId, Object, Overloaded,
-    Reference, Related, Sequential, Type,
+    AnyEnt, AnyEntKind, Concurrent, Design, EntRef, EntityId, Group, HasEntityId, Object,
+    Overloaded, Psl, Reference, Related, Sequential, Type,
 };
 
+pub use crate::lint::clock_domain::ClockEdge;
+pub use crate::lint::registers::{
+    ArchitectureRegisters, InferredRegister, InferredReset, ResetKind,
+};
 pub use crate::project::{Project, SourceFile};
+pub use crate::standard::VHDLStandard;
 pub use crate::syntax::{
-    kind_str, HasTokenSpan, ParserResult, Token, TokenAccess, TokenId, TokenSpan, VHDLParser,
+    kind_str, ConditionalIdentifiers, HasTokenSpan, ParserResult, Token, TokenAccess, TokenId,
+    TokenSpan, VHDLParser,
 };
 
 pub use completion::{list_completion_options, CompletionItem};
@@ -4,4 +4,7 @@
 //
 // Copyright (c) 2022, Olof Kraigher olof.kraigher@gmail.com
 
+pub mod clock_domain;
 pub mod dead_code;
+pub mod registers;
+pub mod synthesis;
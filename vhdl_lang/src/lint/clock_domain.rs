@@ -0,0 +1,1314 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Clock domain crossing detection
+//!
+//! A signal assigned under a clock edge condition is considered a register clocked by that clock.
+//! Combinational logic inherits the clock domains of the signals it reads and instances propagate
+//! the clock domains of their ports to the actual signals. A register or combinational signal
+//! that is read by logic clocked by another clock is reported unless it is the input of a
+//! synchronizer, that is a register chain where each stage is a plain copy of the previous one.
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::Search;
+use crate::ast::search::SearchState;
+use crate::ast::search::Searcher;
+use crate::ast::*;
+use crate::data::Symbol;
+use crate::data::{DiagnosticCode, DiagnosticHandler};
+use crate::named_entity::Reference;
+use crate::syntax::TokenAccess;
+use crate::AnyEntKind;
+use crate::Config;
+use crate::Design;
+use crate::Diagnostic;
+use crate::EntityId;
+use crate::SrcPos;
+use fnv::FnvHashMap;
+use fnv::FnvHashSet;
+use std::rc::Rc;
+
+/// The active edge of a register clock
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ClockEdge {
+    Rising,
+    Falling,
+}
+
+/// The clock signal of a register together with its active edge
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub(crate) struct Clock {
+    pub signal: EntityId,
+    pub edge: ClockEdge,
+}
+
+pub(crate) fn is_signal(root: &DesignRoot, id: EntityId) -> bool {
+    matches!(root.get_ent(id).kind(), AnyEntKind::Object(obj) if obj.class == ObjectClass::Signal)
+}
+
+fn signal_ref(root: &DesignRoot, reference: &Reference) -> Option<EntityId> {
+    reference.get().filter(|id| is_signal(root, *id))
+}
+
+/// The signal that is the prefix of a name such as `sig`, `sig(0)`, `sig(1 downto 0)` or `sig.elem`
+pub(crate) fn base_signal(root: &DesignRoot, name: &Name) -> Option<EntityId> {
+    match name {
+        Name::Designator(designator) => signal_ref(root, &designator.reference),
+        Name::Selected(prefix, suffix) => {
+            signal_ref(root, &suffix.item.reference).or_else(|| base_signal(root, &prefix.item))
+        }
+        Name::Slice(prefix, _) => base_signal(root, &prefix.item),
+        Name::CallOrIndexed(call) => base_signal(root, &call.name.item),
+        Name::SelectedAll(..) | Name::Attribute(..) | Name::External(..) => None,
+    }
+}
+
+pub(crate) fn expression_signal(root: &DesignRoot, expr: &Expression) -> Option<EntityId> {
+    if let Expression::Name(name) = expr {
+        if let Name::Designator(designator) = name.as_ref() {
+            return signal_ref(root, &designator.reference);
+        }
+    }
+    None
+}
+
+/// `clk'event`
+fn event_signal(root: &DesignRoot, expr: &Expression) -> Option<EntityId> {
+    if let Expression::Name(name) = expr {
+        if let Name::Attribute(attr) = name.as_ref() {
+            if attr.attr.item == AttributeDesignator::Signal(SignalAttribute::Event) {
+                if let Name::Designator(ref designator) = attr.name.item {
+                    return signal_ref(root, &designator.reference);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// `clk = '1'` or `'1' = clk`
+fn level_of(root: &DesignRoot, expr: &Expression, signal: EntityId) -> Option<ClockEdge> {
+    if let Expression::Binary(op, lhs, rhs) = expr {
+        if op.item.item != Operator::EQ {
+            return None;
+        }
+
+        let (level, other) = if let Expression::Literal(Literal::Character(chr)) = lhs.item {
+            (chr, &rhs.item)
+        } else if let Expression::Literal(Literal::Character(chr)) = rhs.item {
+            (chr, &lhs.item)
+        } else {
+            return None;
+        };
+
+        if expression_signal(root, other) != Some(signal) {
+            return None;
+        }
+
+        match level {
+            b'1' => Some(ClockEdge::Rising),
+            b'0' => Some(ClockEdge::Falling),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Returns the clock if the expression is a clock edge condition such as
+/// `rising_edge(clk)`, `falling_edge(clk)` or `clk'event and clk = '1'`.
+/// An edge condition may be combined with an enable using `and`.
+pub(crate) fn clock_edge(root: &DesignRoot, expr: &Expression) -> Option<Clock> {
+    match expr {
+        Expression::Name(name) => {
+            let Name::CallOrIndexed(call) = name.as_ref() else {
+                return None;
+            };
+            let Name::Designator(function) = &call.name.item else {
+                return None;
+            };
+            let edge = match &function.item {
+                Designator::Identifier(sym) if sym.name_utf8() == "rising_edge" => {
+                    ClockEdge::Rising
+                }
+                Designator::Identifier(sym) if sym.name_utf8() == "falling_edge" => {
+                    ClockEdge::Falling
+                }
+                _ => return None,
+            };
+            let [param] = call.parameters.as_slice() else {
+                return None;
+            };
+            let ActualPart::Expression(ref actual) = param.actual.item else {
+                return None;
+            };
+            Some(Clock {
+                signal: expression_signal(root, actual)?,
+                edge,
+            })
+        }
+        Expression::Binary(op, lhs, rhs) if op.item.item == Operator::And => {
+            for (event, level) in [(lhs, rhs), (rhs, lhs)] {
+                if let Some(signal) = event_signal(root, &event.item) {
+                    if let Some(edge) = level_of(root, &level.item, signal) {
+                        return Some(Clock { signal, edge });
+                    }
+                }
+            }
+            clock_edge(root, &lhs.item).or_else(|| clock_edge(root, &rhs.item))
+        }
+        _ => None,
+    }
+}
+
+/// Collects all signals that are referenced by a piece of the AST
+struct SignalReads<'a> {
+    root: &'a DesignRoot,
+    reads: Vec<(EntityId, SrcPos)>,
+}
+
+impl<'a> Searcher for SignalReads<'a> {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = signal_ref(self.root, reference) {
+            self.reads.push((id, pos.clone()));
+        }
+        SearchState::NotFinished
+    }
+}
+
+pub(crate) fn signal_reads(
+    root: &DesignRoot,
+    ctx: &dyn TokenAccess,
+    item: &impl Search,
+) -> Vec<(EntityId, SrcPos)> {
+    let mut searcher = SignalReads {
+        root,
+        reads: Vec::new(),
+    };
+    let _ = item.search(ctx, &mut searcher);
+    searcher.reads
+}
+
+fn rhs_reads(
+    root: &DesignRoot,
+    ctx: &dyn TokenAccess,
+    rhs: &AssignmentRightHand<impl Search>,
+) -> Vec<(EntityId, SrcPos)> {
+    let mut reads = Vec::new();
+    match rhs {
+        AssignmentRightHand::Simple(item) => reads.extend(signal_reads(root, ctx, item)),
+        AssignmentRightHand::Conditional(conds) => {
+            for cond in conds.conditionals.iter() {
+                reads.extend(signal_reads(root, ctx, &cond.condition));
+                reads.extend(signal_reads(root, ctx, &cond.item));
+            }
+            if let Some(ref item) = conds.else_item {
+                reads.extend(signal_reads(root, ctx, item));
+            }
+        }
+        AssignmentRightHand::Selected(selection) => {
+            reads.extend(signal_reads(root, ctx, &selection.expression));
+            for alternative in selection.alternatives.iter() {
+                reads.extend(signal_reads(root, ctx, &alternative.item));
+            }
+        }
+    }
+    reads
+}
+
+pub(crate) fn target_signals(root: &DesignRoot, target: &Target) -> Vec<EntityId> {
+    match target {
+        Target::Name(name) => base_signal(root, name).into_iter().collect(),
+        Target::Aggregate(assocs) => assocs
+            .iter()
+            .filter_map(|assoc| match assoc {
+                ElementAssociation::Positional(expr) | ElementAssociation::Named(_, expr) => {
+                    match expr.item {
+                        Expression::Name(ref name) => base_signal(root, name),
+                        _ => None,
+                    }
+                }
+            })
+            .collect(),
+    }
+}
+
+/// The source signal of a plain copy such as `q <= d;`
+fn copied_signal(root: &DesignRoot, rhs: &AssignmentRightHand<Waveform>) -> Option<EntityId> {
+    if let AssignmentRightHand::Simple(Waveform::Elements(elems)) = rhs {
+        if let [elem] = elems.as_slice() {
+            if let Expression::Name(ref name) = elem.value.item {
+                return base_signal(root, name);
+            }
+        }
+    }
+    None
+}
+
+/// Where the clock domain of a port of a design unit comes from
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+enum ClockSource {
+    /// A clock input port
+    Port(Symbol),
+    /// A clock that is internal to the design unit
+    Internal(EntityId),
+}
+
+/// The clock domain properties of the ports of an architecture as seen by an instantiating unit
+#[derive(Default)]
+struct InterfaceSummary {
+    /// Ports in declaration order used for positional association
+    ports: Vec<Symbol>,
+    /// Clock domains of output ports
+    outputs: FnvHashMap<Symbol, FnvHashSet<ClockSource>>,
+    /// Clocks of the logic reading input ports without synchronization
+    inputs: FnvHashMap<Symbol, FnvHashSet<ClockSource>>,
+}
+
+impl InterfaceSummary {
+    fn merge(&mut self, other: &InterfaceSummary) {
+        if self.ports.is_empty() {
+            self.ports = other.ports.clone();
+        }
+        for (port, clocks) in other.outputs.iter() {
+            self.outputs
+                .entry(port.clone())
+                .or_default()
+                .extend(clocks.iter().cloned());
+        }
+        for (port, clocks) in other.inputs.iter() {
+            self.inputs
+                .entry(port.clone())
+                .or_default()
+                .extend(clocks.iter().cloned());
+        }
+    }
+}
+
+struct ClockedRead {
+    signal: EntityId,
+    pos: SrcPos,
+    clock: EntityId,
+    /// The register that is a plain copy of the read signal
+    copy_to: Option<EntityId>,
+}
+
+/// Signals assigned and read by the combinational part of a process
+#[derive(Default)]
+struct Combinational {
+    targets: Vec<EntityId>,
+    reads: Vec<EntityId>,
+}
+
+/// The facts gathered from the statements of a single architecture
+#[derive(Default)]
+struct ArchitectureFacts {
+    /// Clocks of registers and of signals driven by instance outputs
+    clocked_by: FnvHashMap<EntityId, FnvHashSet<EntityId>>,
+    /// Signals read by the combinational logic driving a signal
+    drivers: FnvHashMap<EntityId, FnvHashSet<EntityId>>,
+    clocked_reads: Vec<ClockedRead>,
+}
+
+type UnitKey = (Symbol, Symbol, Symbol);
+
+struct ClockDomainAnalysis<'a> {
+    root: &'a DesignRoot,
+    /// Summaries of analyzed architectures, None while being analyzed
+    summaries: FnvHashMap<UnitKey, Option<Rc<InterfaceSummary>>>,
+    diagnostics: Vec<(Symbol, Diagnostic)>,
+}
+
+impl<'a> ClockDomainAnalysis<'a> {
+    fn new(root: &'a DesignRoot) -> Self {
+        Self {
+            root,
+            summaries: FnvHashMap::default(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn run(&mut self) {
+        for library in self.root.libraries() {
+            for unit in library.primary_units() {
+                for secondary in library.secondary_units(unit.name()) {
+                    self.architecture_summary(library.name(), unit.name(), secondary.name());
+                }
+            }
+        }
+    }
+
+    fn architecture_names(&self, library_name: &Symbol, entity_name: &Symbol) -> Vec<Symbol> {
+        let Some(library) = self.root.get_lib(library_name) else {
+            return Vec::new();
+        };
+        library
+            .secondary_units(entity_name)
+            .filter(|unit| {
+                unit.unit.get().is_some_and(|unit| {
+                    matches!(
+                        unit.data(),
+                        AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(_))
+                    )
+                })
+            })
+            .map(|unit| unit.name().clone())
+            .collect()
+    }
+
+    fn entity_summary(
+        &mut self,
+        library_name: &Symbol,
+        entity_name: &Symbol,
+        architecture_name: Option<&Symbol>,
+    ) -> InterfaceSummary {
+        let architecture_names = if let Some(name) = architecture_name {
+            vec![name.clone()]
+        } else {
+            self.architecture_names(library_name, entity_name)
+        };
+
+        let mut summary = InterfaceSummary::default();
+        for name in architecture_names {
+            if let Some(arch_summary) = self.architecture_summary(library_name, entity_name, &name)
+            {
+                summary.merge(&arch_summary);
+            }
+        }
+        summary
+    }
+
+    fn architecture_summary(
+        &mut self,
+        library_name: &Symbol,
+        entity_name: &Symbol,
+        architecture_name: &Symbol,
+    ) -> Option<Rc<InterfaceSummary>> {
+        let key = (
+            library_name.clone(),
+            entity_name.clone(),
+            architecture_name.clone(),
+        );
+
+        if let Some(summary) = self.summaries.get(&key) {
+            // A recursive instantiation has no summary yet
+            return summary.clone();
+        }
+        self.summaries.insert(key.clone(), None);
+
+        let library = self.root.get_lib(library_name)?;
+        let entity_unit = library.primary_unit(entity_name)?;
+        let arch_unit = library
+            .secondary_units(entity_name)
+            .find(|unit| unit.name() == architecture_name)?;
+
+        let entity_guard = entity_unit.unit.get()?;
+        let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) = entity_guard.data() else {
+            return None;
+        };
+        let arch_guard = arch_unit.unit.get()?;
+        let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) = arch_guard.data()
+        else {
+            return None;
+        };
+
+        let mut ports: FnvHashMap<EntityId, (Symbol, Mode)> = FnvHashMap::default();
+        let mut port_names = Vec::new();
+        for port in entity.port_clause.iter().flatten() {
+            if let InterfaceDeclaration::Object(obj) = port {
+                if let Some(id) = obj.ident.decl.get() {
+                    // A port with a mode view may drive some of its elements
+                    let mode = obj.mode.simple_mode().unwrap_or(Mode::InOut);
+                    ports.insert(id, (obj.ident.tree.item.clone(), mode));
+                }
+                port_names.push(obj.ident.tree.item.clone());
+            }
+        }
+
+        let mut facts = ArchitectureFacts::default();
+        self.concurrent(
+            &mut facts,
+            library_name,
+            &arch_unit.tokens,
+            &arch.statements,
+        );
+
+        let summary = Rc::new(self.check_crossings(library_name, &facts, &ports, port_names));
+        self.summaries.insert(key, Some(summary.clone()));
+        Some(summary)
+    }
+
+    fn concurrent(
+        &mut self,
+        facts: &mut ArchitectureFacts,
+        library_name: &Symbol,
+        ctx: &dyn TokenAccess,
+        statements: &[LabeledConcurrentStatement],
+    ) {
+        for statement in statements.iter() {
+            match statement.statement.item {
+                ConcurrentStatement::Block(ref block) => {
+                    self.concurrent(facts, library_name, ctx, &block.statements)
+                }
+                ConcurrentStatement::Process(ref process) => {
+                    self.process(facts, ctx, process);
+                }
+                ConcurrentStatement::Assignment(ref assign) => {
+                    let reads = rhs_reads(self.root, ctx, &assign.rhs);
+                    for target in target_signals(self.root, &assign.target.item) {
+                        facts
+                            .drivers
+                            .entry(target)
+                            .or_default()
+                            .extend(reads.iter().map(|(id, _)| *id));
+                    }
+                }
+                ConcurrentStatement::Instance(ref instance) => {
+                    self.instance(facts, library_name, instance);
+                }
+                ConcurrentStatement::ForGenerate(ref gen) => {
+                    self.concurrent(facts, library_name, ctx, &gen.body.statements);
+                }
+                ConcurrentStatement::IfGenerate(ref gen) => {
+                    for cond in gen.conds.conditionals.iter() {
+                        self.concurrent(facts, library_name, ctx, &cond.item.statements);
+                    }
+                    if let Some(ref body) = gen.conds.else_item {
+                        self.concurrent(facts, library_name, ctx, &body.statements);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(ref gen) => {
+                    for alternative in gen.sels.alternatives.iter() {
+                        self.concurrent(facts, library_name, ctx, &alternative.item.statements);
+                    }
+                }
+                ConcurrentStatement::ProcedureCall(..)
+                | ConcurrentStatement::Assert(..)
+                | ConcurrentStatement::Psl(..) => {}
+            }
+        }
+    }
+
+    fn process(
+        &self,
+        facts: &mut ArchitectureFacts,
+        ctx: &dyn TokenAccess,
+        process: &ProcessStatement,
+    ) {
+        let mut statements = process.statements.as_slice();
+        let mut clock = None;
+
+        // A process starting with `wait until rising_edge(clk);` is clocked in its entirety
+        if let Some((first, rest)) = statements.split_first() {
+            if let SequentialStatement::Wait(ref wait) = first.statement.item {
+                if let Some(ref cond) = wait.condition_clause {
+                    if let Some(edge) = clock_edge(self.root, &cond.item) {
+                        clock = Some(edge.signal);
+                        statements = rest;
+                    }
+                }
+            }
+        }
+
+        let mut comb = Combinational::default();
+        self.sequential(facts, ctx, statements, clock, &mut comb);
+
+        for target in comb.targets {
+            facts
+                .drivers
+                .entry(target)
+                .or_default()
+                .extend(comb.reads.iter().copied());
+        }
+    }
+
+    fn read(
+        &self,
+        facts: &mut ArchitectureFacts,
+        reads: Vec<(EntityId, SrcPos)>,
+        clock: Option<EntityId>,
+        comb: &mut Combinational,
+    ) {
+        for (signal, pos) in reads {
+            if let Some(clock) = clock {
+                facts.clocked_reads.push(ClockedRead {
+                    signal,
+                    pos,
+                    clock,
+                    copy_to: None,
+                });
+            } else {
+                comb.reads.push(signal);
+            }
+        }
+    }
+
+    fn sequential(
+        &self,
+        facts: &mut ArchitectureFacts,
+        ctx: &dyn TokenAccess,
+        statements: &[LabeledSequentialStatement],
+        clock: Option<EntityId>,
+        comb: &mut Combinational,
+    ) {
+        for statement in statements.iter() {
+            match statement.statement.item {
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    let targets = target_signals(self.root, &assign.target.item);
+                    let reads = rhs_reads(self.root, ctx, &assign.rhs);
+
+                    if let Some(clock) = clock {
+                        let copy = match (targets.as_slice(), copied_signal(self.root, &assign.rhs))
+                        {
+                            ([target], Some(source)) => Some((*target, source)),
+                            _ => None,
+                        };
+
+                        for target in targets.iter() {
+                            facts.clocked_by.entry(*target).or_default().insert(clock);
+                        }
+
+                        for (signal, pos) in reads {
+                            let copy_to = copy
+                                .filter(|(_, source)| *source == signal)
+                                .map(|(target, _)| target);
+                            facts.clocked_reads.push(ClockedRead {
+                                signal,
+                                pos,
+                                clock,
+                                copy_to,
+                            });
+                        }
+                    } else {
+                        comb.targets.extend(targets);
+                        comb.reads.extend(reads.into_iter().map(|(id, _)| id));
+                    }
+                }
+                SequentialStatement::VariableAssignment(ref assign) => {
+                    let reads = rhs_reads(self.root, ctx, &assign.rhs);
+                    self.read(facts, reads, clock, comb);
+                }
+                SequentialStatement::ProcedureCall(ref call) => {
+                    let reads = signal_reads(self.root, ctx, &call.item.parameters);
+                    self.read(facts, reads, clock, comb);
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    for cond in ifstmt.conds.conditionals.iter() {
+                        let reads = signal_reads(self.root, ctx, &cond.condition);
+
+                        if let Some(edge) = clock_edge(self.root, &cond.condition.item) {
+                            let reads = reads
+                                .into_iter()
+                                .filter(|(id, _)| *id != edge.signal)
+                                .collect();
+                            self.read(facts, reads, Some(edge.signal), comb);
+                            self.sequential(facts, ctx, &cond.item, Some(edge.signal), comb);
+                        } else {
+                            self.read(facts, reads, clock, comb);
+                            self.sequential(facts, ctx, &cond.item, clock, comb);
+                        }
+                    }
+                    if let Some(ref item) = ifstmt.conds.else_item {
+                        self.sequential(facts, ctx, item, clock, comb);
+                    }
+                }
+                SequentialStatement::Case(ref case) => {
+                    let reads = signal_reads(self.root, ctx, &case.expression);
+                    self.read(facts, reads, clock, comb);
+                    for alternative in case.alternatives.iter() {
+                        self.sequential(facts, ctx, &alternative.item, clock, comb);
+                    }
+                }
+                SequentialStatement::Loop(ref lp) => {
+                    if let Some(IterationScheme::While(ref cond)) = lp.iteration_scheme {
+                        let reads = signal_reads(self.root, ctx, cond);
+                        self.read(facts, reads, clock, comb);
+                    }
+                    self.sequential(facts, ctx, &lp.statements, clock, comb);
+                }
+                SequentialStatement::SequentialBlock(ref block) => {
+                    self.sequential(facts, ctx, &block.statements, clock, comb);
+                }
+                SequentialStatement::Wait(..)
+                | SequentialStatement::Assert(..)
+                | SequentialStatement::Report(..)
+                | SequentialStatement::SignalForceAssignment(..)
+                | SequentialStatement::SignalReleaseAssignment(..)
+                | SequentialStatement::Next(..)
+                | SequentialStatement::Exit(..)
+                | SequentialStatement::Return(..)
+                | SequentialStatement::Null => {}
+            }
+        }
+    }
+
+    fn instance(
+        &mut self,
+        facts: &mut ArchitectureFacts,
+        library_name: &Symbol,
+        instance: &InstantiationStatement,
+    ) {
+        let Some(id) = instance.entity_reference() else {
+            return;
+        };
+        let ent = self.root.get_ent(id);
+        let Designator::Identifier(entity_name) = ent.designator() else {
+            return;
+        };
+
+        let (library_name, architecture_name) = match (&instance.unit, ent.kind()) {
+            (InstantiatedUnit::Entity(_, arch), AnyEntKind::Design(Design::Entity(..))) => (
+                ent.library_name().unwrap_or(library_name).clone(),
+                arch.as_ref().map(|arch| arch.item.item.clone()),
+            ),
+            // Default binding to an entity with the same name
+            (InstantiatedUnit::Component(_), AnyEntKind::Component(..)) => {
+                (library_name.clone(), None)
+            }
+            _ => return,
+        };
+
+        let summary = self.entity_summary(&library_name, entity_name, architecture_name.as_ref());
+
+        let Some(ref port_map) = instance.port_map else {
+            return;
+        };
+
+        let mut actuals: FnvHashMap<Symbol, (EntityId, SrcPos)> = FnvHashMap::default();
+        for (idx, assoc) in port_map.list.items.iter().enumerate() {
+            let formal = if let Some(ref formal) = assoc.formal {
+                formal_name(&formal.item)
+            } else {
+                summary.ports.get(idx).cloned()
+            };
+            let ActualPart::Expression(Expression::Name(ref name)) = assoc.actual.item else {
+                continue;
+            };
+
+            if let (Some(formal), Some(actual)) = (formal, base_signal(self.root, name)) {
+                actuals.insert(formal, (actual, assoc.actual.pos.clone()));
+            }
+        }
+
+        let clock_of = |source: &ClockSource| match source {
+            ClockSource::Port(port) => actuals.get(port).map(|(id, _)| *id),
+            ClockSource::Internal(id) => Some(*id),
+        };
+
+        for (port, sources) in summary.outputs.iter() {
+            if let Some((actual, _)) = actuals.get(port) {
+                facts
+                    .clocked_by
+                    .entry(*actual)
+                    .or_default()
+                    .extend(sources.iter().filter_map(clock_of));
+            }
+        }
+
+        for (port, sources) in summary.inputs.iter() {
+            if let Some((actual, pos)) = actuals.get(port) {
+                for clock in sources.iter().filter_map(clock_of) {
+                    facts.clocked_reads.push(ClockedRead {
+                        signal: *actual,
+                        pos: pos.clone(),
+                        clock,
+                        copy_to: None,
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_crossings(
+        &mut self,
+        library_name: &Symbol,
+        facts: &ArchitectureFacts,
+        ports: &FnvHashMap<EntityId, (Symbol, Mode)>,
+        port_names: Vec<Symbol>,
+    ) -> InterfaceSummary {
+        let mut domains = DomainResolver::new(facts);
+        let mut summary = InterfaceSummary {
+            ports: port_names,
+            ..Default::default()
+        };
+
+        let source_of = |clock: EntityId| {
+            if let Some((name, _)) = ports.get(&clock) {
+                ClockSource::Port(name.clone())
+            } else {
+                ClockSource::Internal(clock)
+            }
+        };
+
+        for read in facts.clocked_reads.iter() {
+            if domains.is_synchronizer_input(read) {
+                continue;
+            }
+
+            let clock = domains.resolve_clock(read.clock);
+
+            let mut foreign: Vec<_> = domains
+                .domains_of(read.signal)
+                .into_iter()
+                .filter(|domain| *domain != clock)
+                .map(|domain| self.root.get_ent(domain).designator().to_string())
+                .collect();
+            foreign.sort();
+
+            if let Some(domain) = foreign.first() {
+                self.diagnostics.push((
+                    library_name.clone(),
+                    Diagnostic::warning(
+                        &read.pos,
+                        format!(
+                            "Signal '{}' from the clock domain of '{}' is read in the clock domain of '{}' without synchronization",
+                            self.root.get_ent(read.signal).designator(),
+                            domain,
+                            self.root.get_ent(clock).designator()
+                        ),
+                    ),
+                ));
+            }
+
+            for input in domains.input_ports_of(read.signal, ports) {
+                if let Some((name, _)) = ports.get(&input) {
+                    summary
+                        .inputs
+                        .entry(name.clone())
+                        .or_default()
+                        .insert(source_of(clock));
+                }
+            }
+        }
+
+        for (id, (name, mode)) in ports.iter() {
+            if matches!(mode, Mode::Out | Mode::InOut | Mode::Buffer) {
+                let clocks = domains.domains_of(*id);
+                if !clocks.is_empty() {
+                    summary
+                        .outputs
+                        .insert(name.clone(), clocks.into_iter().map(source_of).collect());
+                }
+            }
+        }
+
+        summary
+    }
+}
+
+fn formal_name(name: &Name) -> Option<Symbol> {
+    match name {
+        Name::Designator(designator) => match designator.item {
+            Designator::Identifier(ref sym) => Some(sym.clone()),
+            _ => None,
+        },
+        Name::Selected(prefix, _) | Name::Slice(prefix, _) => formal_name(&prefix.item),
+        Name::CallOrIndexed(call) => formal_name(&call.name.item),
+        Name::SelectedAll(..) | Name::Attribute(..) | Name::External(..) => None,
+    }
+}
+
+/// Resolves the clock domains of the signals of an architecture
+struct DomainResolver<'a> {
+    facts: &'a ArchitectureFacts,
+    domains: FnvHashMap<EntityId, FnvHashSet<EntityId>>,
+}
+
+impl<'a> DomainResolver<'a> {
+    fn new(facts: &'a ArchitectureFacts) -> Self {
+        Self {
+            facts,
+            domains: FnvHashMap::default(),
+        }
+    }
+
+    /// Follow clocks that are plain combinational copies of another clock
+    fn resolve_clock(&self, mut clock: EntityId) -> EntityId {
+        let mut visited = FnvHashSet::default();
+        while visited.insert(clock) && !self.facts.clocked_by.contains_key(&clock) {
+            match self.facts.drivers.get(&clock) {
+                Some(sources) if sources.len() == 1 => {
+                    clock = *sources.iter().next().unwrap();
+                }
+                _ => break,
+            }
+        }
+        clock
+    }
+
+    fn domains_of(&mut self, signal: EntityId) -> FnvHashSet<EntityId> {
+        let mut visited = FnvHashSet::default();
+        self.domains_of_visited(signal, &mut visited)
+    }
+
+    fn domains_of_visited(
+        &mut self,
+        signal: EntityId,
+        visited: &mut FnvHashSet<EntityId>,
+    ) -> FnvHashSet<EntityId> {
+        if let Some(domains) = self.domains.get(&signal) {
+            return domains.clone();
+        }
+
+        if !visited.insert(signal) {
+            return FnvHashSet::default();
+        }
+
+        let mut domains: FnvHashSet<EntityId> = FnvHashSet::default();
+        if let Some(clocks) = self.facts.clocked_by.get(&signal) {
+            domains.extend(clocks.iter().map(|clock| self.resolve_clock(*clock)));
+        } else if let Some(sources) = self.facts.drivers.get(&signal) {
+            for source in sources.iter() {
+                domains.extend(self.domains_of_visited(*source, visited));
+            }
+        }
+
+        self.domains.insert(signal, domains.clone());
+        domains
+    }
+
+    /// Input ports that reach the signal through combinational logic
+    fn input_ports_of(
+        &self,
+        signal: EntityId,
+        ports: &FnvHashMap<EntityId, (Symbol, Mode)>,
+    ) -> FnvHashSet<EntityId> {
+        let mut result = FnvHashSet::default();
+        let mut visited = FnvHashSet::default();
+        let mut pending = vec![signal];
+
+        while let Some(signal) = pending.pop() {
+            if !visited.insert(signal) {
+                continue;
+            }
+            if matches!(ports.get(&signal), Some((_, Mode::In | Mode::InOut))) {
+                result.insert(signal);
+            }
+            if !self.facts.clocked_by.contains_key(&signal) {
+                if let Some(sources) = self.facts.drivers.get(&signal) {
+                    pending.extend(sources.iter().copied());
+                }
+            }
+        }
+        result
+    }
+
+    /// The read is the first stage of a register chain in the reading clock domain
+    /// where each stage is a plain copy of the previous one
+    fn is_synchronizer_input(&self, read: &ClockedRead) -> bool {
+        let Some(stage) = read.copy_to else {
+            return false;
+        };
+        let clock = self.resolve_clock(read.clock);
+
+        let stage_clocks = &self.facts.clocked_by[&stage];
+        if stage_clocks
+            .iter()
+            .any(|stage_clock| self.resolve_clock(*stage_clock) != clock)
+        {
+            return false;
+        }
+
+        if self
+            .facts
+            .drivers
+            .values()
+            .any(|sources| sources.contains(&stage))
+        {
+            return false;
+        }
+
+        let mut stage_reads = self
+            .facts
+            .clocked_reads
+            .iter()
+            .filter(|other| other.signal == stage)
+            .peekable();
+
+        stage_reads.peek().is_some()
+            && stage_reads
+                .all(|other| other.copy_to.is_some() && self.resolve_clock(other.clock) == clock)
+    }
+}
+
+/// Use a struct to keep the result between analyses where nothing has changed
+#[derive(Default)]
+pub(crate) struct ClockDomainCrossingLinter {
+    // library name, diagnostic
+    diagnostics: Option<Vec<(Symbol, Diagnostic)>>,
+}
+
+impl ClockDomainCrossingLinter {
+    pub fn lint(
+        &mut self,
+        root: &DesignRoot,
+        config: &Config,
+        analyzed_units: &[UnitId],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        // Clock domains propagate through the hierarchy so everything is re-computed on any change
+        if !analyzed_units.is_empty() {
+            self.diagnostics = None;
+        }
+
+        let crossings = self.diagnostics.get_or_insert_with(|| {
+            let mut analysis = ClockDomainAnalysis::new(root);
+            analysis.run();
+            analysis
+                .diagnostics
+                .into_iter()
+                .map(|(library_name, diagnostic)| {
+                    (
+                        library_name,
+                        diagnostic.with_code(DiagnosticCode::ClockDomainCrossing),
+                    )
+                })
+                .collect()
+        });
+
+        for (library_name, diagnostic) in crossings.iter() {
+            if let Some(library_config) = config.get_library(&library_name.name_utf8()) {
+                if !library_config.is_third_party {
+                    diagnostics.push(diagnostic.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_diagnostics;
+    use crate::syntax::test::check_no_diagnostics;
+
+    fn find_clock_domain_crossings(root: &DesignRoot) -> Vec<Diagnostic> {
+        let mut analysis = ClockDomainAnalysis::new(root);
+        analysis.run();
+        analysis
+            .diagnostics
+            .into_iter()
+            .map(|(_, diagnostic)| diagnostic)
+            .collect()
+    }
+
+    #[test]
+    fn crossing_inside_architecture() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (
+    clk_a, clk_b : in std_logic;
+    d : in std_logic;
+    q : out std_logic
+  );
+end entity;
+
+architecture a of ent is
+  signal reg_a : std_logic;
+begin
+  process(clk_a)
+  begin
+    if rising_edge(clk_a) then
+      reg_a <= d;
+    end if;
+  end process;
+
+  process(clk_b)
+  begin
+    if clk_b'event and clk_b = '1' then
+      q <= not reg_a;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        check_diagnostics(
+            find_clock_domain_crossings(&root),
+            vec![Diagnostic::warning(
+                code.s("reg_a", 3),
+                "Signal 'reg_a' from the clock domain of 'clk_a' is read in the clock domain of 'clk_b' without synchronization",
+            )],
+        );
+    }
+
+    #[test]
+    fn crossing_through_combinational_logic() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (
+    clk_a, clk_b : in std_logic;
+    d : in std_logic;
+    q : out std_logic
+  );
+end entity;
+
+architecture a of ent is
+  signal reg_a, comb : std_logic;
+begin
+  process
+  begin
+    wait until falling_edge(clk_a);
+    reg_a <= d;
+  end process;
+
+  comb <= reg_a and d;
+
+  process(clk_b)
+  begin
+    if rising_edge(clk_b) then
+      if comb = '1' then
+        q <= d;
+      end if;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        check_diagnostics(
+            find_clock_domain_crossings(&root),
+            vec![Diagnostic::warning(
+                code.s("comb", 3),
+                "Signal 'comb' from the clock domain of 'clk_a' is read in the clock domain of 'clk_b' without synchronization",
+            )],
+        );
+    }
+
+    #[test]
+    fn synchronizer_and_same_domain_are_not_crossings() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (
+    clk_a, clk_b, rst : in std_logic;
+    d : in std_logic;
+    q : out std_logic
+  );
+end entity;
+
+architecture a of ent is
+  signal reg_a, meta, sync, clk_a_copy : std_logic;
+begin
+  clk_a_copy <= clk_a;
+
+  process(clk_a)
+  begin
+    if rising_edge(clk_a) then
+      reg_a <= d;
+    end if;
+  end process;
+
+  process(clk_a_copy)
+  begin
+    if rising_edge(clk_a_copy) then
+      q <= reg_a;
+    end if;
+  end process;
+
+  process(clk_b, rst)
+  begin
+    if rst = '1' then
+      meta <= '0';
+      sync <= '0';
+    elsif rising_edge(clk_b) then
+      meta <= reg_a;
+      sync <= meta;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        check_no_diagnostics(&find_clock_domain_crossings(&root));
+    }
+
+    #[test]
+    fn crossing_through_port_maps() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity child is
+  port (
+    clk : in std_logic;
+    d : in std_logic;
+    q : out std_logic
+  );
+end entity;
+
+architecture a of child is
+begin
+  process(clk)
+  begin
+    if rising_edge(clk) then
+      q <= not d;
+    end if;
+  end process;
+end architecture;
+
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity top is
+  port (
+    clk_a, clk_b : in std_logic;
+    d : in std_logic;
+    q : out std_logic
+  );
+end entity;
+
+architecture a of top is
+  signal from_a, from_b : std_logic;
+  component child is
+    port (
+      clk : in std_logic;
+      d : in std_logic;
+      q : out std_logic
+    );
+  end component;
+begin
+  inst_a: entity work.child
+    port map (
+      clk => clk_a,
+      d => d,
+      q => from_a
+    );
+
+  inst_b: child
+    port map (clk_b, from_a, from_b);
+
+  process(clk_a)
+  begin
+    if rising_edge(clk_a) then
+      q <= from_b;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        check_diagnostics(
+            find_clock_domain_crossings(&root),
+            vec![
+                Diagnostic::warning(
+                    code.s1("clk_b, from_a").s1("from_a"),
+                    "Signal 'from_a' from the clock domain of 'clk_a' is read in the clock domain of 'clk_b' without synchronization",
+                ),
+                Diagnostic::warning(
+                    code.s1("q <= from_b").s1("from_b"),
+                    "Signal 'from_b' from the clock domain of 'clk_b' is read in the clock domain of 'clk_a' without synchronization",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn synchronizer_entity_in_hierarchy() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity sync is
+  port (
+    clk : in std_logic;
+    d : in std_logic;
+    q : out std_logic
+  );
+end entity;
+
+architecture a of sync is
+  signal meta : std_logic;
+begin
+  process(clk)
+  begin
+    if rising_edge(clk) then
+      meta <= d;
+      q <= meta;
+    end if;
+  end process;
+end architecture;
+
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity top is
+  port (
+    clk_a, clk_b : in std_logic;
+    d : in std_logic;
+    q : out std_logic
+  );
+end entity;
+
+architecture a of top is
+  signal reg_a, synced : std_logic;
+begin
+  process(clk_a)
+  begin
+    if rising_edge(clk_a) then
+      reg_a <= d;
+    end if;
+  end process;
+
+  inst: entity work.sync
+    port map (
+      clk => clk_b,
+      d => reg_a,
+      q => synced
+    );
+
+  process(clk_b)
+  begin
+    if rising_edge(clk_b) then
+      q <= synced;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        check_no_diagnostics(&find_clock_domain_crossings(&root));
+    }
+}
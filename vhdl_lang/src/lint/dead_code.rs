@@ -11,8 +11,8 @@ use crate::ast::search::Search;
 use crate::ast::search::SearchState;
 use crate::ast::search::Searcher;
 use crate::ast::UnitId;
-use crate::data::DiagnosticHandler;
 use crate::data::Symbol;
+use crate::data::{DiagnosticCode, DiagnosticHandler};
 use crate::named_entity::{HasEntityId, Reference, Related};
 use crate::syntax::TokenAccess;
 use crate::AnyEntKind;
@@ -233,10 +233,13 @@ impl UnusedDeclarationsLinter {
                     find_unused_declarations(root, library, unit.primary_name())
                         .into_iter()
                         .filter_map(|ent| {
-                            Some(Diagnostic::warning(
-                                ent.decl_pos()?,
-                                format!("Unused declaration of {}", ent.describe()),
-                            ))
+                            Some(
+                                Diagnostic::warning(
+                                    ent.decl_pos()?,
+                                    format!("Unused declaration of {}", ent.describe()),
+                                )
+                                .with_code(DiagnosticCode::Unused),
+                            )
                         })
                         .collect_vec()
                 });
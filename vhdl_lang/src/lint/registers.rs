@@ -0,0 +1,924 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Register and reset inference
+//!
+//! Registers are the signals assigned under a clock edge condition of a process.
+//! A reset is recognized as a branch that only assigns values that do not depend on other signals.
+//! The branch is an asynchronous reset when it precedes the clock edge condition and a
+//! synchronous reset when it is the first branch inside of it.
+//!
+//! A synchronous reset cannot be told apart from a load or an enable by the code alone,
+//! so its signal must also be named like a reset or be used as an asynchronous reset.
+
+use super::clock_domain::{
+    clock_edge, expression_signal, signal_reads, target_signals, Clock, ClockEdge,
+};
+use crate::analysis::DesignRoot;
+use crate::ast::*;
+use crate::data::Symbol;
+use crate::syntax::TokenAccess;
+use crate::Config;
+use crate::Diagnostic;
+use crate::EntityId;
+use crate::SrcPos;
+use fnv::FnvHashMap;
+use fnv::FnvHashSet;
+use serde_json::json;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ResetKind {
+    Synchronous,
+    Asynchronous,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct InferredReset {
+    pub signal: String,
+    pub kind: ResetKind,
+    /// The value assigned when the reset is active
+    pub value: String,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct InferredRegister {
+    pub signal: String,
+    /// The first assignment of the register
+    pub pos: SrcPos,
+    pub clock: String,
+    pub edge: ClockEdge,
+    pub reset: Option<InferredReset>,
+}
+
+/// The registers inferred from the processes of an architecture
+#[derive(PartialEq, Debug, Clone)]
+pub struct ArchitectureRegisters {
+    pub library_name: String,
+    pub entity_name: String,
+    pub architecture_name: String,
+    pub pos: SrcPos,
+    pub registers: Vec<InferredRegister>,
+    /// Registers without reset and inconsistent reset styles
+    pub warnings: Vec<Diagnostic>,
+}
+
+fn pos_to_json(pos: &SrcPos) -> serde_json::Value {
+    json!({
+        "file": pos.file_name().to_string_lossy(),
+        "line": pos.start().line + 1,
+        "column": pos.start().character + 1,
+    })
+}
+
+impl ClockEdge {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ClockEdge::Rising => "rising",
+            ClockEdge::Falling => "falling",
+        }
+    }
+}
+
+impl ResetKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ResetKind::Synchronous => "synchronous",
+            ResetKind::Asynchronous => "asynchronous",
+        }
+    }
+}
+
+impl ArchitectureRegisters {
+    pub fn to_json(&self) -> serde_json::Value {
+        let registers: Vec<_> = self
+            .registers
+            .iter()
+            .map(|register| {
+                json!({
+                    "signal": register.signal,
+                    "location": pos_to_json(&register.pos),
+                    "clock": register.clock,
+                    "edge": register.edge.as_str(),
+                    "reset": register.reset.as_ref().map(|reset| json!({
+                        "signal": reset.signal,
+                        "kind": reset.kind.as_str(),
+                        "value": reset.value,
+                    })),
+                })
+            })
+            .collect();
+
+        let warnings: Vec<_> = self
+            .warnings
+            .iter()
+            .map(|warning| {
+                json!({
+                    "message": warning.message,
+                    "location": pos_to_json(&warning.pos),
+                })
+            })
+            .collect();
+
+        json!({
+            "library": self.library_name,
+            "entity": self.entity_name,
+            "architecture": self.architecture_name,
+            "location": pos_to_json(&self.pos),
+            "registers": registers,
+            "warnings": warnings,
+        })
+    }
+}
+
+/// `rst = '1'`, `rst = '0'`, `rst` or `not rst`
+fn reset_signal(root: &DesignRoot, expr: &Expression) -> Option<EntityId> {
+    match expr {
+        Expression::Binary(op, lhs, rhs) if op.item.item == Operator::EQ => {
+            match (&lhs.item, &rhs.item) {
+                (Expression::Literal(Literal::Character(b'0' | b'1')), other)
+                | (other, Expression::Literal(Literal::Character(b'0' | b'1'))) => {
+                    expression_signal(root, other)
+                }
+                _ => None,
+            }
+        }
+        Expression::Unary(op, expr) if op.item.item == Operator::Not => {
+            expression_signal(root, &expr.item)
+        }
+        _ => expression_signal(root, expr),
+    }
+}
+
+/// The names of the signals recognized as synchronous resets when they are not configured
+const DEFAULT_RESET_SIGNALS: [&str; 15] = [
+    "rst",
+    "rstn",
+    "rst_*",
+    "*_rst",
+    "*_rstn",
+    "*_rst_*",
+    "reset",
+    "resetn",
+    "reset_*",
+    "*_reset",
+    "*_resetn",
+    "*_reset_*",
+    "srst*",
+    "arst*",
+    "nrst*",
+];
+
+/// The signals that a synchronous reset may be controlled by
+struct ResetSignals {
+    patterns: Vec<glob::Pattern>,
+    asynchronous: FnvHashSet<EntityId>,
+}
+
+impl ResetSignals {
+    fn new(config: &Config) -> ResetSignals {
+        let patterns = match config.reset_signals() {
+            Some(patterns) => patterns.iter().map(String::as_str).collect(),
+            None => DEFAULT_RESET_SIGNALS.to_vec(),
+        };
+        ResetSignals {
+            // Invalid patterns are rejected when parsing the configuration
+            patterns: patterns
+                .into_iter()
+                .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+                .collect(),
+            asynchronous: FnvHashSet::default(),
+        }
+    }
+
+    fn contains(&self, root: &DesignRoot, signal: EntityId) -> bool {
+        if self.asynchronous.contains(&signal) {
+            return true;
+        }
+        let name = root.get_ent(signal).designator().to_string();
+        let options = glob::MatchOptions {
+            case_sensitive: false,
+            ..glob::MatchOptions::default()
+        };
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.matches_with(&name, options))
+    }
+}
+
+/// A branch of an if statement that only assigns reset values
+struct ResetBranch {
+    signal: EntityId,
+    kind: ResetKind,
+    values: FnvHashMap<EntityId, String>,
+}
+
+struct RegisterInference<'a> {
+    root: &'a DesignRoot,
+    ctx: &'a dyn TokenAccess,
+    reset_signals: &'a ResetSignals,
+    registers: Vec<InferredRegister>,
+    /// The signals of the asynchronous resets of the registers
+    asynchronous_resets: FnvHashSet<EntityId>,
+    seen: FnvHashSet<EntityId>,
+}
+
+impl<'a> RegisterInference<'a> {
+    fn reset_branch(
+        &self,
+        condition: &Expression,
+        statements: &[LabeledSequentialStatement],
+        kind: ResetKind,
+    ) -> Option<ResetBranch> {
+        let signal = reset_signal(self.root, condition)?;
+        let mut values = FnvHashMap::default();
+
+        for statement in statements.iter() {
+            match statement.statement.item {
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    let AssignmentRightHand::Simple(Waveform::Elements(ref elems)) = assign.rhs
+                    else {
+                        return None;
+                    };
+                    let [elem] = elems.as_slice() else {
+                        return None;
+                    };
+                    if !signal_reads(self.root, self.ctx, &elem.value).is_empty() {
+                        return None;
+                    }
+                    for target in target_signals(self.root, &assign.target.item) {
+                        values.insert(target, elem.value.item.to_string());
+                    }
+                }
+                SequentialStatement::Null => {}
+                _ => return None,
+            }
+        }
+
+        if values.is_empty() {
+            None
+        } else {
+            Some(ResetBranch {
+                signal,
+                kind,
+                values,
+            })
+        }
+    }
+
+    fn concurrent(&mut self, statements: &[LabeledConcurrentStatement]) {
+        for statement in statements.iter() {
+            match statement.statement.item {
+                ConcurrentStatement::Process(ref process) => self.process(process),
+                ConcurrentStatement::Block(ref block) => self.concurrent(&block.statements),
+                ConcurrentStatement::ForGenerate(ref gen) => self.concurrent(&gen.body.statements),
+                ConcurrentStatement::IfGenerate(ref gen) => {
+                    for cond in gen.conds.conditionals.iter() {
+                        self.concurrent(&cond.item.statements);
+                    }
+                    if let Some(ref body) = gen.conds.else_item {
+                        self.concurrent(&body.statements);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(ref gen) => {
+                    for alternative in gen.sels.alternatives.iter() {
+                        self.concurrent(&alternative.item.statements);
+                    }
+                }
+                ConcurrentStatement::ProcedureCall(..)
+                | ConcurrentStatement::Assert(..)
+                | ConcurrentStatement::Psl(..)
+                | ConcurrentStatement::Assignment(..)
+                | ConcurrentStatement::Instance(..) => {}
+            }
+        }
+    }
+
+    fn process(&mut self, process: &ProcessStatement) {
+        // A process starting with `wait until rising_edge(clk);` is clocked in its entirety
+        if let Some((first, rest)) = process.statements.split_first() {
+            if let SequentialStatement::Wait(ref wait) = first.statement.item {
+                if let Some(ref cond) = wait.condition_clause {
+                    if let Some(clock) = clock_edge(self.root, &cond.item) {
+                        self.clocked(rest, clock, &mut Vec::new());
+                        return;
+                    }
+                }
+            }
+        }
+
+        self.unclocked(&process.statements);
+    }
+
+    fn unclocked(&mut self, statements: &[LabeledSequentialStatement]) {
+        for statement in statements.iter() {
+            match statement.statement.item {
+                SequentialStatement::If(ref ifstmt) => {
+                    let mut resets = Vec::new();
+                    for cond in ifstmt.conds.conditionals.iter() {
+                        if let Some(clock) = clock_edge(self.root, &cond.condition.item) {
+                            self.clocked(&cond.item, clock, &mut resets);
+                        } else if let Some(reset) = self.reset_branch(
+                            &cond.condition.item,
+                            &cond.item,
+                            ResetKind::Asynchronous,
+                        ) {
+                            resets.push(reset);
+                        } else {
+                            self.unclocked(&cond.item);
+                        }
+                    }
+                    if let Some(ref item) = ifstmt.conds.else_item {
+                        self.unclocked(item);
+                    }
+                }
+                SequentialStatement::Case(ref case) => {
+                    for alternative in case.alternatives.iter() {
+                        self.unclocked(&alternative.item);
+                    }
+                }
+                SequentialStatement::Loop(ref lp) => self.unclocked(&lp.statements),
+                SequentialStatement::SequentialBlock(ref block) => {
+                    self.unclocked(&block.statements)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn clocked(
+        &mut self,
+        statements: &[LabeledSequentialStatement],
+        clock: Clock,
+        resets: &mut Vec<ResetBranch>,
+    ) {
+        for statement in statements.iter() {
+            match statement.statement.item {
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    for target in target_signals(self.root, &assign.target.item) {
+                        self.register(target, &assign.target.pos, clock, resets);
+                    }
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    let reset = ifstmt
+                        .conds
+                        .conditionals
+                        .first()
+                        .and_then(|first| {
+                            self.reset_branch(
+                                &first.condition.item,
+                                &first.item,
+                                ResetKind::Synchronous,
+                            )
+                        })
+                        .filter(|reset| self.reset_signals.contains(self.root, reset.signal));
+                    let has_reset = reset.is_some();
+                    resets.extend(reset);
+
+                    for cond in ifstmt.conds.conditionals.iter() {
+                        self.clocked(&cond.item, clock, resets);
+                    }
+                    if let Some(ref item) = ifstmt.conds.else_item {
+                        self.clocked(item, clock, resets);
+                    }
+
+                    if has_reset {
+                        resets.pop();
+                    }
+                }
+                SequentialStatement::Case(ref case) => {
+                    for alternative in case.alternatives.iter() {
+                        self.clocked(&alternative.item, clock, resets);
+                    }
+                }
+                SequentialStatement::Loop(ref lp) => self.clocked(&lp.statements, clock, resets),
+                SequentialStatement::SequentialBlock(ref block) => {
+                    self.clocked(&block.statements, clock, resets)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn register(&mut self, signal: EntityId, pos: &SrcPos, clock: Clock, resets: &[ResetBranch]) {
+        if !self.seen.insert(signal) {
+            return;
+        }
+
+        let reset = resets.iter().rev().find_map(|reset| {
+            if reset.kind == ResetKind::Asynchronous {
+                self.asynchronous_resets.insert(reset.signal);
+            }
+            reset.values.get(&signal).map(|value| InferredReset {
+                signal: self.root.get_ent(reset.signal).designator().to_string(),
+                kind: reset.kind,
+                value: value.clone(),
+            })
+        });
+
+        self.registers.push(InferredRegister {
+            signal: self.root.get_ent(signal).designator().to_string(),
+            pos: pos.clone(),
+            clock: self.root.get_ent(clock.signal).designator().to_string(),
+            edge: clock.edge,
+            reset,
+        });
+    }
+}
+
+fn check_reset_styles(arch: &ArchitectureRegisters) -> Vec<Diagnostic> {
+    let mut warnings = Vec::new();
+
+    for register in arch.registers.iter() {
+        if register.reset.is_none() {
+            warnings.push(Diagnostic::warning(
+                &register.pos,
+                format!("Register '{}' has no reset", register.signal),
+            ));
+        }
+    }
+
+    let with_kind = |kind| {
+        arch.registers
+            .iter()
+            .find(|register| matches!(register.reset, Some(ref reset) if reset.kind == kind))
+    };
+
+    if let (Some(sync), Some(asynchronous)) = (
+        with_kind(ResetKind::Synchronous),
+        with_kind(ResetKind::Asynchronous),
+    ) {
+        warnings.push(
+            Diagnostic::warning(
+                &arch.pos,
+                format!(
+                    "Architecture '{}' mixes synchronous and asynchronous resets",
+                    arch.architecture_name
+                ),
+            )
+            .related(
+                &sync.pos,
+                format!("Register '{}' has a synchronous reset", sync.signal),
+            )
+            .related(
+                &asynchronous.pos,
+                format!(
+                    "Register '{}' has an asynchronous reset",
+                    asynchronous.signal
+                ),
+            ),
+        );
+    }
+
+    warnings
+}
+
+fn infer_architecture_registers(
+    root: &DesignRoot,
+    reset_signals: &ResetSignals,
+    library_name: &Symbol,
+    ctx: &dyn TokenAccess,
+    arch: &ArchitectureBody,
+) -> (ArchitectureRegisters, FnvHashSet<EntityId>) {
+    let mut inference = RegisterInference {
+        root,
+        ctx,
+        reset_signals,
+        registers: Vec::new(),
+        asynchronous_resets: FnvHashSet::default(),
+        seen: FnvHashSet::default(),
+    };
+    inference.concurrent(&arch.statements);
+
+    let mut result = ArchitectureRegisters {
+        library_name: library_name.name_utf8(),
+        entity_name: arch.entity_name.item.item.name_utf8(),
+        architecture_name: arch.ident.tree.item.name_utf8(),
+        pos: arch.ident.tree.pos.clone(),
+        registers: inference.registers,
+        warnings: Vec::new(),
+    };
+    result.warnings = check_reset_styles(&result);
+    (result, inference.asynchronous_resets)
+}
+
+/// Infer the registers of all architectures in libraries that are not third party
+pub(crate) fn infer_registers(root: &DesignRoot, config: &Config) -> Vec<ArchitectureRegisters> {
+    let mut architectures = Vec::new();
+    for library in root.libraries() {
+        if let Some(library_config) = config.get_library(&library.name().name_utf8()) {
+            if library_config.is_third_party {
+                continue;
+            }
+        }
+
+        for unit in library.primary_units() {
+            for secondary in library.secondary_units(unit.name()) {
+                let Some(guard) = secondary.unit.get() else {
+                    continue;
+                };
+                if matches!(
+                    guard.data(),
+                    AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(_))
+                ) {
+                    architectures.push((library.name(), secondary, guard));
+                }
+            }
+        }
+    }
+
+    let infer_all = |reset_signals: &ResetSignals| -> Vec<_> {
+        architectures
+            .iter()
+            .map(|(library_name, unit, guard)| {
+                let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) = guard.data()
+                else {
+                    unreachable!("Only architectures are collected");
+                };
+                infer_architecture_registers(root, reset_signals, library_name, &unit.tokens, arch)
+            })
+            .collect()
+    };
+
+    // Signals used as asynchronous resets are also recognized as synchronous resets
+    let mut reset_signals = ResetSignals::new(config);
+    reset_signals.asynchronous = infer_all(&reset_signals)
+        .into_iter()
+        .flat_map(|(_, asynchronous)| asynchronous)
+        .collect();
+    let mut result: Vec<_> = infer_all(&reset_signals)
+        .into_iter()
+        .map(|(registers, _)| registers)
+        .collect();
+
+    result.sort_by(|a, b| {
+        (&a.library_name, &a.entity_name, &a.architecture_name).cmp(&(
+            &b.library_name,
+            &b.entity_name,
+            &b.architecture_name,
+        ))
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_no_diagnostics;
+    use crate::syntax::test::Code;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+
+    fn infer(builder: &LibraryBuilder) -> Vec<ArchitectureRegisters> {
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        infer_registers(&root, &Config::default())
+    }
+
+    fn register(
+        code: &Code,
+        signal: &str,
+        occurence: usize,
+        clock: &str,
+        edge: ClockEdge,
+        reset: Option<(&str, ResetKind, &str)>,
+    ) -> InferredRegister {
+        InferredRegister {
+            signal: signal.to_owned(),
+            pos: code.s(signal, occurence).pos(),
+            clock: clock.to_owned(),
+            edge,
+            reset: reset.map(|(signal, kind, value)| InferredReset {
+                signal: signal.to_owned(),
+                kind,
+                value: value.to_owned(),
+            }),
+        }
+    }
+
+    #[test]
+    fn infers_registers_and_resets() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (
+    clk, rst_n, srst : in std_logic;
+    d : in std_logic_vector(7 downto 0)
+  );
+end entity;
+
+architecture a of ent is
+  signal areg, sreg : std_logic_vector(7 downto 0);
+begin
+  process(clk, rst_n)
+  begin
+    if rst_n = '0' then
+      areg <= (others => '0');
+    elsif rising_edge(clk) then
+      areg <= d;
+    end if;
+  end process;
+
+  process
+  begin
+    wait until falling_edge(clk);
+    if srst then
+      sreg <= x\"55\";
+    else
+      sreg <= d;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        let report = infer(&builder);
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].architecture_name, "a");
+        assert_eq!(
+            report[0].registers,
+            vec![
+                register(
+                    &code,
+                    "areg",
+                    3,
+                    "clk",
+                    ClockEdge::Rising,
+                    Some(("rst_n", ResetKind::Asynchronous, "(others => '0')"))
+                ),
+                register(
+                    &code,
+                    "sreg",
+                    2,
+                    "clk",
+                    ClockEdge::Falling,
+                    Some(("srst", ResetKind::Synchronous, "x\"55\""))
+                ),
+            ]
+        );
+        assert_eq!(
+            report[0]
+                .warnings
+                .iter()
+                .map(|warning| warning.message.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Architecture 'a' mixes synchronous and asynchronous resets"]
+        );
+    }
+
+    #[test]
+    fn registers_without_reset() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (
+    clk, en : in std_logic;
+    d : in std_logic
+  );
+end entity;
+
+architecture a of ent is
+  signal q : std_logic;
+begin
+  process(clk)
+  begin
+    if clk'event and clk = '1' then
+      if en = '1' then
+        q <= d;
+      end if;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        let report = infer(&builder);
+        assert_eq!(
+            report[0].registers,
+            vec![register(&code, "q", 2, "clk", ClockEdge::Rising, None)]
+        );
+        assert_eq!(
+            report[0].warnings,
+            vec![Diagnostic::warning(
+                code.s("q", 2),
+                "Register 'q' has no reset"
+            )]
+        );
+    }
+
+    #[test]
+    fn enable_and_load_are_not_synchronous_resets() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (
+    clk, load, start : in std_logic;
+    d : in std_logic_vector(7 downto 0)
+  );
+end entity;
+
+architecture a of ent is
+  signal q : std_logic_vector(7 downto 0);
+  signal busy : std_logic;
+begin
+  process(clk)
+  begin
+    if rising_edge(clk) then
+      if load = '1' then
+        q <= x\"00\";
+      else
+        q <= d;
+      end if;
+    end if;
+  end process;
+
+  process(clk)
+  begin
+    if rising_edge(clk) then
+      if start = '1' then
+        busy <= '1';
+      end if;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        let report = infer(&builder);
+        assert_eq!(
+            report[0].registers,
+            vec![
+                register(&code, "q", 2, "clk", ClockEdge::Rising, None),
+                register(&code, "busy", 2, "clk", ClockEdge::Rising, None),
+            ]
+        );
+    }
+
+    const CLEAR_CODE: &str = "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (clk, clear, d : in std_logic);
+end entity;
+
+architecture a of ent is
+  signal q : std_logic;
+begin
+  process(clk)
+  begin
+    if rising_edge(clk) then
+      if clear = '1' then
+        q <= '0';
+      else
+        q <= d;
+      end if;
+    end if;
+  end process;
+end architecture;";
+
+    #[test]
+    fn configured_synchronous_reset_signals() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code("libname", CLEAR_CODE);
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let expected = register(
+            &code,
+            "q",
+            2,
+            "clk",
+            ClockEdge::Rising,
+            Some(("clear", ResetKind::Synchronous, "'0'")),
+        );
+        let config =
+            Config::from_str("reset_signals = ['CLEAR']\n[libraries]", Path::new("")).unwrap();
+        assert_eq!(infer_registers(&root, &config)[0].registers, vec![expected]);
+
+        // The configured patterns replace the default ones
+        let config =
+            Config::from_str("reset_signals = ['clr']\n[libraries]", Path::new("")).unwrap();
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code("libname", &CLEAR_CODE.replace("clear", "rst"));
+        let (root, _) = builder.get_analyzed_root();
+        assert_eq!(
+            infer_registers(&root, &config)[0].registers,
+            vec![register(&code, "q", 2, "clk", ClockEdge::Rising, None)]
+        );
+    }
+
+    #[test]
+    fn asynchronous_reset_signals_are_synchronous_resets_elsewhere() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (clk, clear, d : in std_logic);
+end entity;
+
+architecture a of ent is
+  signal areg, sreg : std_logic;
+begin
+  process(clk, clear)
+  begin
+    if clear = '1' then
+      areg <= '0';
+    elsif rising_edge(clk) then
+      areg <= d;
+    end if;
+  end process;
+
+  process(clk)
+  begin
+    if rising_edge(clk) then
+      if clear = '1' then
+        sreg <= '1';
+      else
+        sreg <= d;
+      end if;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        let report = infer(&builder);
+        assert_eq!(
+            report[0].registers[1],
+            register(
+                &code,
+                "sreg",
+                2,
+                "clk",
+                ClockEdge::Rising,
+                Some(("clear", ResetKind::Synchronous, "'1'"))
+            )
+        );
+    }
+
+    #[test]
+    fn report_as_json() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        builder.code(
+            "libname",
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (clk, rst, d : in std_logic);
+end entity;
+
+architecture a of ent is
+  signal q : std_logic;
+begin
+  process(clk, rst)
+  begin
+    if rst = '1' then
+      q <= '0';
+    elsif rising_edge(clk) then
+      q <= d;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        let json = infer(&builder)[0].to_json();
+        assert_eq!(json["library"], "libname");
+        assert_eq!(json["entity"], "ent");
+        assert_eq!(json["registers"][0]["signal"], "q");
+        assert_eq!(json["registers"][0]["clock"], "clk");
+        assert_eq!(json["registers"][0]["edge"], "rising");
+        assert_eq!(json["registers"][0]["reset"]["signal"], "rst");
+        assert_eq!(json["registers"][0]["reset"]["kind"], "asynchronous");
+        assert_eq!(json["registers"][0]["reset"]["value"], "'0'");
+        assert_eq!(json["registers"][0]["location"]["line"], 17);
+        assert_eq!(json["warnings"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,413 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Report constructs that synthesis tools reject in libraries marked as synthesizable
+
+use crate::analysis::DesignRoot;
+use crate::analysis::Library;
+use crate::analysis::LockedUnit;
+use crate::ast::search::FoundDeclaration;
+use crate::ast::search::Search;
+use crate::ast::search::SearchResult;
+use crate::ast::search::SearchState;
+use crate::ast::search::Searcher;
+use crate::ast::*;
+use crate::data::Symbol;
+use crate::data::WithPos;
+use crate::data::{DiagnosticCode, DiagnosticHandler};
+use crate::named_entity::Reference;
+use crate::syntax::TokenAccess;
+use crate::AnyEntKind;
+use crate::Config;
+use crate::Design;
+use crate::Diagnostic;
+use crate::SrcPos;
+use crate::Type;
+use fnv::FnvHashMap;
+
+/// Finds references to signals and variables in an expression
+struct NonConstantSearcher<'a> {
+    root: &'a DesignRoot,
+    found: bool,
+}
+
+impl<'a> Searcher for NonConstantSearcher<'a> {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        _pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            if matches!(
+                self.root.get_ent(id).kind(),
+                AnyEntKind::Object(obj) if obj.class != ObjectClass::Constant
+            ) {
+                self.found = true;
+                return SearchState::Finished(SearchResult::Found);
+            }
+        }
+        SearchState::NotFinished
+    }
+}
+
+struct SynthesisSearcher<'a> {
+    root: &'a DesignRoot,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> SynthesisSearcher<'a> {
+    fn not_synthesizable(&mut self, pos: &SrcPos, what: &str) {
+        self.diagnostics.push(Diagnostic::warning(
+            pos,
+            format!("{what} is not synthesizable"),
+        ));
+    }
+
+    fn is_non_constant(&self, ctx: &dyn TokenAccess, expr: &WithPos<Expression>) -> bool {
+        let mut searcher = NonConstantSearcher {
+            root: self.root,
+            found: false,
+        };
+        let _ = expr.search(ctx, &mut searcher);
+        searcher.found
+    }
+
+    fn assertion(&mut self, ctx: &dyn TokenAccess, assert: &AssertStatement) {
+        if self.is_non_constant(ctx, &assert.condition) {
+            self.not_synthesizable(
+                &assert.condition.pos,
+                "Assertion with a non-constant condition",
+            );
+        }
+    }
+
+    fn delays(&mut self, rhs: &AssignmentRightHand<Waveform>) {
+        let mut waveforms = Vec::new();
+        match rhs {
+            AssignmentRightHand::Simple(waveform) => waveforms.push(waveform),
+            AssignmentRightHand::Conditional(conds) => {
+                waveforms.extend(conds.conditionals.iter().map(|cond| &cond.item));
+                waveforms.extend(conds.else_item.iter());
+            }
+            AssignmentRightHand::Selected(selection) => {
+                waveforms.extend(selection.alternatives.iter().map(|alt| &alt.item));
+            }
+        }
+
+        for waveform in waveforms {
+            if let Waveform::Elements(elems) = waveform {
+                for elem in elems.iter() {
+                    if let Some(ref after) = elem.after {
+                        self.not_synthesizable(&after.pos, "Delay using 'after'");
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_real_signal(&self, ident: &WithDecl<Ident>) -> bool {
+        let Some(id) = ident.decl.get() else {
+            return false;
+        };
+        if let AnyEntKind::Object(obj) = self.root.get_ent(id).kind() {
+            obj.class == ObjectClass::Signal && matches!(obj.subtype.base().kind(), Type::Real)
+        } else {
+            false
+        }
+    }
+}
+
+impl<'a> Searcher for SynthesisSearcher<'a> {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            let ent = self.root.get_ent(id);
+            if matches!(ent.kind(), AnyEntKind::Design(Design::Package(..)))
+                && ent.designator().to_string() == "textio"
+                && ent.library_name().map(|lib| lib.name_utf8()).as_deref() == Some("std")
+            {
+                self.not_synthesizable(pos, "Use of std.textio");
+            }
+        }
+        SearchState::NotFinished
+    }
+
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        match decl {
+            FoundDeclaration::Object(obj) => {
+                if obj.class == ObjectClass::SharedVariable {
+                    self.not_synthesizable(&obj.ident.tree.pos, "Shared variable");
+                } else if self.is_real_signal(&obj.ident) {
+                    self.not_synthesizable(&obj.ident.tree.pos, "Signal of type real");
+                }
+            }
+            FoundDeclaration::InterfaceObject(obj) if self.is_real_signal(&obj.ident) => {
+                self.not_synthesizable(&obj.ident.tree.pos, "Signal of type real");
+            }
+            FoundDeclaration::File(file) => {
+                self.not_synthesizable(&file.ident.tree.pos, "File declaration");
+            }
+            FoundDeclaration::InterfaceFile(file) => {
+                self.not_synthesizable(&file.ident.tree.pos, "File declaration");
+            }
+            FoundDeclaration::Type(typ) => {
+                if let TypeDefinition::Access(_) = typ.def {
+                    self.not_synthesizable(&typ.ident.tree.pos, "Access type");
+                }
+            }
+            FoundDeclaration::SequentialStatement(stmt) => match stmt.statement.item {
+                SequentialStatement::Wait(WaitStatement {
+                    timeout_clause: Some(ref timeout),
+                    ..
+                }) => {
+                    self.not_synthesizable(&timeout.pos, "Wait statement with timeout");
+                }
+                SequentialStatement::SignalAssignment(ref assign) => self.delays(&assign.rhs),
+                SequentialStatement::Assert(ref assert) => self.assertion(ctx, assert),
+                _ => {}
+            },
+            FoundDeclaration::ConcurrentStatement(stmt) => match stmt.statement.item {
+                ConcurrentStatement::Assignment(ref assign) => self.delays(&assign.rhs),
+                ConcurrentStatement::Assert(ref assert) => self.assertion(ctx, &assert.statement),
+                _ => {}
+            },
+            _ => {}
+        }
+        SearchState::NotFinished
+    }
+}
+
+fn search_unit(unit: &LockedUnit, searcher: &mut impl Searcher) {
+    let _ = unit.unit.write().search(&unit.tokens, searcher);
+}
+
+fn find_non_synthesizable(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut searcher = SynthesisSearcher {
+        root,
+        diagnostics: Vec::new(),
+    };
+
+    if let Some(unit) = lib.primary_unit(primary_unit_name) {
+        search_unit(unit, &mut searcher);
+    }
+
+    for unit in lib.secondary_units(primary_unit_name) {
+        search_unit(unit, &mut searcher);
+    }
+
+    searcher.diagnostics
+}
+
+/// Use a struct to keep state of units that do not need to be re-scanned
+#[derive(Default)]
+pub(crate) struct SynthesisLinter {
+    // library name, primary name
+    diagnostics: FnvHashMap<(Symbol, Symbol), Vec<Diagnostic>>,
+}
+
+impl SynthesisLinter {
+    pub fn lint(
+        &mut self,
+        root: &DesignRoot,
+        config: &Config,
+        analyzed_units: &[UnitId],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let is_synthesizable = |library_name: &Symbol| {
+            config
+                .get_library(&library_name.name_utf8())
+                .is_some_and(|library_config| library_config.is_synthesizable)
+        };
+
+        // Prune diagnostics that need to be re-computed
+        for unit in analyzed_units {
+            let key = (unit.library_name().clone(), unit.primary_name().clone());
+            self.diagnostics.remove(&key);
+        }
+
+        // Prune diagnostics for units that no longer exist or are no longer synthesizable
+        self.diagnostics.retain(|(library_name, primary_name), _| {
+            if let Some(library) = root.get_lib(library_name) {
+                if library.primary_unit(primary_name).is_some() {
+                    return is_synthesizable(library_name);
+                }
+            }
+            false
+        });
+
+        for unit in analyzed_units {
+            if !is_synthesizable(unit.library_name()) {
+                continue;
+            }
+
+            let key = (unit.library_name().clone(), unit.primary_name().clone());
+            if let Some(library) = root.get_lib(unit.library_name()) {
+                self.diagnostics.entry(key).or_insert_with(|| {
+                    find_non_synthesizable(root, library, unit.primary_name())
+                        .into_iter()
+                        .map(|diagnostic| diagnostic.with_code(DiagnosticCode::Synthesis))
+                        .collect()
+                });
+            }
+        }
+
+        for unit_diagnostics in self.diagnostics.values() {
+            diagnostics.append(unit_diagnostics.iter().cloned());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_diagnostics;
+    use crate::syntax::test::check_no_diagnostics;
+    use std::path::Path;
+
+    #[test]
+    fn non_synthesizable_constructs() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+use std.textio.all;
+
+entity ent is
+  port (
+    r : in real;
+    clk : in bit
+  );
+end entity;
+
+architecture a of ent is
+  type ptr_t is access integer;
+  type prot_t is protected
+  end protected;
+  type prot_t is protected body
+  end protected body;
+  shared variable sv : prot_t;
+  signal rs : real;
+  signal s, t : bit;
+  constant c : boolean := true;
+begin
+  s <= clk after 1 ns;
+  assert c;
+  assert s = '1';
+
+  process
+    file f : text;
+  begin
+    wait for 10 ns;
+    t <= s;
+    wait on s;
+  end process;
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        check_diagnostics(
+            find_non_synthesizable(&root, lib, &root.symbol_utf8("ent")),
+            vec![
+                Diagnostic::warning(code.s1("textio"), "Use of std.textio is not synthesizable"),
+                Diagnostic::warning(
+                    code.s1("r :").s1("r"),
+                    "Signal of type real is not synthesizable",
+                ),
+                Diagnostic::warning(code.s1("ptr_t"), "Access type is not synthesizable"),
+                Diagnostic::warning(code.s1("sv"), "Shared variable is not synthesizable"),
+                Diagnostic::warning(code.s1("rs"), "Signal of type real is not synthesizable"),
+                Diagnostic::warning(code.s1("1 ns"), "Delay using 'after' is not synthesizable"),
+                Diagnostic::warning(
+                    code.s1("s = '1'"),
+                    "Assertion with a non-constant condition is not synthesizable",
+                ),
+                Diagnostic::warning(
+                    code.s1("f :").s1("f"),
+                    "File declaration is not synthesizable",
+                ),
+                Diagnostic::warning(
+                    code.s1("10 ns"),
+                    "Wait statement with timeout is not synthesizable",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn only_libraries_marked_as_synthesizable_are_linted() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "rtl",
+            "
+package pkg is
+  type prot_t is protected
+  end protected;
+end package;
+
+package body pkg is
+  type prot_t is protected body
+  end protected body;
+  shared variable sv : prot_t;
+end package body;",
+        );
+        builder.code(
+            "tb",
+            "
+package pkg is
+  type prot_t is protected
+  end protected;
+end package;
+
+package body pkg is
+  type prot_t is protected body
+  end protected body;
+  shared variable sv : prot_t;
+end package body;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let config = Config::from_str(
+            "
+[libraries]
+rtl.files = []
+rtl.synthesizable = true
+tb.files = []
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        let units: Vec<_> = root
+            .libraries()
+            .flat_map(|lib| lib.primary_units().map(|unit| unit.unit_id().clone()))
+            .collect();
+
+        let mut diagnostics = Vec::new();
+        SynthesisLinter::default().lint(&root, &config, &units, &mut diagnostics);
+        check_diagnostics(
+            diagnostics,
+            vec![
+                Diagnostic::warning(code.s1("sv"), "Shared variable is not synthesizable")
+                    .with_code(DiagnosticCode::Synthesis),
+            ],
+        );
+    }
+}
@@ -9,15 +9,24 @@
 // Track here: https://github.com/rust-lang/rust-clippy/issues/1981
 #![allow(clippy::ptr_arg)]
 
-use clap::Parser;
-use std::path::Path;
-use std::time::SystemTime;
-use vhdl_lang::{Config, Diagnostic, MessagePrinter, NullMessages, Project, Severity};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use vhdl_lang::{
+    diagnostics_to_json, diagnostics_to_junit, diagnostics_to_sarif, Baseline, CompileOrderFile,
+    CompileOrderScope, Config, Diagnostic, Message, MessageHandler, MessagePrinter, MessageType,
+    NullMessages, Project, Severity, Source,
+};
 
 /// Run vhdl analysis
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The number of threads to use. By default the maximum is selected based on process cores
     #[arg(short = 'p', long)]
     num_threads: Option<usize>,
@@ -34,9 +43,33 @@ struct Args {
     #[arg(long, default_value_t = false)]
     no_hint: bool,
 
+    /// The format diagnostics are printed in
+    #[arg(long, value_enum, default_value_t = DiagnosticFormat::Text)]
+    format: DiagnosticFormat,
+
+    /// Exit with status 1 if there is a diagnostic of this severity or above
+    #[arg(long, value_enum)]
+    fail_on: Option<FailOn>,
+
+    /// Only report diagnostics that are not part of this baseline file
+    #[arg(long)]
+    baseline: Option<String>,
+
+    /// Record the current diagnostics in this baseline file, instead of reporting them
+    #[arg(long, conflicts_with = "baseline")]
+    write_baseline: Option<String>,
+
+    /// Report signals read in another clock domain without synchronization
+    #[arg(long, default_value_t = false)]
+    clock_domain_crossings: bool,
+
+    /// Write the registers and resets inferred for every architecture as JSON to this file
+    #[arg(long)]
+    register_report: Option<String>,
+
     /// Config file in TOML format containing libraries and settings
-    #[arg(short, long)]
-    config: String,
+    #[arg(short, long, required = true)]
+    config: Option<String>,
 
     /// Dump items that are not resolved into an unique reference
     /// This is used for development to test where the language server is blind
@@ -47,28 +80,178 @@ struct Args {
     /// This is used for development to test where the language server is blind
     #[arg(long)]
     count_unresolved: bool,
+
+    /// Keep running and analyse again when the configuration or a source file changes on disk,
+    /// printing the diagnostics that were added and fixed
+    #[arg(long, conflicts_with_all = ["bench", "write_baseline", "format", "register_report"])]
+    watch: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the files of a library, or of everything a top unit depends on, in an order
+    /// they may be analyzed in
+    CompileOrder {
+        /// The library whose files are printed
+        #[arg(long, conflicts_with = "top", required_unless_present = "top")]
+        library: Option<String>,
+
+        /// The top unit given as library.unit, the predefined std and ieee libraries are left out
+        #[arg(long)]
+        top: Option<String>,
+
+        #[arg(long, value_enum, default_value_t = CompileOrderFormat::Text)]
+        format: CompileOrderFormat,
+    },
+
+    /// Print the dependencies between design units as a graph
+    DependencyGraph {
+        /// Only include the units that this unit, given as library.unit, depends on
+        #[arg(long)]
+        root: Option<String>,
+
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+    },
+
+    /// Write the vhdl_ls.toml equivalent to FuseSoC core files and the cores they depend on
+    #[command(name = "import-fusesoc")]
+    Fusesoc {
+        /// The core files of the design
+        #[arg(required = true)]
+        cores: Vec<PathBuf>,
+
+        /// Folder searched for the cores that are depended on, may be given multiple times
+        #[arg(long)]
+        cores_root: Vec<PathBuf>,
+
+        /// The configuration file to write
+        #[arg(short, long, default_value = "vhdl_ls.toml")]
+        output: PathBuf,
+    },
+
+    /// Write the vhdl_ls.toml equivalent to a Vivado .xpr project
+    #[command(name = "import-vivado")]
+    Vivado {
+        project: PathBuf,
+
+        /// The configuration file to write
+        #[arg(short, long, default_value = "vhdl_ls.toml")]
+        output: PathBuf,
+    },
+
+    /// Write the vhdl_ls.toml equivalent to a Quartus .qsf settings file
+    #[command(name = "import-quartus")]
+    Quartus {
+        settings: PathBuf,
+
+        /// The configuration file to write
+        #[arg(short, long, default_value = "vhdl_ls.toml")]
+        output: PathBuf,
+    },
+
+    /// Write the vhdl_ls.toml equivalent to a ModelSim or Questa .do script, modelsim.ini or .mpf project
+    #[command(name = "import-modelsim")]
+    Modelsim {
+        file: PathBuf,
+
+        /// The configuration file to write
+        #[arg(short, long, default_value = "vhdl_ls.toml")]
+        output: PathBuf,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum CompileOrderFormat {
+    /// The library and file name separated by a space on each line
+    Text,
+    Json,
+    /// Appends [list library file] to the compile_order list variable
+    Tcl,
+    /// Sets VHDL_FILES and the target specific VHDL_LIBRARY of each file
+    Make,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum DiagnosticFormat {
+    /// The source code around each diagnostic
+    Text,
+    /// An array of diagnostics that is also a GitLab code quality report
+    Json,
+    /// A SARIF log as read by GitHub code scanning
+    Sarif,
+    Junit,
+    /// file:line:column: severity: message [rule]
+    Gcc,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum FailOn {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl FailOn {
+    fn severity(&self) -> Severity {
+        match self {
+            FailOn::Error => Severity::Error,
+            FailOn::Warning => Severity::Warning,
+            FailOn::Info => Severity::Info,
+            FailOn::Hint => Severity::Hint,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum GraphFormat {
+    /// Graphviz DOT with a cluster for each library
+    Dot,
+    Json,
 }
 
 fn main() {
     let args = Args::parse();
+    if let Some(command) = args.command {
+        run_command(command, args.config.as_deref());
+        return;
+    }
     rayon::ThreadPoolBuilder::new()
         .num_threads(args.num_threads.unwrap_or(0))
         .build_global()
         .unwrap();
 
-    let mut config = Config::default();
+    // Only the diagnostics are printed to stdout when they are read by other tools
     let mut msg_printer = MessagePrinter::default();
-    config.load_external_config(&mut msg_printer);
-    config.append(
-        &Config::read_file_path(Path::new(&args.config)).expect("Failed to read config file"),
-        &mut msg_printer,
-    );
+    let mut quiet_messages: Vec<Message> = Vec::new();
+    let messages: &mut dyn MessageHandler = if args.format == DiagnosticFormat::Text {
+        &mut msg_printer
+    } else {
+        &mut quiet_messages
+    };
+    let config_file = args.config.expect("Config file is required");
+    let config = load_config(&config_file, messages);
+    let baseline =
+        args.baseline
+            .as_ref()
+            .map(|path| match Baseline::read_file(&absolute_path(path)) {
+                Ok(baseline) => baseline,
+                Err(err) => {
+                    eprintln!("Error: {err}");
+                    std::process::exit(1);
+                }
+            });
+    let file_times = modified_times(&config);
 
     let start = SystemTime::now();
 
     let iterations = if args.bench {
         let iterations = 10;
-        println!("Running {iterations} iterations for benchmarking");
+        print_status(
+            args.format,
+            &format!("Running {iterations} iterations for benchmarking"),
+        );
         for _ in 0..(iterations - 1) {
             let mut project = Project::from_config(config.clone(), &mut NullMessages);
             project.analyse();
@@ -78,15 +261,58 @@ fn main() {
         1
     };
 
-    let mut project = Project::from_config(config, &mut msg_printer);
-    let mut diagnostics = project.analyse();
+    let mut project = Project::from_config(config.clone(), messages);
+    print_to_stderr(quiet_messages);
+    if args.clock_domain_crossings {
+        project.enable_clock_domain_crossing_detection();
+    }
+    let analysed = project.analyse();
     let duration = start.elapsed().unwrap() / iterations;
 
-    if args.no_hint {
-        diagnostics.retain(|diag| diag.severity != Severity::Hint);
+    let no_hint = args.no_hint;
+    let reported = move |project: &Project, mut diagnostics: Vec<Diagnostic>| {
+        if no_hint {
+            diagnostics.retain(|diag| diag.severity != Severity::Hint);
+        }
+        match baseline {
+            Some(ref baseline) => baseline.filter(project, diagnostics),
+            None => diagnostics,
+        }
+    };
+    let mut diagnostics = reported(&project, analysed);
+
+    if let Some(ref path) = args.write_baseline {
+        let file_name = absolute_path(path);
+        let mut baseline = Baseline::new(file_name.parent().unwrap_or(Path::new("")));
+        baseline.record(&project, &diagnostics);
+        if let Err(err) = baseline.write_file(&file_name) {
+            eprintln!("Error: {err}");
+            std::process::exit(1);
+        }
+        eprintln!(
+            "Recorded {} diagnostics in {}",
+            diagnostics.len(),
+            file_name.display()
+        );
+        diagnostics.clear();
     }
 
-    show_diagnostics(&diagnostics);
+    show_diagnostics(&diagnostics, args.format);
+    if args.watch {
+        watch(
+            project,
+            Path::new(&config_file),
+            config,
+            file_times,
+            diagnostics,
+            reported,
+        );
+    }
+    let failed = args.fail_on.is_some_and(|fail_on| {
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity >= fail_on.severity())
+    });
 
     if args.perf || args.bench {
         let mut num_files = 0;
@@ -97,14 +323,33 @@ fn main() {
         }
         let duration_per_line = duration.checked_div(num_lines as u32).unwrap();
 
-        println!("Analyzed {num_files} files with {num_lines} lines of code");
-        println!(
-            "Total time to run was {} ms with an average of {} ns per line",
-            duration.as_millis(),
-            duration_per_line.as_nanos()
+        print_status(
+            args.format,
+            &format!("Analyzed {num_files} files with {num_lines} lines of code"),
+        );
+        print_status(
+            args.format,
+            &format!(
+                "Total time to run was {} ms with an average of {} ns per line",
+                duration.as_millis(),
+                duration_per_line.as_nanos()
+            ),
         );
     }
 
+    if let Some(ref path) = args.register_report {
+        let report: Vec<_> = project
+            .register_report()
+            .iter()
+            .map(|arch| arch.to_json())
+            .collect();
+        let report = serde_json::to_string_pretty(&report).unwrap();
+        if let Err(err) = std::fs::write(path, report) {
+            eprintln!("Error: Could not write register report {path}: {err}");
+            std::process::exit(1);
+        }
+    }
+
     if args.dump_unresolved || args.count_unresolved {
         let (total, unresolved) = project.find_all_unresolved();
 
@@ -120,15 +365,381 @@ fn main() {
     }
 
     // Exit without running Drop on entire allocated AST
-    std::process::exit(0);
+    std::process::exit(if failed { 1 } else { 0 });
+}
+
+fn load_config(file_name: &str, messages: &mut dyn MessageHandler) -> Config {
+    try_load_config(Path::new(file_name), messages).expect("Failed to read config file")
+}
+
+fn try_load_config(file_name: &Path, messages: &mut dyn MessageHandler) -> io::Result<Config> {
+    let mut config = Config::default();
+    config.load_external_config(messages);
+    config.append(&Config::read_file_path(file_name)?, messages);
+    Ok(config)
+}
+
+/// How often files are checked for changes in watch mode
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Analyse again each time the configuration or a file of its libraries is modified.
+/// Only the modified files are parsed again and only the design units that depend on them
+/// are analyzed again.
+/// The file patterns of the libraries are only expanded again when the configuration changes,
+/// which includes the included configuration files and the file lists of the libraries
+fn watch(
+    mut project: Project,
+    config_file: &Path,
+    mut config: Config,
+    mut file_times: BTreeMap<PathBuf, SystemTime>,
+    mut diagnostics: Vec<Diagnostic>,
+    reported: impl Fn(&Project, Vec<Diagnostic>) -> Vec<Diagnostic>,
+) -> ! {
+    let mut messages = MessagePrinter::default();
+    let mut config_times = config_modified_times(config_file, &config);
+    println!("Watching for changes, press Ctrl-C to stop");
+
+    loop {
+        std::thread::sleep(WATCH_INTERVAL);
+
+        let config_changed = config_times
+            .iter()
+            .any(|(file_name, time)| modified_time(file_name) != *time);
+        if config_changed {
+            match try_load_config(config_file, &mut messages) {
+                Ok(new_config) => {
+                    config = new_config;
+                    project.update_config(config.clone(), &mut messages);
+                }
+                Err(err) => eprintln!("Error: Could not read {}: {err}", config_file.display()),
+            }
+            config_times = config_modified_times(config_file, &config);
+        }
+
+        let new_file_times = if config_changed {
+            modified_times(&config)
+        } else {
+            polled_times(&file_times)
+        };
+        let changed_files: Vec<&PathBuf> = new_file_times
+            .iter()
+            .filter(|(file_name, time)| file_times.get(*file_name) != Some(time))
+            .map(|(file_name, _)| file_name)
+            .collect();
+        let files_removed = file_times
+            .keys()
+            .any(|file_name| !new_file_times.contains_key(file_name));
+        if !config_changed && changed_files.is_empty() && !files_removed {
+            continue;
+        }
+
+        if files_removed && !config_changed {
+            // Removes the files from their libraries
+            project.update_config(config.clone(), &mut messages);
+        }
+        for file_name in changed_files.iter() {
+            match Source::from_latin1_file(file_name) {
+                Ok(source) => project.update_source(&source),
+                Err(err) => eprintln!("Error: Could not read {}: {err}", file_name.display()),
+            }
+        }
+        file_times = new_file_times;
+
+        let analysed = project.analyse();
+        let new_diagnostics = reported(&project, analysed);
+        show_diagnostics_delta(&diagnostics, &new_diagnostics);
+        diagnostics = new_diagnostics;
+    }
+}
+
+/// The modification time of the files of all libraries, files that cannot be read are left out
+fn modified_times(config: &Config) -> BTreeMap<PathBuf, SystemTime> {
+    config
+        .iter_libraries()
+        .flat_map(|library| library.file_names(&mut NullMessages))
+        .filter_map(|file_name| Some((modified_time(&file_name)?, file_name)))
+        .map(|(time, file_name)| (file_name, time))
+        .collect()
+}
+
+/// The modification time of the configuration file, the configuration files it includes
+/// and the file lists of its libraries.
+/// Files that cannot be read are kept, such that they are noticed once they are created again
+fn config_modified_times(
+    config_file: &Path,
+    config: &Config,
+) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    std::iter::once(config_file.to_owned())
+        .chain(config.included_files().iter().cloned())
+        .chain(
+            config
+                .iter_libraries()
+                .flat_map(|library| library.file_lists()),
+        )
+        .map(|file_name| {
+            let time = modified_time(&file_name);
+            (file_name, time)
+        })
+        .collect()
+}
+
+/// The current modification time of files that were seen before, files that were removed
+/// are left out
+fn polled_times(file_times: &BTreeMap<PathBuf, SystemTime>) -> BTreeMap<PathBuf, SystemTime> {
+    file_times
+        .keys()
+        .filter_map(|file_name| Some((file_name.clone(), modified_time(file_name)?)))
+        .collect()
+}
+
+fn modified_time(file_name: &Path) -> Option<SystemTime> {
+    std::fs::metadata(file_name)
+        .and_then(|metadata| metadata.modified())
+        .ok()
 }
 
-fn show_diagnostics(diagnostics: &[Diagnostic]) {
-    for diagnostic in diagnostics {
+/// Print the diagnostics that were added in full and those that were fixed as a single line.
+/// A diagnostic that has moved is both fixed and added
+fn show_diagnostics_delta(before: &[Diagnostic], after: &[Diagnostic]) {
+    let mut unmatched = vec![true; before.len()];
+    let mut added = Vec::new();
+    for diagnostic in after {
+        let matching = before
+            .iter()
+            .zip(unmatched.iter_mut())
+            .find(|(other, unmatched)| **unmatched && *other == diagnostic);
+        match matching {
+            Some((_, unmatched)) => *unmatched = false,
+            None => added.push(diagnostic),
+        }
+    }
+    let fixed: Vec<&Diagnostic> = before
+        .iter()
+        .zip(unmatched)
+        .filter_map(|(diagnostic, unmatched)| unmatched.then_some(diagnostic))
+        .collect();
+
+    let root = std::env::current_dir().unwrap_or_default();
+    for diagnostic in added.iter() {
         println!("{}", diagnostic.show());
     }
+    for diagnostic in fixed.iter() {
+        println!("Fixed {}", diagnostic.show_gcc(&root));
+    }
+    println!(
+        "{} new and {} fixed diagnostics, {} in total",
+        added.len(),
+        fixed.len(),
+        after.len()
+    );
+}
+
+fn run_command(command: Command, config_file: Option<&str>) {
+    let mut msg_printer = MessagePrinter::default();
+    match command {
+        Command::CompileOrder {
+            library,
+            top,
+            format,
+        } => {
+            let scope = match (library, top) {
+                (Some(library), _) => CompileOrderScope::Library(library),
+                (None, Some(top)) => {
+                    let (library, unit) = split_unit_name(&top, "top");
+                    CompileOrderScope::Top(library.to_owned(), unit.to_owned())
+                }
+                (None, None) => unreachable!("Ensured by clap"),
+            };
+
+            let project = analyse_quietly(config_file, "compile-order");
+            match project.compile_order(&scope) {
+                Ok(files) => print!("{}", format_compile_order(&files, format)),
+                Err(err) => {
+                    eprintln!("Error: {err}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::DependencyGraph { root, format } => {
+            let root = root.as_deref().map(|root| split_unit_name(root, "root"));
+
+            let project = analyse_quietly(config_file, "dependency-graph");
+            match project.dependency_graph(root) {
+                Ok(graph) => match format {
+                    GraphFormat::Dot => print!("{}", graph.to_dot()),
+                    GraphFormat::Json => {
+                        println!(
+                            "{}",
+                            serde_json::to_string_pretty(&graph.to_json()).unwrap()
+                        )
+                    }
+                },
+                Err(err) => {
+                    eprintln!("Error: {err}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Fusesoc {
+            cores,
+            cores_root,
+            output,
+        } => {
+            let config = Config::from_fusesoc_cores(&cores, &cores_root, &mut msg_printer);
+            write_config(&config, &output);
+        }
+        Command::Vivado { project, output } => {
+            let config = Config::from_vivado_project(&project, &mut msg_printer);
+            write_config(&config, &output);
+        }
+        Command::Quartus { settings, output } => {
+            let config = Config::from_quartus_settings(&settings, &mut msg_printer);
+            write_config(&config, &output);
+        }
+        Command::Modelsim { file, output } => {
+            let config = Config::from_modelsim(&file, &mut msg_printer);
+            write_config(&config, &output);
+        }
+    }
+}
+
+/// Analyse the project of the configuration file for a command whose output is used by other tools,
+/// which is why errors and warnings are printed to stderr
+fn analyse_quietly(config_file: Option<&str>, command: &str) -> Project {
+    let Some(config_file) = config_file else {
+        eprintln!("{command} requires a --config file");
+        std::process::exit(2);
+    };
 
-    if !diagnostics.is_empty() {
-        println!("Found {} diagnostics", diagnostics.len());
+    let mut messages: Vec<Message> = Vec::new();
+    let config = load_config(config_file, &mut messages);
+    let mut project = Project::from_config(config, &mut messages);
+    project.analyse();
+    print_to_stderr(messages);
+    project
+}
+
+fn print_to_stderr(messages: Vec<Message>) {
+    for message in messages {
+        if matches!(
+            message.message_type,
+            MessageType::Error | MessageType::Warning
+        ) {
+            eprintln!("{message}");
+        }
+    }
+}
+
+/// Print a line that is not part of the diagnostics,
+/// which goes to stderr when the diagnostics are read by other tools
+fn print_status(format: DiagnosticFormat, line: &str) {
+    if format == DiagnosticFormat::Text {
+        println!("{line}");
+    } else {
+        eprintln!("{line}");
+    }
+}
+
+/// Split a unit given as library.unit on the command line
+fn split_unit_name<'a>(name: &'a str, argument: &str) -> (&'a str, &'a str) {
+    match name.split_once('.') {
+        Some(names) => names,
+        None => {
+            eprintln!("The {argument} unit must be given as library.unit");
+            std::process::exit(2);
+        }
+    }
+}
+
+fn format_compile_order(files: &[CompileOrderFile], format: CompileOrderFormat) -> String {
+    let mut output = String::new();
+    match format {
+        CompileOrderFormat::Text => {
+            for file in files {
+                output.push_str(&format!("{} {}\n", file.library, file.file_name.display()));
+            }
+        }
+        CompileOrderFormat::Json => {
+            let files: Vec<_> = files
+                .iter()
+                .map(|file| {
+                    serde_json::json!({
+                        "library": file.library,
+                        "file_name": file.file_name.to_string_lossy(),
+                    })
+                })
+                .collect();
+            output = serde_json::to_string_pretty(&files).unwrap();
+            output.push('\n');
+        }
+        CompileOrderFormat::Tcl => {
+            for file in files {
+                output.push_str(&format!(
+                    "lappend compile_order [list {{{}}} {{{}}}]\n",
+                    file.library,
+                    file.file_name.display()
+                ));
+            }
+        }
+        CompileOrderFormat::Make => {
+            output.push_str("VHDL_FILES :=");
+            for file in files {
+                output.push_str(&format!(" \\\n\t{}", file.file_name.display()));
+            }
+            output.push_str("\n\n");
+            for file in files {
+                output.push_str(&format!(
+                    "{}: VHDL_LIBRARY := {}\n",
+                    file.file_name.display(),
+                    file.library
+                ));
+            }
+        }
+    }
+    output
+}
+
+/// Write a configuration with file names relative to the folder of the configuration file
+fn write_config(config: &Config, file_name: &Path) {
+    let parent = file_name
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let parent = dunce::canonicalize(parent).expect("Output folder must exist");
+    std::fs::write(file_name, config.to_toml(&parent)).expect("Failed to write config file");
+    println!("Wrote {}", file_name.to_string_lossy());
+}
+
+/// A file name relative to the working folder, as the folder of a baseline must be known
+fn absolute_path(path: &str) -> PathBuf {
+    std::env::current_dir().unwrap_or_default().join(path)
+}
+
+fn show_diagnostics(diagnostics: &[Diagnostic], format: DiagnosticFormat) {
+    let root = std::env::current_dir().unwrap_or_default();
+    match format {
+        DiagnosticFormat::Text => {
+            for diagnostic in diagnostics {
+                println!("{}", diagnostic.show());
+            }
+
+            if !diagnostics.is_empty() {
+                println!("Found {} diagnostics", diagnostics.len());
+            }
+        }
+        DiagnosticFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&diagnostics_to_json(diagnostics, &root)).unwrap()
+        ),
+        DiagnosticFormat::Sarif => println!(
+            "{}",
+            serde_json::to_string_pretty(&diagnostics_to_sarif(diagnostics, &root)).unwrap()
+        ),
+        DiagnosticFormat::Junit => print!("{}", diagnostics_to_junit(diagnostics, &root)),
+        DiagnosticFormat::Gcc => {
+            for diagnostic in diagnostics {
+                println!("{}", diagnostic.show_gcc(&root));
+            }
+        }
     }
 }
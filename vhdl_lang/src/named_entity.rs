@@ -7,9 +7,10 @@
 use crate::ast::{
     AliasDeclaration, AnyDesignUnit, AnyPrimaryUnit, AnySecondaryUnit, Attribute,
     AttributeDeclaration, AttributeSpecification, ComponentDeclaration, Declaration, Designator,
-    FileDeclaration, HasIdent, Ident, InterfaceFileDeclaration, InterfacePackageDeclaration,
-    ObjectClass, ObjectDeclaration, PackageInstantiation, SubprogramBody, SubprogramInstantiation,
-    SubprogramSpecification, TypeDeclaration, WithDecl,
+    EntityClassEntry, FileDeclaration, HasIdent, Ident, InterfaceFileDeclaration,
+    InterfacePackageDeclaration, ObjectClass, ObjectDeclaration, PackageInstantiation,
+    PslDeclaration, SubprogramBody, SubprogramInstantiation, SubprogramSpecification,
+    TypeDeclaration, WithDecl,
 };
 use crate::ast::{ExternalObjectClass, InterfaceDeclaration, InterfaceObjectDeclaration};
 use crate::data::*;
@@ -27,6 +28,9 @@ pub use object::{Object, ObjectEnt, ObjectInterface};
 mod design;
 pub use design::{Design, DesignEnt};
 
+mod view;
+pub use view::{ElementMode, View, ViewRef};
+
 mod attribute;
 pub use attribute::AttributeEnt;
 
@@ -69,6 +73,9 @@ pub enum AnyEntKind<'a> {
     DeferredConstant(Subtype<'a>),
     Library,
     Design(Design<'a>),
+    View(View<'a>),
+    Psl(Psl),
+    Group(Group<'a>),
 }
 
 impl<'a> AnyEntKind<'a> {
@@ -131,6 +138,9 @@ impl<'a> AnyEntKind<'a> {
             Library => "library",
             Design(design) => design.describe(),
             Type(typ) => typ.describe(),
+            View(..) => "view",
+            Psl(psl) => psl.describe(),
+            Group(group) => group.describe(),
         }
     }
 }
@@ -545,6 +555,7 @@ impl HasEntityId for AnyDesignUnit {
                 AnyPrimaryUnit::Package(pkg) => pkg.ident.decl.get(),
                 AnyPrimaryUnit::PackageInstance(inst) => inst.ident.decl.get(),
                 AnyPrimaryUnit::Context(ctx) => ctx.ident.decl.get(),
+                AnyPrimaryUnit::VerificationUnit(unit) => unit.ident.decl.get(),
             },
             AnyDesignUnit::Secondary(secondary) => match secondary {
                 AnySecondaryUnit::Architecture(arch) => arch.ident.decl.get(),
@@ -608,6 +619,12 @@ impl HasEntityId for Declaration {
             Declaration::Package(pkg) => pkg.ent_id(),
             Declaration::Use(_) => None,
             Declaration::Configuration(_) => None,
+            Declaration::View(view) => view.ident.decl.get(),
+            Declaration::Psl(PslDeclaration::Named(decl)) => decl.ident.decl.get(),
+            Declaration::Psl(PslDeclaration::DefaultClock(_)) => None,
+            Declaration::Disconnection(_) => None,
+            Declaration::GroupTemplate(template) => template.ident.decl.get(),
+            Declaration::Group(group) => group.ident.decl.get(),
         }
     }
 }
@@ -740,11 +757,46 @@ impl Concurrent {
     }
 }
 
+/// A named PSL declaration or a formal parameter of one
+#[derive(Copy, Clone, Debug)]
+pub enum Psl {
+    Property,
+    Sequence,
+    Parameter,
+}
+
+impl Psl {
+    fn describe(&self) -> &'static str {
+        match self {
+            Psl::Property => "property",
+            Psl::Sequence => "sequence",
+            Psl::Parameter => "property parameter",
+        }
+    }
+}
+
+/// A group template or a group of named entities declared from a template
+#[derive(Clone)]
+pub enum Group<'a> {
+    Template(Vec<EntityClassEntry>),
+    Declaration(EntRef<'a>),
+}
+
+impl<'a> Group<'a> {
+    fn describe(&self) -> &'static str {
+        match self {
+            Group::Template(_) => "group template",
+            Group::Declaration(_) => "group",
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum Sequential {
     Loop,
     If,
     Case,
+    Block,
 }
 
 impl Sequential {
@@ -753,6 +805,7 @@ impl Sequential {
             Sequential::Case => "case",
             Sequential::If => "if",
             Sequential::Loop => "loop",
+            Sequential::Block => "block",
         }
     }
 }
@@ -9,7 +9,9 @@ use std::ops::Deref;
 use super::*;
 use crate::ast::InterfaceType;
 use crate::ast::Mode;
+use crate::ast::ModeViewIndicationKind;
 use crate::ast::ObjectClass;
+use crate::ast::SignalKind;
 
 // A named entity that is known to be an object
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -58,6 +60,25 @@ impl<'a> ObjectEnt<'a> {
                     }
                 }
                 ObjectInterface::Port(mode) => format!("port '{}' : {}", self.designator(), mode),
+                ObjectInterface::View(typ, kind, view) => {
+                    let what = if typ == InterfaceType::Port {
+                        "port"
+                    } else {
+                        "parameter"
+                    };
+                    match kind {
+                        ModeViewIndicationKind::Record => {
+                            format!("{what} '{}' : view {}", self.designator(), view.describe())
+                        }
+                        ModeViewIndicationKind::Array => {
+                            format!(
+                                "{what} '{}' : view ({})",
+                                self.designator(),
+                                view.describe()
+                            )
+                        }
+                    }
+                }
             }
         } else {
             self.describe_name()
@@ -78,13 +99,15 @@ impl<'a> ObjectEnt<'a> {
 }
 
 #[derive(Copy, Clone)]
-pub enum ObjectInterface {
+pub enum ObjectInterface<'a> {
     Generic,
     Port(Mode),
     Parameter(Mode),
+    /// A signal port or parameter whose element modes are given by a mode view
+    View(InterfaceType, ModeViewIndicationKind, ViewRef<'a>),
 }
 
-impl ObjectInterface {
+impl<'a> ObjectInterface<'a> {
     pub fn new(typ: InterfaceType, mode: Mode) -> Self {
         match typ {
             // @TODO error on non-input mode
@@ -98,6 +121,8 @@ impl ObjectInterface {
         match self {
             ObjectInterface::Generic => Mode::In,
             ObjectInterface::Parameter(m) | ObjectInterface::Port(m) => *m,
+            // The element modes are checked against the view
+            ObjectInterface::View(..) => Mode::InOut,
         }
     }
 
@@ -106,6 +131,20 @@ impl ObjectInterface {
             ObjectInterface::Generic => InterfaceType::Generic,
             ObjectInterface::Parameter(..) => InterfaceType::Parameter,
             ObjectInterface::Port(..) => InterfaceType::Port,
+            ObjectInterface::View(typ, ..) => *typ,
+        }
+    }
+
+    /// The element modes of an interface object with a mode view
+    pub fn view_mode(&self) -> Option<ElementMode<'a>> {
+        match self {
+            ObjectInterface::View(_, ModeViewIndicationKind::Record, view) => {
+                Some(ElementMode::View(*view))
+            }
+            ObjectInterface::View(_, ModeViewIndicationKind::Array, view) => {
+                Some(ElementMode::Array(*view))
+            }
+            _ => None,
         }
     }
 }
@@ -116,9 +155,11 @@ impl ObjectInterface {
 #[derive(Clone)]
 pub struct Object<'a> {
     pub class: ObjectClass,
-    pub iface: Option<ObjectInterface>,
+    pub iface: Option<ObjectInterface<'a>>,
     pub subtype: Subtype<'a>,
     pub has_default: bool,
+    /// The kind of a guarded signal, LRM 6.4.2.3
+    pub signal_kind: Option<SignalKind>,
 }
 
 impl<'a> Object<'a> {
@@ -128,6 +169,7 @@ impl<'a> Object<'a> {
             iface: Some(ObjectInterface::Parameter(Mode::In)),
             subtype,
             has_default: false,
+            signal_kind: None,
         }
     }
 
@@ -137,7 +179,10 @@ impl<'a> Object<'a> {
     }
 
     pub fn is_port(&self) -> bool {
-        matches!(self.iface, Some(ObjectInterface::Port(..)))
+        matches!(
+            self.iface,
+            Some(ObjectInterface::Port(..) | ObjectInterface::View(InterfaceType::Port, ..))
+        )
     }
 
     pub fn is_generic(&self) -> bool {
@@ -145,7 +190,12 @@ impl<'a> Object<'a> {
     }
 
     pub fn is_param(&self) -> bool {
-        matches!(self.iface, Some(ObjectInterface::Parameter(_)))
+        matches!(
+            self.iface,
+            Some(
+                ObjectInterface::Parameter(_) | ObjectInterface::View(InterfaceType::Parameter, ..)
+            )
+        )
     }
 
     pub fn mode(&self) -> Option<Mode> {
@@ -0,0 +1,113 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::ast::Mode;
+
+/// A mode view declaration (VHDL-2019)
+pub struct View<'a> {
+    /// The record type that the view applies to
+    pub typ: TypeEnt<'a>,
+    /// The mode of each record element, keyed by the element name
+    pub elements: FnvHashMap<Symbol, ElementMode<'a>>,
+}
+
+impl<'a> View<'a> {
+    pub fn element_mode(&self, element: &Symbol) -> Option<ElementMode<'a>> {
+        self.elements.get(element).copied()
+    }
+}
+
+/// A reference to a mode view, possibly through the 'converse attribute
+#[derive(Clone, Copy)]
+pub struct ViewRef<'a> {
+    pub view: EntRef<'a>,
+    pub converse: bool,
+}
+
+impl<'a> ViewRef<'a> {
+    pub fn new(view: EntRef<'a>) -> Self {
+        ViewRef {
+            view,
+            converse: false,
+        }
+    }
+
+    pub fn converse(self) -> Self {
+        ViewRef {
+            view: self.view,
+            converse: !self.converse,
+        }
+    }
+
+    pub fn kind(&self) -> &'a View<'a> {
+        if let AnyEntKind::View(view) = self.view.actual_kind() {
+            view
+        } else {
+            unreachable!("Must be a view")
+        }
+    }
+
+    /// The view name as written, for example `master'converse`
+    pub fn describe(&self) -> String {
+        if self.converse {
+            format!("{}'converse", self.view.designator())
+        } else {
+            self.view.designator().to_string()
+        }
+    }
+
+    pub fn typ(&self) -> TypeEnt<'a> {
+        self.kind().typ
+    }
+
+    /// The mode of a record element as seen through this reference
+    pub fn element_mode(&self, element: &Symbol) -> Option<ElementMode<'a>> {
+        let mode = self.kind().element_mode(element)?;
+        Some(if self.converse { mode.converse() } else { mode })
+    }
+
+    /// True if any element, including those of nested views, has mode in
+    pub fn has_input_element(&self) -> bool {
+        self.kind()
+            .elements
+            .keys()
+            .any(|elem| match self.element_mode(elem) {
+                Some(ElementMode::Simple(mode)) => mode == Mode::In,
+                Some(ElementMode::View(view)) | Some(ElementMode::Array(view)) => {
+                    view.has_input_element()
+                }
+                None => false,
+            })
+    }
+}
+
+/// The mode of a single record element within a mode view
+#[derive(Clone, Copy)]
+pub enum ElementMode<'a> {
+    Simple(Mode),
+    /// The element is a record with its own view
+    View(ViewRef<'a>),
+    /// The element is an array of records with a view
+    Array(ViewRef<'a>),
+}
+
+impl<'a> ElementMode<'a> {
+    /// LRM 6.5.2 The converse of a mode view swaps in and out,
+    /// buffer becomes in and inout remains inout
+    pub fn converse(self) -> Self {
+        match self {
+            ElementMode::Simple(mode) => ElementMode::Simple(match mode {
+                Mode::In => Mode::Out,
+                Mode::Out | Mode::Buffer => Mode::In,
+                Mode::InOut => Mode::InOut,
+                Mode::Linkage => Mode::Linkage,
+            }),
+            ElementMode::View(view) => ElementMode::View(view.converse()),
+            ElementMode::Array(view) => ElementMode::Array(view.converse()),
+        }
+    }
+}
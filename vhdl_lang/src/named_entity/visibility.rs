@@ -117,6 +117,14 @@ impl<'a> Visibility<'a> {
         }
     }
 
+    /// Collect the designators of all potentially visible declarations
+    pub fn designators_into(&self, designators: &mut Vec<Designator>) {
+        for visible_region in self.all_in_regions.iter() {
+            designators.extend(visible_region.region.entities.keys().cloned());
+        }
+        designators.extend(self.visible.keys().cloned());
+    }
+
     /// Helper function lookup a visible declaration within the region
     pub fn lookup_into(&self, designator: &Designator, visible: &mut Visible<'a>) {
         for visible_region in self.all_in_regions.iter() {
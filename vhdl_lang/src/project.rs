@@ -4,13 +4,17 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use crate::analysis::DesignRoot;
+use crate::analysis::{CompileOrderFile, CompileOrderScope, DependencyGraph, DesignRoot};
 use crate::ast::search::Searcher;
-use crate::ast::DesignFile;
+use crate::ast::{DesignFile, HasUnitId};
 use crate::completion::{list_completion_options, CompletionItem};
 use crate::config::Config;
+use crate::lint::clock_domain::ClockDomainCrossingLinter;
 use crate::lint::dead_code::UnusedDeclarationsLinter;
+use crate::lint::registers::{infer_registers, ArchitectureRegisters};
+use crate::lint::synthesis::SynthesisLinter;
 use crate::named_entity::{AnyEnt, EntRef};
+use crate::standard::VHDLStandard;
 use crate::syntax::VHDLParser;
 use crate::{data::*, EntHierarchy, EntityId};
 use fnv::{FnvHashMap, FnvHashSet};
@@ -24,6 +28,8 @@ pub struct Project {
     files: FnvHashMap<FilePath, SourceFile>,
     empty_libraries: FnvHashSet<Symbol>,
     lint: Option<UnusedDeclarationsLinter>,
+    clock_domain_lint: Option<ClockDomainCrossingLinter>,
+    synthesis_lint: SynthesisLinter,
 }
 
 impl Project {
@@ -35,6 +41,8 @@ impl Project {
             empty_libraries: FnvHashSet::default(),
             parser,
             lint: None,
+            clock_domain_lint: None,
+            synthesis_lint: SynthesisLinter::default(),
             config: Config::default(),
         }
     }
@@ -43,13 +51,19 @@ impl Project {
         self.lint = Some(UnusedDeclarationsLinter::default());
     }
 
+    /// Report signals that are read in another clock domain than the one they are registered in
+    pub fn enable_clock_domain_crossing_detection(&mut self) {
+        self.clock_domain_lint = Some(ClockDomainCrossingLinter::default());
+    }
+
     /// Create instance from given configuration.
     /// Files referred by configuration are parsed into corresponding libraries.
     pub fn from_config(config: Config, messages: &mut dyn MessageHandler) -> Project {
         let mut project = Project::new();
+        project.parser.conditional_identifiers = config.conditional_analysis().clone();
         let files = project.load_files_from_config(&config, messages);
-        project.parse_and_add_files(files, messages);
         project.config = config;
+        project.parse_and_add_files(files, messages);
         project
     }
 
@@ -57,7 +71,10 @@ impl Project {
     /// The design state is reset, new files are added and parsed. Existing source files will be
     /// kept and parsed from in-memory source (required for incremental document updates).
     pub fn update_config(&mut self, config: Config, messages: &mut dyn MessageHandler) {
-        self.parser = VHDLParser::default();
+        self.parser = VHDLParser {
+            conditional_identifiers: config.conditional_analysis().clone(),
+            ..VHDLParser::default()
+        };
         self.root = DesignRoot::new(self.parser.symbols.clone());
 
         // Reset library associations for known files,
@@ -73,17 +90,20 @@ impl Project {
             .into_iter()
             .partition(|(file_name, _library_names)| self.files.contains_key(file_name));
 
+        self.config = config;
+
         for (file_name, library_names) in known_files {
             if let Some(source_file) = self.files.get_mut(&file_name) {
                 source_file.parser_diagnostics.clear();
+                source_file.design_file = self.parser.parse_design_source(
+                    &source_file.source,
+                    standard_of_file(&self.config, &library_names),
+                    &mut source_file.parser_diagnostics,
+                );
                 source_file.library_names = library_names;
-                source_file.design_file = self
-                    .parser
-                    .parse_design_source(&source_file.source, &mut source_file.parser_diagnostics);
             }
         }
 
-        self.config = config;
         self.parse_and_add_files(new_files, messages);
     }
 
@@ -133,10 +153,11 @@ impl Project {
         let parsed: Vec<_> = files_to_parse
             .into_par_iter()
             .map_init(
-                || &self.parser,
-                |parser, (file_name, library_names)| {
+                || (&self.parser, &self.config),
+                |(parser, config), (file_name, library_names)| {
                     let mut diagnostics = Vec::new();
-                    let result = parser.parse_design_file(&file_name, &mut diagnostics);
+                    let standard = standard_of_file(config, &library_names);
+                    let result = parser.parse_design_file(&file_name, standard, &mut diagnostics);
                     (file_name, library_names, diagnostics, result)
                 },
             )
@@ -180,6 +201,29 @@ impl Project {
             .map(|file| file.source.clone())
     }
 
+    /// Returns true if the file matches the file patterns of any library of the configuration
+    pub fn is_mapped_by_config(&self, file_name: &Path) -> bool {
+        self.config.libraries_of_file(file_name).next().is_some()
+    }
+
+    /// The libraries of a file that was not part of the project when the configuration was loaded,
+    /// such as a file that was created afterwards
+    fn library_names_of_new_file(&self, file_name: &Path) -> FnvHashSet<Symbol> {
+        let mut library_names: FnvHashSet<Symbol> = self
+            .config
+            .libraries_of_file(file_name)
+            .map(|library| self.root.symbol_utf8(library.name()))
+            .collect();
+
+        if library_names.is_empty() {
+            // Add unmapped files to the default library or an anonymous library work
+            // To still get some semantic analysis for unmapped files
+            let default_library = self.config.default_library().unwrap_or("work");
+            library_names.insert(self.root.symbol_utf8(default_library));
+        }
+        library_names
+    }
+
     pub fn update_source(&mut self, source: &Source) {
         let mut source_file = {
             if let Some(mut source_file) = self.files.remove(source.file_path()) {
@@ -191,25 +235,20 @@ impl Project {
                 source_file
             } else {
                 // File is not part of the project
-                // @TODO use config wildcards to map to library
-
-                // Add unmapped files to an anonymous library work
-                // To still get some semantic analysis for unmapped files
-                let mut library_names = FnvHashSet::default();
-                library_names.insert(self.root.symbol_utf8("work"));
-
                 SourceFile {
                     source: source.clone(),
-                    library_names,
+                    library_names: self.library_names_of_new_file(source.file_name()),
                     parser_diagnostics: vec![],
                     design_file: DesignFile::default(),
                 }
             }
         };
         source_file.parser_diagnostics.clear();
-        source_file.design_file = self
-            .parser
-            .parse_design_source(source, &mut source_file.parser_diagnostics);
+        source_file.design_file = self.parser.parse_design_source(
+            source,
+            standard_of_file(&self.config, &source_file.library_names),
+            &mut source_file.parser_diagnostics,
+        );
         self.files
             .insert(source.file_path().to_owned(), source_file);
     }
@@ -225,10 +264,14 @@ impl Project {
             for library_name in source_file.library_names.iter() {
                 let design_file = design_files.pop().unwrap();
                 self.root.add_design_file(library_name.clone(), design_file);
+                self.root.set_standard(
+                    library_name.clone(),
+                    self.config.standard_of(&library_name.name_utf8()),
+                );
             }
 
             for diagnostic in source_file.parser_diagnostics.iter().cloned() {
-                diagnostics.push(diagnostic);
+                diagnostics.push(diagnostic.with_code(DiagnosticCode::Syntax));
             }
         }
 
@@ -242,6 +285,13 @@ impl Project {
             lint.lint(&self.root, &self.config, &analyzed_units, &mut diagnostics);
         }
 
+        if let Some(ref mut lint) = self.clock_domain_lint {
+            lint.lint(&self.root, &self.config, &analyzed_units, &mut diagnostics);
+        }
+
+        self.synthesis_lint
+            .lint(&self.root, &self.config, &analyzed_units, &mut diagnostics);
+
         diagnostics
     }
 
@@ -317,6 +367,34 @@ impl Project {
         self.root.find_all_unresolved()
     }
 
+    /// Infer the registers and their resets in all architectures that are not third party
+    pub fn register_report(&self) -> Vec<ArchitectureRegisters> {
+        infer_registers(&self.root, &self.config)
+    }
+
+    /// The files of the scope in an order they may be analyzed in by other tools.
+    /// The project must be analyzed first
+    pub fn compile_order(
+        &self,
+        scope: &CompileOrderScope,
+    ) -> Result<Vec<CompileOrderFile>, String> {
+        self.root.compile_order(scope)
+    }
+
+    /// The dependency graph of all design units, or of those reachable from a root unit
+    /// given as library and unit name. The project must be analyzed first
+    pub fn dependency_graph(&self, root: Option<(&str, &str)>) -> Result<DependencyGraph, String> {
+        self.root.dependency_graph(root)
+    }
+
+    /// The design unit that a position is within as `library.unit`,
+    /// where architectures are named `entity(architecture)` and package bodies `package(body)`
+    pub(crate) fn enclosing_unit_name(&self, pos: &SrcPos) -> Option<String> {
+        self.root
+            .unit_at(pos)
+            .map(|unit| format!("{}.{}", unit.unit_id().library_name(), unit.display_name()))
+    }
+
     pub fn files(&self) -> impl Iterator<Item = &SourceFile> {
         self.files.values()
     }
@@ -326,10 +404,27 @@ impl Project {
         source: &Source,
         cursor: Position,
     ) -> Vec<CompletionItem> {
-        list_completion_options(&self.root, source, cursor)
+        let standard = match self.files.get(source.file_path()) {
+            Some(source_file) => standard_of_file(&self.config, &source_file.library_names),
+            None => standard_of_file(
+                &self.config,
+                &self.library_names_of_new_file(source.file_name()),
+            ),
+        };
+        list_completion_options(&self.root, source, cursor, standard)
     }
 }
 
+/// A file in several libraries is only parsed once, using the newest of their revisions
+/// to not reject constructs that one of the libraries allows.
+fn standard_of_file(config: &Config, library_names: &FnvHashSet<Symbol>) -> VHDLStandard {
+    library_names
+        .iter()
+        .map(|library_name| config.standard_of(&library_name.name_utf8()))
+        .max()
+        .unwrap_or_default()
+}
+
 /// Multiply clonable value by cloning
 /// Avoid clone for n=1
 fn multiply<T: Clone>(value: T, n: usize) -> Vec<T> {
@@ -406,6 +501,43 @@ lib.files = ['file.vhd']
         check_no_diagnostics(&project.analyse());
     }
 
+    #[test]
+    fn completion_uses_the_standard_of_the_library() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("pkg.vhd"),
+            "
+package context is
+  constant c : natural := 0;
+end package;
+",
+        )
+        .unwrap();
+        let use_file = root.path().join("use.vhd");
+        std::fs::write(&use_file, "use legacy.context.").unwrap();
+
+        let std_files = Path::new(env!("CARGO_MANIFEST_DIR")).join("../vhdl_libraries/std/*.vhd");
+        let config_str = format!(
+            "
+[libraries]
+std.files = ['{}']
+legacy.files = ['*.vhd']
+legacy.standard = '1993'
+        ",
+            std_files.display()
+        );
+        let config = Config::from_str(&config_str, root.path()).unwrap();
+        let mut project = Project::from_config(config, &mut Vec::new());
+        project.analyse();
+
+        let source = project.get_source(&use_file).unwrap();
+        let cursor = Position::new(0, "use legacy.context.".len() as u32);
+        let options = project.list_completion_options(&source, cursor);
+        assert!(options
+            .iter()
+            .any(|option| matches!(option, CompletionItem::Simple(ent) if ent.designator().to_string() == "c")));
+    }
+
     #[test]
     fn unmapped_libraries_are_analyzed() {
         let mut messages = Vec::new();
@@ -441,6 +573,76 @@ end architecture;
         assert_eq!(diag.message, "Duplicate architecture 'rtl' of entity 'ent'")
     }
 
+    #[test]
+    fn new_files_are_mapped_by_library_patterns() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(tempdir.path()).unwrap();
+        std::fs::create_dir(root.join("rtl")).unwrap();
+
+        let config = Config::from_str(
+            "
+default_library = 'scratch'
+[libraries]
+rtl.files = ['rtl/*.vhd']
+        ",
+            &root,
+        )
+        .unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(
+            messages,
+            vec![Message::warning(format!(
+                "Pattern '{}' did not match any file",
+                root.join("rtl/*.vhd").to_str().unwrap()
+            ))]
+        );
+
+        // The files are opened before they exist on disk
+        let pkg = Source::inline(
+            &root.join("rtl").join("pkg.vhd"),
+            "
+package pkg is
+end package;
+        ",
+        );
+        let ent = Source::inline(
+            &root.join("ent.vhd"),
+            "
+library rtl;
+use rtl.pkg.all;
+
+entity ent is
+end entity;
+        ",
+        );
+        project.update_source(&pkg);
+        project.update_source(&ent);
+
+        assert!(project.is_mapped_by_config(pkg.file_name()));
+        assert!(!project.is_mapped_by_config(ent.file_name()));
+        assert_eq!(
+            project.library_mapping_of(&pkg),
+            vec![project.root.symbol_utf8("rtl")]
+        );
+        assert_eq!(
+            project.library_mapping_of(&ent),
+            vec![project.root.symbol_utf8("scratch")]
+        );
+        check_no_diagnostics(&project.analyse());
+    }
+
+    #[test]
+    fn new_files_default_to_work_library() {
+        let mut project = Project::from_config(Config::default(), &mut Vec::new());
+        let source = Source::inline(Path::new("unmapped.vhd"), "");
+        project.update_source(&source);
+        assert_eq!(
+            project.library_mapping_of(&source),
+            vec![project.root.symbol_utf8("work")]
+        );
+    }
+
     /// Test that the same file can be added to several libraries
     #[test]
     fn test_same_file_in_multiple_libraries() {
@@ -488,6 +690,45 @@ use_lib.files = ['use_file.vhd']
         check_no_diagnostics(&project.analyse());
     }
 
+    /// Test that files are parsed according to the standard of their library
+    #[test]
+    fn library_standard_is_used_for_parsing() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("file.vhd");
+        std::fs::write(
+            vhdl_file_path,
+            "
+entity context is
+end entity;
+
+architecture a of context is
+begin
+  process (all)
+  begin
+  end process;
+end architecture;
+        ",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+legacy.files = ['file.vhd']
+legacy.standard = '1993'
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "'process (all)' requires VHDL-2008 or later, the library uses VHDL-1993"
+        );
+    }
+
     fn update(project: &mut Project, source: &mut Source, contents: &str) {
         std::fs::write(std::path::Path::new(source.file_name()), contents).unwrap();
         *source = Source::from_latin1_file(source.file_name()).unwrap();
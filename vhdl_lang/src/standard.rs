@@ -0,0 +1,70 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Revision of the VHDL language (IEEE 1076) that a library is written in.
+/// VHDL-2000 and VHDL-2002 differ in no way that matters to the analysis and share a variant.
+///
+/// The variants are ordered so that a later revision compares greater than an earlier one.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum VHDLStandard {
+    VHDL1993,
+    VHDL2002,
+    #[default]
+    VHDL2008,
+    VHDL2019,
+}
+
+impl VHDLStandard {
+    /// The year of the revision as used in `vhdl_ls.toml`
+    pub fn year(&self) -> &'static str {
+        match self {
+            VHDLStandard::VHDL1993 => "1993",
+            VHDLStandard::VHDL2002 => "2002",
+            VHDLStandard::VHDL2008 => "2008",
+            VHDLStandard::VHDL2019 => "2019",
+        }
+    }
+}
+
+impl FromStr for VHDLStandard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1993" | "93" => Ok(VHDLStandard::VHDL1993),
+            "2000" | "2002" | "02" => Ok(VHDLStandard::VHDL2002),
+            "2008" | "08" => Ok(VHDLStandard::VHDL2008),
+            "2019" | "19" => Ok(VHDLStandard::VHDL2019),
+            _ => Err(format!(
+                "Unknown VHDL standard '{s}', expected one of 1993, 2002, 2008 or 2019"
+            )),
+        }
+    }
+}
+
+impl Display for VHDLStandard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VHDL-{}", self.year())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_standard() {
+        assert_eq!("1993".parse(), Ok(VHDLStandard::VHDL1993));
+        assert_eq!("02".parse(), Ok(VHDLStandard::VHDL2002));
+        assert_eq!("2019".parse(), Ok(VHDLStandard::VHDL2019));
+        assert_eq!("2000".parse(), Ok(VHDLStandard::VHDL2002));
+        assert!("1987".parse::<VHDLStandard>().is_err());
+        assert!(VHDLStandard::VHDL1993 < VHDLStandard::VHDL2008);
+    }
+}
@@ -16,17 +16,22 @@ mod configuration;
 mod context;
 mod declarative_part;
 mod design_unit;
+mod disconnection;
 mod expression;
+mod group;
 mod interface_declaration;
 mod names;
 mod object_declaration;
 mod parser;
+mod psl;
 mod range;
+mod recover;
 mod separated_list;
 mod sequential_statement;
 mod subprogram;
 mod subtype_indication;
 mod type_declaration;
+mod view_declaration;
 mod waveform;
 
 #[cfg(test)]
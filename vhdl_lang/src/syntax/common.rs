@@ -9,6 +9,7 @@ use crate::ast::Ident;
 use crate::data::Diagnostic;
 use crate::data::DiagnosticHandler;
 use crate::data::WithPos;
+use crate::standard::VHDLStandard;
 use crate::SrcPos;
 
 /// Parse optional part followed by optional keyword
@@ -31,6 +32,23 @@ where
     Ok(optional)
 }
 
+/// Report constructs that were introduced in a later revision of the language than the one parsed
+pub fn check_standard(
+    stream: &TokenStream,
+    pos: impl AsRef<SrcPos>,
+    construct: &str,
+    required: VHDLStandard,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let standard = stream.standard();
+    if standard < required {
+        diagnostics.error(
+            pos,
+            format!("{construct} requires {required} or later, the library uses {standard}"),
+        );
+    }
+}
+
 pub fn check_end_identifier_mismatch<T: std::fmt::Display + std::cmp::PartialEq>(
     ident: &WithPos<T>,
     end_ident: Option<WithPos<T>>,
@@ -4,8 +4,8 @@
 //
 // Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 
-use super::common::check_label_identifier_mismatch;
 use super::common::ParseResult;
+use super::common::{check_label_identifier_mismatch, check_standard};
 use super::declarative_part::{is_declarative_part, parse_declarative_part};
 use super::expression::parse_aggregate;
 use super::expression::{parse_choices, parse_expression};
@@ -14,7 +14,9 @@ use super::names::parse_name;
 use super::names::{
     expression_to_ident, into_selected_name, parse_association_list, parse_selected_name,
 };
+use super::psl::parse_psl_directive;
 use super::range::parse_discrete_range;
+use super::recover::{skip_construct, RecoverContext};
 use super::sequential_statement::{
     parse_assert_statement, parse_labeled_sequential_statements, parse_selection,
     parse_signal_assignment_right_hand, parse_target,
@@ -23,6 +25,7 @@ use super::tokens::{Kind::*, TokenStream};
 use super::waveform::{parse_delay_mechanism, parse_waveform};
 use crate::ast::*;
 use crate::data::*;
+use crate::standard::VHDLStandard;
 use crate::syntax::{Kind, TokenAccess};
 
 /// LRM 11.2 Block statement
@@ -38,7 +41,7 @@ pub fn parse_block_statement(
             LeftPar => {
                 stream.skip();
                 let expr = parse_expression(stream)?;
-                stream.pop_if_kind(RightPar);
+                stream.expect_kind(RightPar)?;
                 Some(expr)
             }
             _ => None,
@@ -181,6 +184,7 @@ pub fn parse_process_statement(
     let sensitivity_list = if stream.skip_if_kind(LeftPar) {
         peek_token!(stream, token,
         All => {
+            check_standard(stream, token, "'process (all)'", VHDLStandard::VHDL2008, diagnostics);
             stream.skip();
             stream.expect_kind(RightPar)?;
             Some(SensitivityList::All)
@@ -273,8 +277,7 @@ fn parse_assignment_known_target(
 ) -> ParseResult<ConcurrentStatement> {
     // @TODO postponed
     let postponed = false;
-    // @TODO guarded
-    let guarded = false;
+    let guarded = stream.skip_if_kind(Guarded);
     let delay_mechanism = parse_delay_mechanism(stream)?;
     Ok(ConcurrentStatement::Assignment(
         ConcurrentSignalAssignment {
@@ -309,8 +312,7 @@ fn parse_selected_signal_assignment(
     stream.expect_kind(Select)?;
     let target = parse_target(stream)?;
     stream.expect_kind(LTE)?;
-    // @TODO guarded
-    let guarded = false;
+    let guarded = stream.skip_if_kind(Guarded);
     let delay_mechanism = parse_delay_mechanism(stream)?;
     let rhs = AssignmentRightHand::Selected(parse_selection(stream, expression, parse_waveform)?);
     Ok(ConcurrentSignalAssignment {
@@ -332,6 +334,23 @@ pub fn parse_concurrent_assert_statement(
     })
 }
 
+/// A concurrent assertion is parsed as a PSL directive when its condition is not a VHDL expression
+fn parse_concurrent_assert_or_psl_directive(
+    stream: &TokenStream,
+) -> ParseResult<ConcurrentStatement> {
+    let state = stream.state();
+    match parse_concurrent_assert_statement(stream, false) {
+        Ok(assert) => Ok(ConcurrentStatement::Assert(assert)),
+        Err(err) => {
+            if stream.standard() < VHDLStandard::VHDL2008 {
+                return Err(err);
+            }
+            stream.set_state(state);
+            Ok(ConcurrentStatement::Psl(parse_psl_directive(stream)?))
+        }
+    }
+}
+
 pub fn parse_map_aspect(
     stream: &TokenStream,
     aspect_kind: Kind,
@@ -617,7 +636,10 @@ pub fn parse_concurrent_statement(
             For => ConcurrentStatement::ForGenerate(parse_for_generate_statement(stream, label, diagnostics)?),
             If => ConcurrentStatement::IfGenerate(parse_if_generate_statement(stream, label, diagnostics)?),
             Case => ConcurrentStatement::CaseGenerate(parse_case_generate_statement(stream, label, diagnostics)?),
-            Assert => ConcurrentStatement::Assert(parse_concurrent_assert_statement(stream, false)?),
+            Assert => parse_concurrent_assert_or_psl_directive(stream)?,
+            Assume | AssumeGuarantee | Restrict | RestrictGuarantee | Cover | Fairness | Strong => {
+                ConcurrentStatement::Psl(parse_psl_directive(stream)?)
+            },
             Postponed => {
                 stream.skip();
                 let token = stream.peek_expect()?;
@@ -672,7 +694,14 @@ pub fn parse_labeled_concurrent_statements(
                 break Ok(statements);
             }
             _ => {
-                statements.push(parse_labeled_concurrent_statement(stream, diagnostics)?);
+                let start = stream.state();
+                match parse_labeled_concurrent_statement(stream, diagnostics) {
+                    Ok(stmt) => statements.push(stmt),
+                    Err(diag) => {
+                        diagnostics.push(diag);
+                        skip_construct(stream, start, RecoverContext::Statement)?;
+                    }
+                }
             }
         }
     }
@@ -1152,6 +1181,32 @@ end process;",
         );
     }
 
+    #[test]
+    fn process_all_requires_vhdl_2008() {
+        let code = Code::new(
+            "\
+process (all) is
+begin
+end process;",
+        )
+        .with_standard(VHDLStandard::VHDL1993);
+        let (stmt, diagnostics) = code.with_stream_diagnostics(parse_labeled_concurrent_statement);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                code.s1("all"),
+                "'process (all)' requires VHDL-2008 or later, the library uses VHDL-1993"
+            )]
+        );
+        assert!(matches!(
+            stmt.statement.item,
+            ConcurrentStatement::Process(ProcessStatement {
+                sensitivity_list: Some(SensitivityList::All),
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_concurrent_assert() {
         let code = Code::new("assert cond = true;");
@@ -1260,6 +1315,38 @@ with x(0) + 1 select
         assert_eq!(stmt.statement.pos, code.pos());
     }
 
+    #[test]
+    fn test_guarded_concurrent_signal_assignment() {
+        let code = Code::new("foo <= guarded transport bar after 2 ns;");
+        let assign = ConcurrentSignalAssignment {
+            postponed: false,
+            guarded: true,
+            target: code.s1("foo").name().map_into(Target::Name),
+            delay_mechanism: Some(DelayMechanism::Transport),
+            rhs: AssignmentRightHand::Simple(code.s1("bar after 2 ns").waveform()),
+        };
+        let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
+        assert_eq!(
+            stmt.statement,
+            WithPos::new(ConcurrentStatement::Assignment(assign), code.pos())
+        );
+    }
+
+    #[test]
+    fn test_guarded_selected_signal_assignment() {
+        let code = Code::new(
+            "\
+with sel select
+   foo <= guarded bar when '0', baz when others;",
+        );
+        let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
+        let ConcurrentStatement::Assignment(assign) = stmt.statement.item else {
+            panic!("Expected assignment");
+        };
+        assert!(assign.guarded);
+        assert_eq!(assign.target, code.s1("foo").name().map_into(Target::Name));
+    }
+
     #[test]
     fn test_component_instantiation() {
         let code = Code::new("inst: component lib.foo.bar;");
@@ -2001,4 +2088,26 @@ end generate gen1;",
             )
         );
     }
+
+    #[test]
+    fn recover_from_broken_process_header() {
+        let code = Code::new(
+            "\
+process (clk
+begin
+  if rising_edge(clk) then
+    q <= d;
+  end if;
+end process;
+foo <= bar;
+end",
+        );
+        let (statements, diagnostics) =
+            code.with_partial_stream_diagnostics(parse_labeled_concurrent_statements);
+        assert_eq!(
+            statements,
+            Ok(vec![code.s1("foo <= bar;").concurrent_statement()])
+        );
+        assert_eq!(diagnostics.len(), 1);
+    }
 }
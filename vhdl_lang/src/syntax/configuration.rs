@@ -8,6 +8,7 @@ use super::common::check_end_identifier_mismatch;
 use super::common::ParseResult;
 use super::concurrent_statement::parse_generic_and_port_map;
 use super::context::parse_use_clause;
+use super::group::parse_group;
 use super::names::{parse_name, parse_selected_name};
 use super::tokens::{Kind::*, TokenSpan, TokenStream};
 use crate::ast::*;
@@ -293,6 +294,14 @@ pub fn parse_configuration_declaration(
                     diagnostics,
                 )?));
             }
+            Group => match parse_group(stream)? {
+                Declaration::Group(group) => decl.push(ConfigurationDeclarativeItem::Group(group)),
+                Declaration::GroupTemplate(template) => diagnostics.error(
+                    template.ident.tree.pos(),
+                    "Group template declaration not allowed in a configuration declaration",
+                ),
+                _ => unreachable!(),
+            },
             _ => break Vec::new(),
         }
     };
@@ -453,6 +462,29 @@ end configuration cfg;
         );
     }
 
+    #[test]
+    fn configuration_group_declaration() {
+        let code = Code::new(
+            "\
+configuration cfg of entity_name is
+  group grp : tmpl (lbl);
+  for rtl
+  end for;
+end configuration cfg;
+",
+        );
+        let cfg = code.with_stream_no_diagnostics(parse_configuration_declaration);
+        assert_eq!(
+            cfg.decl,
+            vec![ConfigurationDeclarativeItem::Group(GroupDeclaration {
+                span: code.s1("group grp : tmpl (lbl);").token_span(),
+                ident: code.s1("grp").decl_ident(),
+                template_name: code.s1("tmpl").name(),
+                constituents: vec![code.s1("lbl").name()],
+            })]
+        );
+    }
+
     #[test]
     fn configuration_vunit_binding_indication() {
         let code = Code::new(
@@ -10,13 +10,19 @@ use super::common::ParseResult;
 use super::component_declaration::parse_component_declaration;
 use super::configuration::parse_configuration_specification;
 use super::context::parse_use_clause;
+use super::disconnection::parse_disconnection_specification;
+use super::group::parse_group;
 use super::names::parse_selected_name;
 use super::object_declaration::{parse_file_declaration, parse_object_declaration};
+use super::psl::parse_psl_declaration;
+use super::recover::{skip_construct, RecoverContext};
 use super::subprogram::parse_subprogram;
 use super::tokens::{Kind::*, *};
 use super::type_declaration::parse_type_declaration;
+use super::view_declaration::parse_mode_view_declaration;
 use crate::ast::{ContextClause, Declaration, PackageInstantiation};
 use crate::data::DiagnosticHandler;
+use crate::standard::VHDLStandard;
 use crate::syntax::concurrent_statement::parse_map_aspect;
 
 pub fn parse_package_instantiation(
@@ -47,7 +53,8 @@ pub fn is_declarative_part(stream: &TokenStream, begin_is_end: bool) -> ParseRes
 fn check_declarative_part(token: &Token, may_end: bool, may_begin: bool) -> ParseResult<()> {
     match token.kind {
         Use | Type | Subtype | Shared | Constant | Signal | Variable | File | Component
-        | Attribute | Alias | Impure | Pure | Function | Procedure | Package | For => Ok(()),
+        | Attribute | Alias | Impure | Pure | Function | Procedure | Package | For | View
+        | Property | Sequence | Default | Disconnect | Group => Ok(()),
         Begin if may_begin => Ok(()),
         End if may_end => Ok(()),
         _ => {
@@ -61,95 +68,115 @@ fn check_declarative_part(token: &Token, may_end: bool, may_begin: bool) -> Pars
     }
 }
 
+/// Returns true if the token kind starts a declaration of a declarative part
+pub fn is_declaration_start(kind: Kind) -> bool {
+    matches!(
+        kind,
+        Type | Subtype
+            | Component
+            | Impure
+            | Pure
+            | Function
+            | Procedure
+            | Package
+            | For
+            | File
+            | Shared
+            | Constant
+            | Signal
+            | Variable
+            | Attribute
+            | Use
+            | Alias
+            | View
+            | Property
+            | Sequence
+            | Default
+            | Disconnect
+            | Group
+    )
+}
+
+fn is_recover_token(kind: Kind) -> bool {
+    is_declaration_start(kind) || matches!(kind, Begin | End)
+}
+
+/// Parse a single declaration, the next token must start a declaration.
+/// A declaration with a syntax error is reported and skipped.
+pub fn parse_declaration(
+    stream: &TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+    declarations: &mut Vec<Declaration>,
+) -> ParseResult<()> {
+    let start = stream.state();
+    let token = stream.peek_expect()?;
+    let decls: ParseResult<Vec<Declaration>> = match token.kind {
+        Type | Subtype => {
+            parse_type_declaration(stream, diagnostics).map(|decl| vec![Declaration::Type(decl)])
+        }
+        Component => parse_component_declaration(stream, diagnostics)
+            .map(|decl| vec![Declaration::Component(decl)]),
+        Impure | Pure | Function | Procedure => {
+            parse_subprogram(stream, diagnostics).map(|decl| vec![decl])
+        }
+        Package => parse_package_instantiation(stream, diagnostics)
+            .map(|decl| vec![Declaration::Package(decl)]),
+        For => parse_configuration_specification(stream, diagnostics)
+            .map(|decl| vec![Declaration::Configuration(decl)]),
+        View => parse_mode_view_declaration(stream, diagnostics)
+            .map(|decl| vec![Declaration::View(decl)]),
+        File => parse_file_declaration(stream)
+            .map(|decls| decls.into_iter().map(Declaration::File).collect()),
+        Shared | Constant | Signal | Variable => parse_object_declaration(stream)
+            .map(|decls| decls.into_iter().map(Declaration::Object).collect()),
+        Attribute => parse_attribute(stream)
+            .map(|decls| decls.into_iter().map(Declaration::Attribute).collect()),
+        Use => parse_use_clause(stream, diagnostics).map(|decl| vec![Declaration::Use(decl)]),
+        Alias => parse_alias_declaration(stream).map(|decl| vec![Declaration::Alias(decl)]),
+        Disconnect => parse_disconnection_specification(stream)
+            .map(|decl| vec![Declaration::Disconnection(decl)]),
+        Group => parse_group(stream).map(|decl| vec![decl]),
+        Property | Sequence | Default => {
+            parse_psl_declaration(stream).map(|decl| vec![Declaration::Psl(decl)])
+        }
+        _ => return Err(token.kinds_error(&expected_declaration_kinds(stream))),
+    };
+
+    match decls {
+        Ok(mut decls) => declarations.append(&mut decls),
+        Err(err) => {
+            diagnostics.push(err);
+            skip_construct(stream, start, RecoverContext::Declaration)?;
+        }
+    }
+    Ok(())
+}
+
+fn expected_declaration_kinds(stream: &TokenStream) -> Vec<Kind> {
+    let mut expected = vec![
+        Type, Subtype, Component, Impure, Pure, Function, Procedure, Package, For, File, Shared,
+        Constant, Signal, Variable, Attribute, Use, Alias,
+    ];
+    if stream.standard() >= VHDLStandard::VHDL2019 {
+        expected.push(View);
+    }
+    expected
+}
+
 pub fn parse_declarative_part(
     stream: &TokenStream,
     diagnostics: &mut dyn DiagnosticHandler,
 ) -> ParseResult<Vec<Declaration>> {
     let mut declarations: Vec<Declaration> = Vec::new();
 
-    fn is_recover_token(kind: Kind) -> bool {
-        matches!(
-            kind,
-            Type | Subtype
-                | Component
-                | Impure
-                | Pure
-                | Function
-                | Procedure
-                | Package
-                | For
-                | File
-                | Shared
-                | Constant
-                | Signal
-                | Variable
-                | Attribute
-                | Use
-                | Alias
-                | Begin
-                | End
-        )
-    }
-
     while let Some(token) = stream.peek() {
         match token.kind {
             Begin | End => break,
-            Type | Subtype | Component | Impure | Pure | Function | Procedure | Package | For => {
-                let decl = match token.kind {
-                    Type | Subtype => {
-                        parse_type_declaration(stream, diagnostics).map(Declaration::Type)?
-                    }
-                    Component => parse_component_declaration(stream, diagnostics)
-                        .map(Declaration::Component)?,
-                    Impure | Pure | Function | Procedure => parse_subprogram(stream, diagnostics)?,
-                    Package => parse_package_instantiation(stream, diagnostics)
-                        .map(Declaration::Package)?,
-                    For => parse_configuration_specification(stream, diagnostics)
-                        .map(Declaration::Configuration)?,
-                    _ => unreachable!(),
-                };
-                declarations.push(decl);
-            }
-
-            File | Shared | Constant | Signal | Variable | Attribute => {
-                let decls: ParseResult<Vec<Declaration>> = match token.kind {
-                    File => parse_file_declaration(stream)
-                        .map(|decls| decls.into_iter().map(Declaration::File).collect()),
-                    Shared | Constant | Signal | Variable => parse_object_declaration(stream)
-                        .map(|decls| decls.into_iter().map(Declaration::Object).collect()),
-                    Attribute => parse_attribute(stream)
-                        .map(|decls| decls.into_iter().map(Declaration::Attribute).collect()),
-                    _ => unreachable!(),
-                };
-                match decls.or_recover_until(stream, diagnostics, is_recover_token) {
-                    Ok(ref mut decls) => declarations.append(decls),
-                    Err(err) => {
-                        diagnostics.push(err);
-                        continue;
-                    }
-                }
-            }
-
-            Use | Alias => {
-                let decl: ParseResult<Declaration> = match token.kind {
-                    Use => parse_use_clause(stream, diagnostics).map(Declaration::Use),
-                    Alias => parse_alias_declaration(stream).map(Declaration::Alias),
-                    _ => unreachable!(),
-                };
-                match decl.or_recover_until(stream, diagnostics, is_recover_token) {
-                    Ok(decl) => declarations.push(decl),
-                    Err(err) => {
-                        diagnostics.push(err);
-                        continue;
-                    }
-                }
+            kind if is_declaration_start(kind) => {
+                parse_declaration(stream, diagnostics, &mut declarations)?;
             }
-
             _ => {
-                diagnostics.push(token.kinds_error(&[
-                    Type, Subtype, Component, Impure, Pure, Function, Procedure, Package, For,
-                    File, Shared, Constant, Signal, Variable, Attribute, Use, Alias,
-                ]));
+                diagnostics.push(token.kinds_error(&expected_declaration_kinds(stream)));
                 stream.skip_until(is_recover_token)?;
                 continue;
             }
@@ -165,6 +192,7 @@ mod tests {
     use crate::ast::{ObjectClass, ObjectDeclaration};
     use crate::data::Diagnostic;
     use crate::syntax::test::Code;
+    use assert_matches::assert_matches;
 
     #[test]
     fn package_instantiation() {
@@ -228,6 +256,7 @@ constant x: natural := 5;
                 class: ObjectClass::Constant,
                 ident: code.s1("x").decl_ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: Some(code.s1("5").expr())
             })])
         );
@@ -251,4 +280,32 @@ constant x: natural := 5;
         let (decl, _) = code.with_partial_stream_diagnostics(parse_declarative_part);
         assert!(decl.is_err());
     }
+
+    #[test]
+    fn parse_declarative_part_recover_from_broken_subprogram() {
+        let code = Code::new(
+            "\
+function func return natural is
+begin
+  return 1 + ;
+end function;
+type rec_t is record
+  a : ;
+end record;
+constant x: natural := 5;
+",
+        );
+        let (decls, msgs) = code.with_partial_stream_diagnostics(parse_declarative_part);
+        let decls = decls.unwrap();
+        // The subprogram body recovers from the broken statement by itself
+        assert_eq!(decls.len(), 2);
+        assert_matches!(decls[0], Declaration::SubprogramBody(_));
+        assert_eq!(
+            decls[1],
+            code.s1("constant x: natural := 5;")
+                .declarative_part()
+                .remove(0)
+        );
+        assert_eq!(msgs.len(), 2);
+    }
 }
@@ -16,6 +16,7 @@ use super::context::{
 };
 use super::declarative_part::{parse_declarative_part, parse_package_instantiation};
 use super::interface_declaration::parse_generic_interface_list;
+use super::psl::parse_verification_unit;
 use crate::ast::*;
 use crate::data::*;
 
@@ -228,6 +229,15 @@ pub fn parse_design_file(
                 Err(diagnostic) => diagnostics.push(diagnostic),
             },
 
+            Vunit | Vprop | Vmode => match parse_verification_unit(stream, diagnostics) {
+                Ok(mut unit) => {
+                    let tokens = stream.slice_tokens();
+                    unit.context_clause = take_context_clause(&mut context_clause);
+                    design_units.push((tokens, AnyDesignUnit::Primary(AnyPrimaryUnit::VerificationUnit(unit))));
+                }
+                Err(diagnostic) => diagnostics.push(diagnostic),
+            },
+
             Configuration => match parse_configuration_declaration(stream, diagnostics) {
                 Ok(mut configuration) => {
                     let tokens = stream.slice_tokens();
@@ -0,0 +1,82 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::common::ParseResult;
+use super::expression::parse_expression;
+use super::names::{parse_name, parse_type_mark};
+use super::tokens::{Kind::*, TokenSpan, TokenStream};
+use crate::ast::*;
+
+/// LRM 7.4 guarded_signal_specification
+fn parse_guarded_signal_list(stream: &TokenStream) -> ParseResult<GuardedSignalList> {
+    if stream.skip_if_kind(All) {
+        Ok(GuardedSignalList::All)
+    } else if stream.skip_if_kind(Others) {
+        Ok(GuardedSignalList::Others)
+    } else {
+        let mut names = vec![parse_name(stream)?];
+        while stream.skip_if_kind(Comma) {
+            names.push(parse_name(stream)?);
+        }
+        Ok(GuardedSignalList::Names(names))
+    }
+}
+
+/// LRM 7.4 Disconnection specification
+pub fn parse_disconnection_specification(
+    stream: &TokenStream,
+) -> ParseResult<DisconnectionSpecification> {
+    let start_token = stream.expect_kind(Disconnect)?;
+    let signals = parse_guarded_signal_list(stream)?;
+    stream.expect_kind(Colon)?;
+    let type_mark = parse_type_mark(stream)?;
+    stream.expect_kind(After)?;
+    let after = parse_expression(stream)?;
+    let end_token = stream.expect_kind(SemiColon)?;
+
+    Ok(DisconnectionSpecification {
+        span: TokenSpan::new(start_token, end_token),
+        signals,
+        type_mark,
+        after,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn parses_disconnection_specification() {
+        let code = Code::new("disconnect s1, s2 : bit after 5 ns;");
+
+        assert_eq!(
+            code.with_stream(parse_disconnection_specification),
+            DisconnectionSpecification {
+                span: code.token_span(),
+                signals: GuardedSignalList::Names(vec![code.s1("s1").name(), code.s1("s2").name()]),
+                type_mark: code.s1("bit").type_mark(),
+                after: code.s1("5 ns").expr(),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_disconnection_specification_for_all_and_others() {
+        let code = Code::new("disconnect all : std_logic after 0 ns;");
+        assert_eq!(
+            code.with_stream(parse_disconnection_specification).signals,
+            GuardedSignalList::All
+        );
+
+        let code = Code::new("disconnect others : std_logic after 0 ns;");
+        assert_eq!(
+            code.with_stream(parse_disconnection_specification).signals,
+            GuardedSignalList::Others
+        );
+    }
+}
@@ -0,0 +1,152 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::attributes::parse_entity_class;
+use super::common::ParseResult;
+use super::names::parse_name;
+use super::tokens::{Kind::*, TokenSpan, TokenStream};
+use crate::ast::{self, *};
+use crate::data::*;
+
+/// LRM 6.9 entity_class_entry_list
+fn parse_entity_class_entry_list(stream: &TokenStream) -> ParseResult<Vec<EntityClassEntry>> {
+    stream.expect_kind(LeftPar)?;
+    let mut entries = Vec::new();
+    loop {
+        let class = parse_entity_class(stream)?;
+        let is_box = stream.skip_if_kind(BOX);
+        entries.push(EntityClassEntry { class, is_box });
+
+        expect_token!(
+            stream,
+            token,
+            Comma => continue,
+            RightPar => break
+        );
+    }
+    Ok(entries)
+}
+
+/// LRM 6.9 Group template declarations and 6.10 Group declarations
+pub fn parse_group(stream: &TokenStream) -> ParseResult<Declaration> {
+    let start_token = stream.expect_kind(Group)?;
+    let ident = WithDecl::new(stream.expect_ident()?);
+    expect_token!(
+        stream,
+        token,
+        Is => {
+            let entity_classes = parse_entity_class_entry_list(stream)?;
+            let end_token = stream.expect_kind(SemiColon)?;
+            Ok(Declaration::GroupTemplate(GroupTemplateDeclaration {
+                span: TokenSpan::new(start_token, end_token),
+                ident,
+                entity_classes,
+            }))
+        },
+        Colon => {
+            let template_name = parse_name(stream)?;
+            let end_token = stream.expect_kind(SemiColon)?;
+            let (template_name, constituents) = split_group_constituents(template_name)?;
+            Ok(Declaration::Group(GroupDeclaration {
+                span: TokenSpan::new(start_token, end_token),
+                ident,
+                template_name,
+                constituents,
+            }))
+        }
+    )
+}
+
+/// The constituent list of a group declaration parses as the parameters of a function call
+fn split_group_constituents(
+    name: WithPos<Name>,
+) -> ParseResult<(WithPos<Name>, Vec<WithPos<Name>>)> {
+    let Name::CallOrIndexed(call) = name.item else {
+        return Err(Diagnostic::error(
+            name.pos,
+            "Expected group constituent list",
+        ));
+    };
+    let CallOrIndexed { name, parameters } = *call;
+
+    let mut constituents = Vec::with_capacity(parameters.len());
+    for parameter in parameters {
+        if let Some(formal) = parameter.formal {
+            return Err(Diagnostic::error(formal.pos, "Expected group constituent"));
+        }
+        let actual = parameter.actual;
+        let constituent = match actual.item {
+            ActualPart::Expression(Expression::Name(name)) => WithPos::new(*name, actual.pos),
+            ActualPart::Expression(Expression::Literal(ast::Literal::Character(chr))) => {
+                WithPos::new(
+                    Name::Designator(Designator::Character(chr).into_ref()),
+                    actual.pos,
+                )
+            }
+            _ => return Err(Diagnostic::error(actual.pos, "Expected group constituent")),
+        };
+        constituents.push(constituent);
+    }
+    Ok((name, constituents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn parses_group_template_declaration() {
+        let code = Code::new("group pin2pin is (signal, signal <>);");
+        assert_eq!(
+            code.with_stream(parse_group),
+            Declaration::GroupTemplate(GroupTemplateDeclaration {
+                span: code.token_span(),
+                ident: code.s1("pin2pin").decl_ident(),
+                entity_classes: vec![
+                    EntityClassEntry {
+                        class: EntityClass::Signal,
+                        is_box: false
+                    },
+                    EntityClassEntry {
+                        class: EntityClass::Signal,
+                        is_box: true
+                    }
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn parses_group_declaration() {
+        let code = Code::new("group path : pin2pin (clk, q, '1');");
+        assert_eq!(
+            code.with_stream(parse_group),
+            Declaration::Group(GroupDeclaration {
+                span: code.token_span(),
+                ident: code.s1("path").decl_ident(),
+                template_name: code.s1("pin2pin").name(),
+                constituents: vec![
+                    code.s1("clk").name(),
+                    code.s1("q").name(),
+                    code.s1("'1'").name()
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn group_declaration_requires_constituents() {
+        let code = Code::new("group path : pin2pin;");
+        assert_eq!(
+            code.parse(parse_group),
+            Err(Diagnostic::error(
+                code.s1("pin2pin"),
+                "Expected group constituent list"
+            ))
+        );
+    }
+}
@@ -4,8 +4,10 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use super::common::ParseResult;
-use super::names::{parse_association_list_no_leftpar, parse_identifier_list, parse_selected_name};
+use super::common::{check_standard, ParseResult};
+use super::names::{
+    parse_association_list_no_leftpar, parse_identifier_list, parse_name, parse_selected_name,
+};
 use super::object_declaration::parse_optional_assignment;
 use super::subprogram::parse_subprogram_specification;
 use super::subtype_indication::parse_subtype_indication;
@@ -13,6 +15,7 @@ use super::tokens::{Kind::*, *};
 /// LRM 6.5 Interface declarations
 use crate::ast::*;
 use crate::data::*;
+use crate::standard::VHDLStandard;
 
 fn parse_optional_mode(stream: &TokenStream) -> ParseResult<Option<WithPos<Mode>>> {
     let token = stream.peek_expect()?;
@@ -100,6 +103,10 @@ fn parse_interface_object_declaration(
 
     stream.expect_kind(Colon)?;
 
+    if stream.next_kind_is(View) {
+        return parse_interface_view_declaration(stream, list_type, explicit_object_class, idents);
+    }
+
     let mode_with_pos = parse_optional_mode(stream)?;
     let mode = mode_with_pos
         .as_ref()
@@ -120,8 +127,18 @@ fn parse_interface_object_declaration(
     };
 
     let subtype = parse_subtype_indication(stream)?;
+    let bus = stream.pop_if_kind(Bus);
     let expr = parse_optional_assignment(stream)?;
 
+    if let Some(bus) = bus {
+        if object_class != ObjectClass::Signal {
+            return Err(Diagnostic::error(
+                stream.get_token(bus),
+                "Only interface signals may be declared with bus",
+            ));
+        }
+    }
+
     // @TODO maybe move this to a semantic check?
     for ident in idents.iter() {
         if object_class == ObjectClass::Constant && mode != Mode::In {
@@ -154,11 +171,87 @@ fn parse_interface_object_declaration(
         .map(|ident| {
             InterfaceDeclaration::Object(InterfaceObjectDeclaration {
                 list_type,
-                mode,
                 class: object_class,
                 ident: ident.into(),
-                subtype_indication: subtype.clone(),
-                expression: expr.clone(),
+                mode: ModeIndication::Simple(SimpleModeIndication {
+                    mode,
+                    subtype_indication: subtype.clone(),
+                    bus: bus.is_some(),
+                    expression: expr.clone(),
+                }),
+            })
+        })
+        .collect())
+}
+
+/// Parse the name of a mode view, either `view_name` or `(view_name)` for an array of records
+pub fn parse_view_name(
+    stream: &TokenStream,
+) -> ParseResult<(ModeViewIndicationKind, WithPos<Name>)> {
+    if stream.skip_if_kind(LeftPar) {
+        let name = parse_name(stream)?;
+        stream.expect_kind(RightPar)?;
+        Ok((ModeViewIndicationKind::Array, name))
+    } else {
+        Ok((ModeViewIndicationKind::Record, parse_name(stream)?))
+    }
+}
+
+/// LRM 6.5.2 mode_view_indication
+fn parse_mode_view_indication(stream: &TokenStream) -> ParseResult<ModeViewIndication> {
+    stream.expect_kind(View)?;
+    let (kind, name) = parse_view_name(stream)?;
+    // The subtype is optional for a record but an array needs it to know the array type
+    let subtype_indication = if kind == ModeViewIndicationKind::Array {
+        stream.expect_kind(Of)?;
+        Some(parse_subtype_indication(stream)?)
+    } else if stream.skip_if_kind(Of) {
+        Some(parse_subtype_indication(stream)?)
+    } else {
+        None
+    };
+
+    Ok(ModeViewIndication {
+        kind,
+        name,
+        subtype_indication,
+    })
+}
+
+fn parse_interface_view_declaration(
+    stream: &TokenStream,
+    list_type: InterfaceType,
+    explicit_object_class: Option<WithPos<ObjectClass>>,
+    idents: Vec<Ident>,
+) -> ParseResult<Vec<InterfaceDeclaration>> {
+    let view_pos = stream.peek_expect()?.pos.clone();
+    let view = parse_mode_view_indication(stream)?;
+
+    let object_class = match explicit_object_class {
+        Some(class) if class.item != ObjectClass::Signal => {
+            return Err(Diagnostic::error(
+                class.pos,
+                "Mode view indication is only allowed for signal ports and parameters",
+            ));
+        }
+        _ => ObjectClass::Signal,
+    };
+
+    if list_type == InterfaceType::Generic {
+        return Err(Diagnostic::error(
+            view_pos,
+            "Mode view indication is only allowed for signal ports and parameters",
+        ));
+    }
+
+    Ok(idents
+        .into_iter()
+        .map(|ident| {
+            InterfaceDeclaration::Object(InterfaceObjectDeclaration {
+                list_type,
+                class: object_class,
+                ident: ident.into(),
+                mode: ModeIndication::View(view.clone()),
             })
         })
         .collect())
@@ -235,30 +328,33 @@ fn parse_interface_declaration(
         },
         File => parse_interface_file_declaration(stream),
         Type => {
+            check_standard(stream, token, "Generic type", VHDLStandard::VHDL2008, diagnostics);
             stream.skip();
             let ident = stream.expect_ident()?;
             Ok(vec![InterfaceDeclaration::Type(WithDecl::new(ident))])
         },
         Function | Procedure | Impure | Pure => {
+            check_standard(stream, token, "Generic subprogram", VHDLStandard::VHDL2008, diagnostics);
             let spec = parse_subprogram_specification(stream, diagnostics)?;
             let default = parse_subprogram_default(stream)?;
 
             Ok(vec![InterfaceDeclaration::Subprogram(spec, default)])
         },
         Package => {
+            check_standard(stream, token, "Generic package", VHDLStandard::VHDL2008, diagnostics);
             Ok(vec![InterfaceDeclaration::Package (parse_interface_package(stream, diagnostics)?)])
         }
     )
 }
 
 /// Parse ; separator in generic or port lists.
-/// Expect ; for all but the last item
+/// Expect ; for all but the last item, VHDL-2019 also allows it after the last item
 fn parse_semicolon_separator(stream: &TokenStream) -> ParseResult<()> {
     peek_token!(
         stream, token,
         SemiColon => {
             stream.skip();
-            if stream.next_kind_is(RightPar) {
+            if stream.next_kind_is(RightPar) && stream.standard() < VHDLStandard::VHDL2019 {
                 return Err(Diagnostic::error(&token.pos,
                         format!("Last interface element may not end with {}",
                         kinds_str(&[SemiColon]))));
@@ -426,24 +522,61 @@ mod tests {
             vec![
                 InterfaceDeclaration::Object(InterfaceObjectDeclaration {
                     list_type: InterfaceType::Generic,
-                    mode: Mode::In,
                     class: ObjectClass::Constant,
                     ident: code.s1("foo").decl_ident(),
-                    subtype_indication: code.s1("natural").subtype_indication(),
-                    expression: None
+                    mode: ModeIndication::Simple(SimpleModeIndication {
+                        mode: Mode::In,
+                        subtype_indication: code.s1("natural").subtype_indication(),
+                        bus: false,
+                        expression: None
+                    })
                 }),
                 InterfaceDeclaration::Object(InterfaceObjectDeclaration {
                     list_type: InterfaceType::Generic,
-                    mode: Mode::In,
                     class: ObjectClass::Constant,
                     ident: code.s1("bar").decl_ident(),
-                    subtype_indication: code.s1("natural").subtype_indication(),
-                    expression: None
+                    mode: ModeIndication::Simple(SimpleModeIndication {
+                        mode: Mode::In,
+                        subtype_indication: code.s1("natural").subtype_indication(),
+                        bus: false,
+                        expression: None
+                    })
                 })
             ]
         );
     }
 
+    #[test]
+    fn parses_bus_port() {
+        let code = Code::new("signal foo : inout resolved_bit bus");
+        assert_eq!(
+            code.with_stream(parse_port),
+            InterfaceDeclaration::Object(InterfaceObjectDeclaration {
+                list_type: InterfaceType::Port,
+                class: ObjectClass::Signal,
+                ident: code.s1("foo").decl_ident(),
+                mode: ModeIndication::Simple(SimpleModeIndication {
+                    mode: Mode::InOut,
+                    subtype_indication: code.s1("resolved_bit").subtype_indication(),
+                    bus: true,
+                    expression: None
+                })
+            })
+        );
+    }
+
+    #[test]
+    fn bus_is_only_allowed_for_interface_signals() {
+        let code = Code::new("constant foo : in natural bus");
+        assert_eq!(
+            code.with_stream_err(parse_parameter),
+            Diagnostic::error(
+                code.s1("bus"),
+                "Only interface signals may be declared with bus"
+            )
+        );
+    }
+
     #[test]
     fn parses_generic() {
         let code = Code::new("foo : std_logic");
@@ -451,11 +584,14 @@ mod tests {
             code.with_stream(parse_generic),
             InterfaceDeclaration::Object(InterfaceObjectDeclaration {
                 list_type: InterfaceType::Generic,
-                mode: Mode::In,
                 class: ObjectClass::Constant,
                 ident: code.s1("foo").decl_ident(),
-                subtype_indication: code.s1("std_logic").subtype_indication(),
-                expression: None
+                mode: ModeIndication::Simple(SimpleModeIndication {
+                    mode: Mode::In,
+                    subtype_indication: code.s1("std_logic").subtype_indication(),
+                    bus: false,
+                    expression: None
+                })
             })
         );
     }
@@ -533,11 +669,14 @@ mod tests {
             code.with_stream(parse_port),
             InterfaceDeclaration::Object(InterfaceObjectDeclaration {
                 list_type: InterfaceType::Port,
-                mode: Mode::In,
                 class: ObjectClass::Signal,
                 ident: code.s1("foo").decl_ident(),
-                subtype_indication: code.s1("std_logic").subtype_indication(),
-                expression: None
+                mode: ModeIndication::Simple(SimpleModeIndication {
+                    mode: Mode::In,
+                    subtype_indication: code.s1("std_logic").subtype_indication(),
+                    bus: false,
+                    expression: None
+                })
             })
         );
     }
@@ -553,7 +692,7 @@ mod tests {
     fn parses_port_without_explicit_class() {
         let code = Code::new("foo : std_logic");
         let result = to_interface_object(code.with_stream(parse_port));
-        assert_eq!(result.mode, Mode::In);
+        assert_eq!(result.mode.simple_mode(), Some(Mode::In));
         assert_eq!(result.class, ObjectClass::Signal);
     }
 
@@ -561,7 +700,7 @@ mod tests {
     fn parses_generic_without_explicit_class() {
         let code = Code::new("foo : std_logic");
         let result = to_interface_object(code.with_stream(parse_generic));
-        assert_eq!(result.mode, Mode::In);
+        assert_eq!(result.mode.simple_mode(), Some(Mode::In));
         assert_eq!(result.class, ObjectClass::Constant);
     }
 
@@ -575,22 +714,22 @@ mod tests {
         // @TODO forbid mode != in for function
         let code = Code::new("foo : std_logic");
         let result = to_interface_object(code.with_stream(parse_parameter));
-        assert_eq!(result.mode, Mode::In);
+        assert_eq!(result.mode.simple_mode(), Some(Mode::In));
         assert_eq!(result.class, ObjectClass::Constant);
 
         let code = Code::new("foo : in std_logic");
         let result = to_interface_object(code.with_stream(parse_parameter));
-        assert_eq!(result.mode, Mode::In);
+        assert_eq!(result.mode.simple_mode(), Some(Mode::In));
         assert_eq!(result.class, ObjectClass::Constant);
 
         let code = Code::new("foo : out std_logic");
         let result = to_interface_object(code.with_stream(parse_parameter));
-        assert_eq!(result.mode, Mode::Out);
+        assert_eq!(result.mode.simple_mode(), Some(Mode::Out));
         assert_eq!(result.class, ObjectClass::Variable);
 
         let code = Code::new("foo : inout std_logic");
         let result = to_interface_object(code.with_stream(parse_parameter));
-        assert_eq!(result.mode, Mode::InOut);
+        assert_eq!(result.mode.simple_mode(), Some(Mode::InOut));
         assert_eq!(result.class, ObjectClass::Variable);
     }
 
@@ -601,11 +740,14 @@ mod tests {
             code.with_stream(parse_generic),
             InterfaceDeclaration::Object(InterfaceObjectDeclaration {
                 list_type: InterfaceType::Generic,
-                mode: Mode::In,
                 class: ObjectClass::Constant,
                 ident: code.s1("foo").decl_ident(),
-                subtype_indication: code.s1("std_logic").subtype_indication(),
-                expression: None
+                mode: ModeIndication::Simple(SimpleModeIndication {
+                    mode: Mode::In,
+                    subtype_indication: code.s1("std_logic").subtype_indication(),
+                    bus: false,
+                    expression: None
+                })
             })
         );
     }
@@ -617,15 +759,77 @@ mod tests {
             code.with_stream(parse_port),
             InterfaceDeclaration::Object(InterfaceObjectDeclaration {
                 list_type: InterfaceType::Port,
-                mode: Mode::In,
                 class: ObjectClass::Signal,
                 ident: code.s1("foo").decl_ident(),
-                subtype_indication: code.s1("std_logic").subtype_indication(),
-                expression: None
+                mode: ModeIndication::Simple(SimpleModeIndication {
+                    mode: Mode::In,
+                    subtype_indication: code.s1("std_logic").subtype_indication(),
+                    bus: false,
+                    expression: None
+                })
+            })
+        );
+    }
+
+    #[test]
+    fn parses_port_with_mode_view() {
+        let code = Code::new("(foo, bar : view master'converse of bus_t)")
+            .with_standard(VHDLStandard::VHDL2019);
+        let view = ModeViewIndication {
+            kind: ModeViewIndicationKind::Record,
+            name: code.s1("master'converse").name(),
+            subtype_indication: Some(code.s1("bus_t").subtype_indication()),
+        };
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_port_interface_list),
+            vec![
+                InterfaceDeclaration::Object(InterfaceObjectDeclaration {
+                    list_type: InterfaceType::Port,
+                    class: ObjectClass::Signal,
+                    ident: code.s1("foo").decl_ident(),
+                    mode: ModeIndication::View(view.clone()),
+                }),
+                InterfaceDeclaration::Object(InterfaceObjectDeclaration {
+                    list_type: InterfaceType::Port,
+                    class: ObjectClass::Signal,
+                    ident: code.s1("bar").decl_ident(),
+                    mode: ModeIndication::View(view),
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_port_with_array_mode_view() {
+        let code =
+            Code::new("foo : view (master) of bus_arr_t").with_standard(VHDLStandard::VHDL2019);
+        assert_eq!(
+            code.with_stream(parse_port),
+            InterfaceDeclaration::Object(InterfaceObjectDeclaration {
+                list_type: InterfaceType::Port,
+                class: ObjectClass::Signal,
+                ident: code.s1("foo").decl_ident(),
+                mode: ModeIndication::View(ModeViewIndication {
+                    kind: ModeViewIndicationKind::Array,
+                    name: code.s1("master").name(),
+                    subtype_indication: Some(code.s1("bus_arr_t").subtype_indication()),
+                }),
             })
         );
     }
 
+    #[test]
+    fn mode_view_is_not_allowed_for_generics() {
+        let code = Code::new("foo : view master").with_standard(VHDLStandard::VHDL2019);
+        assert_eq!(
+            code.with_partial_stream(parse_generic),
+            Err(Diagnostic::error(
+                code.s1("view").pos(),
+                "Mode view indication is only allowed for signal ports and parameters"
+            ))
+        );
+    }
+
     #[test]
     fn parse_generic_non_in_mode_error() {
         let code = Code::new("foo : out boolean");
@@ -681,6 +885,54 @@ bar : natural)",
         );
     }
 
+    #[test]
+    fn test_parse_port_interface_list_trailing_semi_colon_vhdl_2019() {
+        let code = Code::new(
+            "\
+(signal foo : in std_logic;
+ bar : out natural;
+)",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+        let (result, diagnostics) = code.with_stream_diagnostics(parse_port_interface_list);
+
+        assert_eq!(
+            result,
+            vec![
+                code.s1("signal foo : in std_logic").port(),
+                code.s1("bar : out natural").port()
+            ]
+        );
+        assert_eq!(diagnostics, vec![]);
+    }
+
+    #[test]
+    fn generic_types_and_subprograms_require_vhdl_2008() {
+        let code = Code::new(
+            "\
+(type T;
+ function f return T;
+ constant c : natural)",
+        )
+        .with_standard(VHDLStandard::VHDL2002);
+        let (result, diagnostics) = code.with_stream_diagnostics(parse_generic_interface_list);
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::error(
+                    code.s1("type"),
+                    "Generic type requires VHDL-2008 or later, the library uses VHDL-2002"
+                ),
+                Diagnostic::error(
+                    code.s1("function"),
+                    "Generic subprogram requires VHDL-2008 or later, the library uses VHDL-2002"
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_port_interface_list() {
         let code = Code::new(
@@ -938,6 +1190,7 @@ function foo() return bit;
                     header: None,
                     param_tok: None,
                     parameter_list: vec![],
+                    return_identifier: None,
                     return_type: code.s1("bit").type_mark()
                 })
             }
@@ -0,0 +1,526 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! PSL as embedded in VHDL-2008 (LRM 15.10 and IEEE 1850 Annex B)
+//!
+//! PSL expressions are not parsed into a tree,
+//! only the VHDL names they reference are extracted so that they can be resolved.
+
+use super::common::{parse_optional, ParseResult};
+use super::concurrent_statement::parse_labeled_concurrent_statement;
+use super::declarative_part::{is_declaration_start, parse_declaration};
+use super::expression::parse_expression;
+use super::tokens::{Kind, Kind::*, TokenSpan, TokenStream};
+use crate::ast::*;
+use crate::data::*;
+
+/// PSL keywords and built-in functions that are tokenized as VHDL identifiers
+const PSL_BUILTINS: &[&str] = &[
+    "abort",
+    "always",
+    "async_abort",
+    "before",
+    "before_",
+    "boolean",
+    "clock",
+    "const",
+    "countones",
+    "ended",
+    "eventually",
+    "fell",
+    "forall",
+    "hdl_type",
+    "inf",
+    "isunknown",
+    "never",
+    "next_a",
+    "next_e",
+    "next_event",
+    "next_event_a",
+    "next_event_e",
+    "nondet",
+    "nondet_vector",
+    "numeric",
+    "onehot",
+    "onehot0",
+    "prev",
+    "rose",
+    "stable",
+    "string",
+    "sync_abort",
+    "union",
+    "until_",
+    "within",
+];
+
+fn is_psl_builtin(ident: &Ident) -> bool {
+    let name = ident.item.name_utf8().to_ascii_lowercase();
+    PSL_BUILTINS.contains(&name.as_str())
+}
+
+/// Parse a PSL expression until one of the terminator kinds is found outside of any parenthesis.
+/// The terminator is not consumed.
+fn parse_psl_expression(stream: &TokenStream, terminators: &[Kind]) -> ParseResult<PslExpression> {
+    let mut names = Vec::new();
+    // Variables introduced by forall replication
+    let mut bound: Vec<Symbol> = Vec::new();
+    let mut depth = 0_usize;
+    let mut prev_kind = None;
+    let mut is_forall_variable = false;
+
+    loop {
+        let token = stream.peek_expect()?;
+        if depth == 0 && terminators.contains(&token.kind) {
+            break;
+        }
+
+        match token.kind {
+            LeftPar | LeftSquare | LeftCurly => depth += 1,
+            RightPar | RightSquare | RightCurly | SemiColon if depth == 0 => {
+                return Err(token.kinds_error(terminators));
+            }
+            RightPar | RightSquare | RightCurly => depth -= 1,
+            Identifier => {
+                let ident = token.to_identifier_value()?;
+                if is_forall_variable {
+                    bound.push(ident.item);
+                    is_forall_variable = false;
+                } else if is_psl_builtin(&ident) {
+                    is_forall_variable = ident.item.name_utf8().eq_ignore_ascii_case("forall");
+                } else if !matches!(prev_kind, Some(Dot | Tick)) && !bound.contains(&ident.item) {
+                    names.push(ident.map_into(|sym| WithRef::new(Designator::Identifier(sym))));
+                }
+            }
+            _ => {}
+        }
+
+        prev_kind = Some(token.kind);
+        stream.skip();
+    }
+
+    Ok(PslExpression { names })
+}
+
+/// Skip a balanced parenthesized list such as the subtype of a `hdl_type(...)` parameter
+fn skip_parenthesized(stream: &TokenStream) -> ParseResult<()> {
+    stream.expect_kind(LeftPar)?;
+    let mut depth = 1_usize;
+    while depth > 0 {
+        let token = stream.peek_expect()?;
+        match token.kind {
+            LeftPar => depth += 1,
+            RightPar => depth -= 1,
+            _ => {}
+        }
+        stream.skip();
+    }
+    Ok(())
+}
+
+/// IEEE 1850 6.1.1.1 and 6.2.4 formal parameter list, `(param_type name {, name} {; ...})`
+fn parse_psl_parameters(stream: &TokenStream) -> ParseResult<Vec<WithDecl<Ident>>> {
+    let mut parameters = Vec::new();
+    if !stream.skip_if_kind(LeftPar) {
+        return Ok(parameters);
+    }
+
+    loop {
+        expect_token!(stream, token, Identifier | Property | Sequence => {});
+        if stream.next_kind_is(LeftPar) {
+            skip_parenthesized(stream)?;
+        }
+
+        loop {
+            parameters.push(WithDecl::new(stream.expect_ident()?));
+            if !stream.skip_if_kind(Comma) {
+                break;
+            }
+        }
+
+        expect_token!(
+            stream,
+            token,
+            SemiColon => continue,
+            RightPar => break
+        );
+    }
+
+    Ok(parameters)
+}
+
+/// LRM 15.10 PSL_Property_Declaration, PSL_Sequence_Declaration and PSL_Clock_Declaration
+pub fn parse_psl_declaration(stream: &TokenStream) -> ParseResult<PslDeclaration> {
+    let start_token = stream.get_current_token_id();
+    let token = stream.peek_expect()?;
+    match token.kind {
+        Property | Sequence => {
+            let kind = if token.kind == Property {
+                PslNamedKind::Property
+            } else {
+                PslNamedKind::Sequence
+            };
+            stream.skip();
+            let ident = WithDecl::new(stream.expect_ident()?);
+            let parameters = parse_psl_parameters(stream)?;
+            stream.expect_kind(Is)?;
+            let expression = parse_psl_expression(stream, &[SemiColon])?;
+            let end_token = stream.expect_kind(SemiColon)?;
+            Ok(PslDeclaration::Named(PslNamedDeclaration {
+                span: TokenSpan::new(start_token, end_token),
+                kind,
+                ident,
+                parameters,
+                expression,
+            }))
+        }
+        Default => {
+            stream.skip();
+            let clock = stream.expect_ident()?;
+            if !clock.item.name_utf8().eq_ignore_ascii_case("clock") {
+                return Err(Diagnostic::error(&clock.pos, "Expected 'clock'"));
+            }
+            stream.expect_kind(Is)?;
+            let clock = parse_psl_expression(stream, &[SemiColon])?;
+            let end_token = stream.expect_kind(SemiColon)?;
+            Ok(PslDeclaration::DefaultClock(PslDefaultClock {
+                span: TokenSpan::new(start_token, end_token),
+                clock,
+            }))
+        }
+        _ => Err(token.kinds_error(&[Property, Sequence, Default])),
+    }
+}
+
+/// LRM 11.1 PSL_Directive, the directive keyword is the next token
+pub fn parse_psl_directive(stream: &TokenStream) -> ParseResult<PslDirective> {
+    let kind = expect_token!(
+        stream,
+        token,
+        Assert => PslDirectiveKind::Assert,
+        Assume => PslDirectiveKind::Assume,
+        AssumeGuarantee => PslDirectiveKind::AssumeGuarantee,
+        Restrict => PslDirectiveKind::Restrict,
+        RestrictGuarantee => PslDirectiveKind::RestrictGuarantee,
+        Cover => PslDirectiveKind::Cover,
+        Fairness => PslDirectiveKind::Fairness,
+        Strong => {
+            stream.expect_kind(Fairness)?;
+            PslDirectiveKind::StrongFairness
+        }
+    );
+    let property = parse_psl_expression(stream, &[SemiColon, Report, Severity])?;
+    let report = parse_optional(stream, Report, parse_expression)?;
+    let severity = parse_optional(stream, Severity, parse_expression)?;
+    stream.expect_kind(SemiColon)?;
+
+    Ok(PslDirective {
+        kind,
+        property,
+        report,
+        severity,
+    })
+}
+
+/// IEEE 1850 7.2 Verification units
+pub fn parse_verification_unit(
+    stream: &TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<VerificationUnit> {
+    let start_token = stream.get_current_token_id();
+    let kind = expect_token!(
+        stream,
+        token,
+        Vunit => VerificationUnitKind::Vunit,
+        Vprop => VerificationUnitKind::Vprop,
+        Vmode => VerificationUnitKind::Vmode
+    );
+    let ident = WithDecl::new(stream.expect_ident()?);
+
+    let binding = if stream.skip_if_kind(LeftPar) {
+        let entity_name = WithRef::new(stream.expect_ident()?);
+        let architecture_name = if stream.skip_if_kind(LeftPar) {
+            let architecture_name = stream.expect_ident()?;
+            stream.expect_kind(RightPar)?;
+            Some(WithRef::new(architecture_name))
+        } else {
+            None
+        };
+        stream.expect_kind(RightPar)?;
+        Some(VerificationUnitBinding {
+            entity_name,
+            architecture_name,
+        })
+    } else {
+        None
+    };
+
+    stream.expect_kind(LeftCurly)?;
+    let mut inherits = Vec::new();
+    let mut decl = Vec::new();
+    let mut statements = Vec::new();
+
+    loop {
+        let token = stream.peek_expect()?;
+        match token.kind {
+            RightCurly => break,
+            // A for keyword starts a for generate statement rather than a configuration specification
+            For => statements.push(parse_labeled_concurrent_statement(stream, diagnostics)?),
+            kind if is_declaration_start(kind) => {
+                parse_declaration(stream, diagnostics, &mut decl)?;
+            }
+            Identifier
+                if stream.nth_kind_is(1, Identifier)
+                    && token
+                        .to_identifier_value()?
+                        .item
+                        .name_utf8()
+                        .eq_ignore_ascii_case("inherit") =>
+            {
+                stream.skip();
+                loop {
+                    inherits.push(WithRef::new(stream.expect_ident()?));
+                    if !stream.skip_if_kind(Comma) {
+                        break;
+                    }
+                }
+                stream.expect_kind(SemiColon)?;
+            }
+            _ => statements.push(parse_labeled_concurrent_statement(stream, diagnostics)?),
+        }
+    }
+    let end_token = stream.expect_kind(RightCurly)?;
+
+    Ok(VerificationUnit {
+        span: TokenSpan::new(start_token, end_token),
+        context_clause: ContextClause::default(),
+        kind,
+        ident,
+        binding,
+        inherits,
+        decl,
+        statements,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+    use crate::VHDLStandard;
+
+    fn names(code: &Code, names: &[&str]) -> Vec<WithPos<WithRef<Designator>>> {
+        names
+            .iter()
+            .map(|name| code.s1(name).ref_designator())
+            .collect()
+    }
+
+    #[test]
+    fn parses_property_declaration_with_parameters() {
+        let code = Code::new("property p(boolean a, b; const n) is always {a; b[*n]} |=> rose(c);")
+            .with_standard(VHDLStandard::VHDL2008);
+
+        assert_eq!(
+            code.with_stream(parse_psl_declaration),
+            PslDeclaration::Named(PslNamedDeclaration {
+                span: code.token_span(),
+                kind: PslNamedKind::Property,
+                ident: code.s1("p(").s1("p").decl_ident(),
+                parameters: vec![
+                    code.s1("a,").s1("a").decl_ident(),
+                    code.s1("b;").s1("b").decl_ident(),
+                    code.s1("n)").s1("n").decl_ident()
+                ],
+                expression: PslExpression {
+                    names: vec![
+                        code.s1("{a").s1("a").ref_designator(),
+                        code.s1("b[").s1("b").ref_designator(),
+                        code.s1("*n").s1("n").ref_designator(),
+                        code.s1("(c)").s1("c").ref_designator(),
+                    ]
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn parses_sequence_declaration() {
+        let code = Code::new("sequence s is {req; ack};").with_standard(VHDLStandard::VHDL2008);
+
+        assert_eq!(
+            code.with_stream(parse_psl_declaration),
+            PslDeclaration::Named(PslNamedDeclaration {
+                span: code.token_span(),
+                kind: PslNamedKind::Sequence,
+                ident: code.s1("s is").s1("s").decl_ident(),
+                parameters: vec![],
+                expression: PslExpression {
+                    names: names(&code, &["req", "ack"])
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn parses_default_clock() {
+        let code =
+            Code::new("default clock is rising_edge(clk);").with_standard(VHDLStandard::VHDL2008);
+
+        assert_eq!(
+            code.with_stream(parse_psl_declaration),
+            PslDeclaration::DefaultClock(PslDefaultClock {
+                span: code.token_span(),
+                clock: PslExpression {
+                    names: names(&code, &["rising_edge", "clk"])
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn default_requires_clock() {
+        let code =
+            Code::new("default clk is rising_edge(clk);").with_standard(VHDLStandard::VHDL2008);
+
+        assert_eq!(
+            code.parse(parse_psl_declaration),
+            Err(Diagnostic::error(code.s1("clk"), "Expected 'clock'"))
+        );
+    }
+
+    #[test]
+    fn parses_directives() {
+        let code =
+            Code::new("cover {req; ack} report \"covered\";").with_standard(VHDLStandard::VHDL2008);
+        assert_eq!(
+            code.with_stream(parse_psl_directive),
+            PslDirective {
+                kind: PslDirectiveKind::Cover,
+                property: PslExpression {
+                    names: names(&code, &["req", "ack"])
+                },
+                report: Some(code.s1("\"covered\"").expr()),
+                severity: None,
+            }
+        );
+
+        let code = Code::new("strong fairness req, ack;").with_standard(VHDLStandard::VHDL2008);
+        assert_eq!(
+            code.with_stream(parse_psl_directive),
+            PslDirective {
+                kind: PslDirectiveKind::StrongFairness,
+                property: PslExpression {
+                    names: names(&code, &["req", "ack"])
+                },
+                report: None,
+                severity: None,
+            }
+        );
+    }
+
+    #[test]
+    fn builtins_selected_suffixes_and_forall_variables_are_not_names() {
+        let code = Code::new(
+            "assume forall i in {0 to 3} : always (rec.valid(i) -> next stable(x'last_value));",
+        )
+        .with_standard(VHDLStandard::VHDL2008);
+        assert_eq!(
+            code.with_stream(parse_psl_directive),
+            PslDirective {
+                kind: PslDirectiveKind::Assume,
+                property: PslExpression {
+                    names: vec![
+                        code.s1("rec").ref_designator(),
+                        code.s1("x'").s1("x").ref_designator()
+                    ]
+                },
+                report: None,
+                severity: None,
+            }
+        );
+    }
+
+    #[test]
+    fn concurrent_assert_falls_back_to_psl_directive() {
+        let code = Code::new("assert always req -> eventually! ack severity warning;")
+            .with_standard(VHDLStandard::VHDL2008);
+        let stmt = code.concurrent_statement();
+        assert_eq!(
+            stmt.statement.item,
+            ConcurrentStatement::Psl(PslDirective {
+                kind: PslDirectiveKind::Assert,
+                property: PslExpression {
+                    names: names(&code, &["req", "ack"])
+                },
+                report: None,
+                severity: Some(code.s1("warning").expr()),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_directive_in_psl_comment() {
+        let code = Code::new(
+            "\
+-- psl cover {req;
+--   ack};",
+        )
+        .with_standard(VHDLStandard::VHDL2008);
+        let stmt = code.concurrent_statement();
+        assert_eq!(
+            stmt.statement.item,
+            ConcurrentStatement::Psl(PslDirective {
+                kind: PslDirectiveKind::Cover,
+                property: PslExpression {
+                    names: names(&code, &["req", "ack"])
+                },
+                report: None,
+                severity: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_verification_unit() {
+        let code = Code::new(
+            "\
+vunit v (ent(arch)) {
+  inherit base, other;
+  signal tmp : bit;
+  property p is always req -> next ack;
+  lbl: cover {req; ack};
+  tmp <= req;
+}",
+        )
+        .with_standard(VHDLStandard::VHDL2008);
+
+        let unit = code.with_stream_no_diagnostics(parse_verification_unit);
+        assert_eq!(unit.span, code.token_span());
+        assert_eq!(unit.kind, VerificationUnitKind::Vunit);
+        assert_eq!(unit.ident, code.s1("v (").s1("v").decl_ident());
+        assert_eq!(
+            unit.binding,
+            Some(VerificationUnitBinding {
+                entity_name: WithRef::new(code.s1("ent").ident()),
+                architecture_name: Some(WithRef::new(code.s1("arch").ident())),
+            })
+        );
+        assert_eq!(
+            unit.inherits,
+            vec![
+                WithRef::new(code.s1("base").ident()),
+                WithRef::new(code.s1("other").ident())
+            ]
+        );
+        assert_eq!(unit.decl.len(), 2);
+        assert!(matches!(unit.decl[1], Declaration::Psl(_)));
+        assert_eq!(unit.statements.len(), 2);
+        assert!(matches!(
+            unit.statements[0].statement.item,
+            ConcurrentStatement::Psl(_)
+        ));
+    }
+}
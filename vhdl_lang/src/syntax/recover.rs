@@ -0,0 +1,252 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Resynchronization of the token stream after a syntax error.
+//!
+//! When a declaration or statement fails to parse, the tokens of that construct are skipped
+//! so that parsing can continue with the next declaration or statement of the enclosing list.
+//! Nested constructs such as `if ... end if;` or `record ... end record;` are skipped as a whole
+//! so that their `end` is not mistaken for the end of the enclosing list.
+
+use super::declarative_part::is_declaration_start;
+use super::tokens::{Kind, Kind::*, TokenStream};
+use crate::data::DiagnosticResult;
+
+/// The kind of list that contains the construct that failed to parse
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RecoverContext {
+    Declaration,
+    Statement,
+}
+
+impl RecoverContext {
+    /// Keywords that start a nested construct which ends with `end`
+    fn is_opener(self, kind: Kind) -> bool {
+        match self {
+            RecoverContext::Statement => matches!(kind, If | Case | Block | Process),
+            // Subprogram bodies are opened by the 'is' of their specification
+            RecoverContext::Declaration => matches!(
+                kind,
+                If | Case | Block | Record | Units | Protected | Component
+            ),
+        }
+    }
+
+    /// Keywords following `end` that close a nested construct
+    fn is_closer(self, kind: Kind) -> bool {
+        match self {
+            RecoverContext::Statement => matches!(
+                kind,
+                If | Case | Loop | Block | Process | Generate | Postponed
+            ),
+            // Within a declaration every 'end' closes something
+            RecoverContext::Declaration => true,
+        }
+    }
+
+    /// Tokens that begin the next construct of the enclosing list or terminate it
+    fn is_boundary(self, kind: Kind, previous: Kind) -> bool {
+        match self {
+            RecoverContext::Statement => matches!(kind, End | Elsif),
+            RecoverContext::Declaration => {
+                matches!(kind, End | Begin)
+                    // Declaration keywords may also appear within a declaration,
+                    // such as 'file' in 'type t is file of ...' or the entity class of an attribute specification
+                    || (is_declaration_start(kind) && !matches!(previous, Colon | Is | LtLt))
+            }
+        }
+    }
+}
+
+/// Skip the construct that starts at the `start` state of the stream.
+///
+/// The construct ends after its terminating `;` or before a token that begins the next
+/// construct of the enclosing list, whichever comes first.
+pub fn skip_construct(
+    stream: &TokenStream,
+    start: usize,
+    context: RecoverContext,
+) -> DiagnosticResult<()> {
+    stream.set_state(start);
+
+    let mut depth = 0_usize;
+    let mut parens = 0_usize;
+    // A 'for' or 'while' is already counted, the following 'loop' or 'generate' does not open anything
+    let mut in_loop_header = false;
+    // The 'is' of a subprogram specification opens the subprogram body
+    let mut in_subprogram_header = false;
+    let mut previous = None;
+
+    loop {
+        let kind = stream.peek_expect()?.kind;
+
+        if let Some(previous) = previous {
+            if depth == 0 && (parens == 0 || kind == End) && context.is_boundary(kind, previous) {
+                return Ok(());
+            }
+        }
+        stream.skip();
+
+        match kind {
+            LeftPar => parens += 1,
+            RightPar => parens = parens.saturating_sub(1),
+            SemiColon if parens == 0 => {
+                in_subprogram_header = false;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+            Function | Procedure
+                if context == RecoverContext::Declaration && previous != Some(Colon) =>
+            {
+                in_subprogram_header = true;
+            }
+            Is if in_subprogram_header && parens == 0 => {
+                in_subprogram_header = false;
+                if !stream.next_kind_is(New) {
+                    depth += 1;
+                }
+            }
+            End => {
+                // Parentheses never span an 'end', any unbalanced ones were part of the error
+                parens = 0;
+                if let Some(next) = stream.peek_kind() {
+                    if context.is_closer(next) {
+                        if next != SemiColon && next != Identifier {
+                            stream.skip();
+                            if next == Postponed {
+                                stream.skip_if_kind(Process);
+                            }
+                        }
+                        depth = depth.saturating_sub(1);
+                    }
+                }
+            }
+            While => {
+                depth += 1;
+                in_loop_header = true;
+            }
+            For if stream.next_kinds_are(&[Identifier, In]) => {
+                depth += 1;
+                in_loop_header = true;
+            }
+            Loop | Generate if in_loop_header => in_loop_header = false,
+            Loop => depth += 1,
+            kind if context.is_opener(kind) => depth += 1,
+            _ => {}
+        }
+        previous = Some(kind);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    fn remaining_after_skip(code: &str, context: RecoverContext) -> Option<Kind> {
+        let code = Code::new(code);
+        code.with_partial_stream(|stream| {
+            skip_construct(stream, stream.state(), context).unwrap();
+            stream.peek_kind()
+        })
+    }
+
+    #[test]
+    fn skips_simple_statement_including_semicolon() {
+        assert_eq!(
+            remaining_after_skip("foo <= ; bar <= 1;", RecoverContext::Statement),
+            Some(Identifier)
+        );
+    }
+
+    #[test]
+    fn skips_nested_statements() {
+        assert_eq!(
+            remaining_after_skip(
+                "if a = then
+                   for i in 0 to 1 loop
+                     case x is when others => null; end case;
+                   end loop;
+                 end if;
+                 end process;",
+                RecoverContext::Statement
+            ),
+            Some(End)
+        );
+    }
+
+    #[test]
+    fn skips_generate_statement() {
+        assert_eq!(
+            remaining_after_skip(
+                "gen: for i in 0 to 1 generate
+                   process begin wait for 1 ns; end process;
+                 end generate;
+                 foo <= bar;",
+                RecoverContext::Statement
+            ),
+            Some(Identifier)
+        );
+    }
+
+    #[test]
+    fn stops_before_end_of_enclosing_list() {
+        assert_eq!(
+            remaining_after_skip("foo := 1 + end if;", RecoverContext::Statement),
+            Some(End)
+        );
+    }
+
+    #[test]
+    fn skips_record_type_declaration() {
+        assert_eq!(
+            remaining_after_skip(
+                "type rec_t is record a : bad bad; end record; signal s : bit;",
+                RecoverContext::Declaration
+            ),
+            Some(Signal)
+        );
+    }
+
+    #[test]
+    fn skips_subprogram_body() {
+        assert_eq!(
+            remaining_after_skip(
+                "procedure proc(signal a : bit; b : bit) is
+                 begin
+                   if a = then end if;
+                 end procedure;
+                 begin",
+                RecoverContext::Declaration
+            ),
+            Some(Begin)
+        );
+        assert_eq!(
+            remaining_after_skip(
+                "function func return natural is
+                   constant c : natural := ;
+                   variable v : natural;
+                 begin
+                 end;
+                 begin",
+                RecoverContext::Declaration
+            ),
+            Some(Begin)
+        );
+    }
+
+    #[test]
+    fn stops_at_next_declaration_when_semicolon_is_missing() {
+        assert_eq!(
+            remaining_after_skip(
+                "attribute foo of bar : signal is 1 constant c : natural := 0;",
+                RecoverContext::Declaration
+            ),
+            Some(Constant)
+        );
+    }
+}
@@ -5,15 +5,18 @@
 // Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 
 use super::common::parse_optional;
-use super::common::ParseResult;
+use super::common::{check_standard, ParseResult};
+use super::declarative_part::parse_declarative_part;
 use super::expression::parse_aggregate;
 use super::expression::{parse_choices, parse_expression};
 use super::names::parse_name;
 use super::range::parse_discrete_range;
-use super::tokens::{Kind::*, TokenStream};
+use super::recover::{skip_construct, RecoverContext};
+use super::tokens::{Kind::*, TokenAccess, TokenStream};
 use super::waveform::{parse_delay_mechanism, parse_waveform};
 use crate::ast::*;
 use crate::data::*;
+use crate::standard::VHDLStandard;
 use crate::syntax::common::check_label_identifier_mismatch;
 
 /// LRM 10.2 Wait statement
@@ -76,13 +79,16 @@ pub fn parse_labeled_sequential_statements(
             End | Else | Elsif | When => {
                 break Ok(statements);
             }
-            _ => match parse_sequential_statement(stream, diagnostics) {
-                Ok(stmt) => statements.push(stmt),
-                Err(diag) => {
-                    diagnostics.push(diag);
-                    let _ = stream.skip_until(|kind| matches!(kind, End | Else | Elsif | When));
+            _ => {
+                let start = stream.state();
+                match parse_sequential_statement(stream, diagnostics) {
+                    Ok(stmt) => statements.push(stmt),
+                    Err(diag) => {
+                        diagnostics.push(diag);
+                        skip_construct(stream, start, RecoverContext::Statement)?;
+                    }
                 }
-            },
+            }
         }
     }
 }
@@ -264,17 +270,59 @@ fn parse_exit_statement(stream: &TokenStream) -> ParseResult<ExitStatement> {
 }
 
 /// LRM 10.13 Return statement
-fn parse_return_statement(stream: &TokenStream) -> ParseResult<ReturnStatement> {
+fn parse_return_statement(
+    stream: &TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<ReturnStatement> {
     stream.expect_kind(Return)?;
-    let expression = {
-        if stream.peek_kind() == Some(SemiColon) {
-            None
-        } else {
-            Some(parse_expression(stream)?)
+    if stream.skip_if_kind(SemiColon) {
+        return Ok(ReturnStatement { expression: None });
+    }
+
+    let expression = parse_variable_assignment_right_hand(stream)?;
+    if let AssignmentRightHand::Conditional(ref conditionals) = expression {
+        if let Some(conditional) = conditionals.conditionals.first() {
+            check_standard(
+                stream,
+                &conditional.condition.pos,
+                "Conditional return statement",
+                VHDLStandard::VHDL2019,
+                diagnostics,
+            );
         }
-    };
+    }
+    Ok(ReturnStatement {
+        expression: Some(expression),
+    })
+}
+
+/// LRM 10.16 Sequential block statement
+fn parse_sequential_block(
+    stream: &TokenStream,
+    label: Option<&Ident>,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<SequentialBlock> {
+    let block = stream.expect_kind(Block)?;
+    check_standard(
+        stream,
+        stream.get_pos(block),
+        "Sequential block statement",
+        VHDLStandard::VHDL2019,
+        diagnostics,
+    );
+    stream.pop_if_kind(Is);
+    let decl = parse_declarative_part(stream, diagnostics)?;
+    stream.expect_kind(Begin)?;
+    let statements = parse_labeled_sequential_statements(stream, diagnostics)?;
+    stream.expect_kind(End)?;
+    stream.expect_kind(Block)?;
+    let end_ident = stream.pop_optional_ident();
     stream.expect_kind(SemiColon)?;
-    Ok(ReturnStatement { expression })
+    Ok(SequentialBlock {
+        decl,
+        statements,
+        end_label_pos: check_label_identifier_mismatch(label, end_ident, diagnostics),
+    })
 }
 
 /// LRM 10.5 Signal assignment statement
@@ -535,7 +583,8 @@ fn parse_unlabeled_sequential_statement(
             },
             Next => SequentialStatement::Next(parse_next_statement(stream)?),
             Exit => SequentialStatement::Exit(parse_exit_statement(stream)?),
-            Return => SequentialStatement::Return(parse_return_statement(stream)?),
+            Return => SequentialStatement::Return(parse_return_statement(stream, diagnostics)?),
+            Block => SequentialStatement::SequentialBlock(parse_sequential_block(stream, label, diagnostics)?),
             Null => {
                 stream.skip();
                 stream.expect_kind(SemiColon)?;
@@ -1841,7 +1890,31 @@ end loop;",
                 None,
                 WithPos::new(
                     SequentialStatement::Return(ReturnStatement {
-                        expression: Some(code.s1("1 + 2").expr()),
+                        expression: Some(AssignmentRightHand::Simple(code.s1("1 + 2").expr())),
+                    }),
+                    code.pos()
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn parse_conditional_return_statement() {
+        let code = Code::new("return 1 when foo else 2;").with_standard(VHDLStandard::VHDL2019);
+        let statement = parse_stmt(&code);
+        assert_eq!(
+            statement,
+            with_label(
+                None,
+                WithPos::new(
+                    SequentialStatement::Return(ReturnStatement {
+                        expression: Some(AssignmentRightHand::Conditional(Conditionals {
+                            conditionals: vec![Conditional {
+                                condition: code.s1("foo").expr(),
+                                item: code.s1("1").expr(),
+                            }],
+                            else_item: Some(code.s1("2").expr()),
+                        })),
                     }),
                     code.pos()
                 )
@@ -1849,6 +1922,66 @@ end loop;",
         );
     }
 
+    #[test]
+    fn conditional_return_statement_requires_vhdl_2019() {
+        let code = Code::new("return 1 when foo else 2;").with_standard(VHDLStandard::VHDL2008);
+        let (_, diagnostics) = code.with_stream_diagnostics(parse_sequential_statement);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                code.s1("foo"),
+                "Conditional return statement requires VHDL-2019 or later, the library uses VHDL-2008"
+            )]
+        );
+    }
+
+    #[test]
+    fn parse_sequential_block_statement() {
+        let code = Code::new(
+            "\
+name: block is
+  variable v : natural;
+begin
+  stmt1;
+end block name;",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+        let statement = parse_stmt(&code);
+        assert_eq!(
+            statement,
+            with_label(
+                Some(code.s1("name").ident()),
+                WithPos::new(
+                    SequentialStatement::SequentialBlock(SequentialBlock {
+                        decl: code.s1("variable v : natural;").declarative_part(),
+                        statements: vec![code.s1("stmt1;").sequential_statement()],
+                        end_label_pos: Some(code.s("name", 2).pos()),
+                    }),
+                    code.pos_after("name: ")
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn sequential_block_statement_requires_vhdl_2019() {
+        let code = Code::new(
+            "\
+block
+begin
+end block;",
+        )
+        .with_standard(VHDLStandard::VHDL2008);
+        let (_, diagnostics) = code.with_stream_diagnostics(parse_sequential_statement);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                code.s1("block"),
+                "Sequential block statement requires VHDL-2019 or later, the library uses VHDL-2008"
+            )]
+        );
+    }
+
     #[test]
     fn parse_null_statement() {
         let (code, statement) = parse("null;");
@@ -1857,4 +1990,24 @@ end loop;",
             with_label(None, WithPos::new(SequentialStatement::Null, code.pos()))
         );
     }
+
+    #[test]
+    fn recover_from_broken_statements() {
+        let code = Code::new(
+            "\
+foo := 1 + ;
+if a = then
+  null;
+end if;
+bar := 2;
+end",
+        );
+        let (statements, diagnostics) =
+            code.with_partial_stream_diagnostics(parse_labeled_sequential_statements);
+        assert_eq!(
+            statements,
+            Ok(vec![code.s1("bar := 2;").sequential_statement()])
+        );
+        assert_eq!(diagnostics.len(), 2);
+    }
 }
@@ -4,7 +4,7 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use super::common::{check_end_identifier_mismatch, ParseResult};
+use super::common::{check_end_identifier_mismatch, check_standard, ParseResult};
 use super::declarative_part::parse_declarative_part;
 use super::interface_declaration::parse_parameter_interface_list;
 use super::names::parse_type_mark;
@@ -12,6 +12,7 @@ use super::sequential_statement::parse_labeled_sequential_statements;
 use super::tokens::{kinds_error, Kind::*, TokenAccess, TokenId, TokenSpan, TokenStream};
 use crate::ast::*;
 use crate::data::*;
+use crate::standard::VHDLStandard;
 use crate::syntax::concurrent_statement::parse_map_aspect;
 use crate::syntax::interface_declaration::parse_generic_interface_list;
 use crate::syntax::names::parse_name;
@@ -180,6 +181,20 @@ pub fn parse_subprogram_specification(
 
     if is_function {
         stream.expect_kind(Return)?;
+        let return_identifier = if stream.nth_kind_is(1, Of) {
+            let ident = stream.expect_ident()?;
+            check_standard(
+                stream,
+                &ident.pos,
+                "Return identifier",
+                VHDLStandard::VHDL2019,
+                diagnostics,
+            );
+            stream.skip();
+            Some(WithDecl::new(ident))
+        } else {
+            None
+        };
         let return_type = parse_type_mark(stream)?;
         Ok(SubprogramSpecification::Function(FunctionSpecification {
             pure: is_pure,
@@ -187,6 +202,7 @@ pub fn parse_subprogram_specification(
             designator: designator.into(),
             header,
             parameter_list,
+            return_identifier,
             return_type,
         }))
     } else {
@@ -344,12 +360,60 @@ function foo return lib.foo.natural;
                     header: None,
                     param_tok: None,
                     parameter_list: Vec::new(),
+                    return_identifier: None,
                     return_type: code.s1("lib.foo.natural").type_mark()
                 })
             }
         );
     }
 
+    #[test]
+    pub fn parses_function_specification_with_return_identifier() {
+        let code = Code::new(
+            "\
+function foo return r of natural;
+",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_subprogram_declaration),
+            SubprogramDeclaration {
+                span: code.token_span(),
+                specification: SubprogramSpecification::Function(FunctionSpecification {
+                    pure: true,
+                    designator: code
+                        .s1("foo")
+                        .ident()
+                        .map_into(SubprogramDesignator::Identifier)
+                        .into(),
+                    header: None,
+                    param_tok: None,
+                    parameter_list: Vec::new(),
+                    return_identifier: Some(code.s1("r of").s1("r").decl_ident()),
+                    return_type: code.s1("natural").type_mark()
+                })
+            }
+        );
+    }
+
+    #[test]
+    pub fn return_identifier_requires_vhdl_2019() {
+        let code = Code::new(
+            "\
+function foo return r of natural;
+",
+        )
+        .with_standard(VHDLStandard::VHDL2008);
+        let (_, diagnostics) = code.with_stream_diagnostics(parse_subprogram_declaration);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                code.s1("r of").s1("r"),
+                "Return identifier requires VHDL-2019 or later, the library uses VHDL-2008"
+            )]
+        );
+    }
+
     #[test]
     pub fn parses_function_specification_operator() {
         let code = Code::new(
@@ -371,6 +435,7 @@ function \"+\" return lib.foo.natural;
                     header: None,
                     param_tok: None,
                     parameter_list: Vec::new(),
+                    return_identifier: None,
                     return_type: code.s1("lib.foo.natural").type_mark()
                 })
             }
@@ -398,6 +463,7 @@ impure function foo return lib.foo.natural;
                     header: None,
                     param_tok: None,
                     parameter_list: Vec::new(),
+                    return_identifier: None,
                     return_type: code.s1("lib.foo.natural").type_mark()
                 })
             }
@@ -424,6 +490,7 @@ pure function foo return lib.foo.natural;
                     header: None,
                     param_tok: None,
                     parameter_list: Vec::new(),
+                    return_identifier: None,
                     return_type: code.s1("lib.foo.natural").type_mark()
                 })
             }
@@ -475,6 +542,7 @@ function foo(foo : natural) return lib.foo.natural;
                     header: None,
                     param_tok: None,
                     parameter_list: vec![code.s1("foo : natural").parameter()],
+                    return_identifier: None,
                     return_type: code.s1("lib.foo.natural").type_mark()
                 })
             }
@@ -502,6 +570,7 @@ function foo parameter (foo : natural) return lib.foo.natural;
                     header: None,
                     param_tok: Some(code.s1("parameter").token()),
                     parameter_list: vec![code.s1("foo : natural").parameter()],
+                    return_identifier: None,
                     return_type: code.s1("lib.foo.natural").type_mark()
                 })
             }
@@ -533,6 +602,7 @@ function foo generic (abc_def: natural) parameter (foo : natural) return lib.foo
                     }),
                     param_tok: Some(code.s1("parameter").token()),
                     parameter_list: vec![code.s1("foo : natural").parameter()],
+                    return_identifier: None,
                     return_type: code.s1("lib.foo.natural").type_mark()
                 })
             }
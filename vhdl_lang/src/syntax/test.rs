@@ -35,6 +35,7 @@ use crate::ast;
 use crate::ast::*;
 use crate::data::Range;
 use crate::data::*;
+use crate::standard::VHDLStandard;
 use crate::syntax::concurrent_statement::parse_map_aspect;
 use crate::syntax::context::{parse_context, DeclarationOrReference};
 use crate::syntax::names::parse_association_element;
@@ -91,6 +92,7 @@ impl CodeBuilder {
         let code = Code {
             symbols: self.symbols.clone(),
             pos,
+            standard: VHDLStandard::default(),
         };
 
         // Ensure symbol table is populated
@@ -127,6 +129,7 @@ impl<T> SeparatedList<T> {
 pub struct Code {
     pub symbols: Arc<Symbols>,
     pos: SrcPos,
+    standard: VHDLStandard,
 }
 
 impl Code {
@@ -138,10 +141,23 @@ impl Code {
         CodeBuilder::new().code_with_file_name(file_name, code)
     }
 
+    /// Parse the code according to another revision of the language than the default
+    pub fn with_standard(self, standard: VHDLStandard) -> Code {
+        let code = Code { standard, ..self };
+        // Ensure symbol table is populated
+        code.tokenize_result();
+        code
+    }
+
+    pub fn standard(&self) -> VHDLStandard {
+        self.standard
+    }
+
     fn in_range(&self, range: Range) -> Code {
         Code {
             symbols: self.symbols.clone(),
             pos: SrcPos::new(self.pos.source.clone(), range),
+            standard: self.standard,
         }
     }
 
@@ -155,6 +171,7 @@ impl Code {
                     end: self.pos.end(),
                 },
             ),
+            standard: self.standard,
         }
     }
 
@@ -168,6 +185,7 @@ impl Code {
                     end,
                 },
             ),
+            standard: self.standard,
         }
     }
 
@@ -267,7 +285,7 @@ impl Code {
             );
             let contents = source.contents();
             let reader = ContentReader::new(&contents);
-            let mut tokenizer = Tokenizer::new(&self.symbols, &source, reader);
+            let mut tokenizer = Tokenizer::new(&self.symbols, &source, reader, self.standard);
             loop {
                 let token = tokenizer.pop();
 
@@ -318,7 +336,7 @@ impl Code {
         );
         let contents = source.contents();
         let reader = ContentReader::new(&contents);
-        let tokenizer = Tokenizer::new(&self.symbols, &source, reader);
+        let tokenizer = Tokenizer::new(&self.symbols, &source, reader, self.standard);
         let stream = TokenStream::new(tokenizer, &mut NoDiagnostics);
         forward(&stream, self.pos.start());
         stream.peek().expect("No token found");
@@ -337,7 +355,7 @@ impl Code {
         );
         let contents = source.contents();
         let reader = ContentReader::new(&contents);
-        let tokenizer = Tokenizer::new(&self.symbols, &source, reader);
+        let tokenizer = Tokenizer::new(&self.symbols, &source, reader, self.standard);
         let mut stream = TokenStream::new(tokenizer, &mut NoDiagnostics);
         forward(&stream, self.pos.start());
         parse_fun(&mut stream)
@@ -362,7 +380,7 @@ impl Code {
     {
         let contents = self.pos.source.contents();
         let reader = ContentReader::new(&contents);
-        let tokenizer = Tokenizer::new(&self.symbols, &self.pos.source, reader);
+        let tokenizer = Tokenizer::new(&self.symbols, &self.pos.source, reader, self.standard);
         let mut stream = TokenStream::new(tokenizer, &mut NoDiagnostics);
         parse_fun(&mut stream)
     }
@@ -6,7 +6,9 @@
 
 #[macro_use]
 mod tokenizer;
+mod conditional_analysis;
 mod tokenstream;
 
+pub use conditional_analysis::ConditionalIdentifiers;
 pub use tokenizer::*;
 pub use tokenstream::*;
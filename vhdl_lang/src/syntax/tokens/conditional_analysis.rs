@@ -0,0 +1,695 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! LRM 24.2 Conditional analysis tool directives
+//!
+//! ```vhdl
+//! `if VHDL_VERSION >= "2019" and TOOL_TYPE /= "SYNTHESIS" then
+//!   ...
+//! `elsif MY_DEFINE = "1" then
+//!   ...
+//! `else
+//!   `warning "Fallback implementation"
+//! `end if
+//! ```
+
+use super::tokenizer::Kind::*;
+use super::tokenizer::*;
+use crate::data::*;
+use crate::standard::VHDLStandard;
+use fnv::FnvHashMap;
+
+/// The values that conditional analysis identifiers evaluate to.
+/// The predefined identifiers such as `VHDL_VERSION` have a default value that may be overridden.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct ConditionalIdentifiers {
+    // Identifiers are case insensitive and stored in upper case
+    values: FnvHashMap<String, String>,
+}
+
+impl ConditionalIdentifiers {
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        self.values.insert(name.to_uppercase(), value.into());
+    }
+
+    /// Add the identifiers of other, in case of conflict other takes precedence
+    pub fn append(&mut self, other: &ConditionalIdentifiers) {
+        for (name, value) in other.values.iter() {
+            self.values.insert(name.clone(), value.clone());
+        }
+    }
+
+    /// The user defined identifiers and their values
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The value of an identifier, `None` if it is neither predefined nor user defined
+    pub fn get(&self, name: &str, standard: VHDLStandard) -> Option<String> {
+        let name = name.to_uppercase();
+        if let Some(value) = self.values.get(&name) {
+            return Some(value.clone());
+        }
+
+        let value = match name.as_str() {
+            "VHDL_VERSION" => standard.year(),
+            "TOOL_TYPE" => "SIMULATION",
+            "TOOL_VENDOR" => "rust_hdl",
+            "TOOL_NAME" => "vhdl_lang",
+            "TOOL_EDITION" => "",
+            "TOOL_VERSION" => env!("CARGO_PKG_VERSION"),
+            _ => return None,
+        };
+        Some(value.to_owned())
+    }
+}
+
+struct Branch {
+    // The `if directive, to report a missing `end if
+    pos: SrcPos,
+    // True if the region enclosing the `if is active
+    parent_active: bool,
+    // True once any condition of the `if/`elsif chain has been true
+    taken: bool,
+    active: bool,
+    seen_else: bool,
+}
+
+/// Evaluates conditional analysis directives while the token stream is created
+/// and decides which tokens are part of the design
+pub(crate) struct ConditionalAnalysis<'i> {
+    identifiers: &'i ConditionalIdentifiers,
+    standard: VHDLStandard,
+    branches: Vec<Branch>,
+    // Start of the inactive region that is currently being skipped
+    inactive_start: Option<Position>,
+}
+
+impl<'i> ConditionalAnalysis<'i> {
+    pub fn new(identifiers: &'i ConditionalIdentifiers, standard: VHDLStandard) -> Self {
+        ConditionalAnalysis {
+            identifiers,
+            standard,
+            branches: Vec::new(),
+            inactive_start: None,
+        }
+    }
+
+    /// True if tokens at the current position are analyzed
+    pub fn is_active(&self) -> bool {
+        self.branches
+            .last()
+            .map(|branch| branch.active)
+            .unwrap_or(true)
+    }
+
+    /// Handle a directive that starts with a grave accent.
+    /// Directives that are not conditional analysis directives are tool directives of the form
+    /// ```vhdl
+    /// `identifier { any chars until newline }
+    /// ```
+    /// These need special handling as the text that follows the identifier is arbitrary.
+    pub fn directive(
+        &mut self,
+        grave_accent: Token,
+        tokenizer: &mut Tokenizer,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let was_active = self.is_active();
+        let start = grave_accent.pos.start();
+        let end = self.parse_directive(grave_accent, tokenizer, diagnostics);
+
+        match (was_active, self.is_active()) {
+            (true, false) => self.inactive_start = Some(end),
+            (false, true) => self.mark_inactive(tokenizer.source, start, diagnostics),
+            _ => {}
+        }
+    }
+
+    /// Report directives that are not closed and the final inactive region
+    pub fn finish(&mut self, source: &Source, diagnostics: &mut dyn DiagnosticHandler) {
+        self.mark_inactive(source, source.contents().end(), diagnostics);
+        for branch in self.branches.drain(..) {
+            diagnostics.error(branch.pos, "Missing `end if for conditional analysis `if");
+        }
+    }
+
+    fn mark_inactive(
+        &mut self,
+        source: &Source,
+        end: Position,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if let Some(start) = self.inactive_start.take() {
+            if start < end {
+                diagnostics.push(
+                    Diagnostic::hint(
+                        source.pos(start, end),
+                        "Inactive conditional analysis region",
+                    )
+                    .with_tag(DiagnosticTag::Unnecessary),
+                );
+            }
+        }
+    }
+
+    /// Parse a single directive and return the position where it ends
+    fn parse_directive(
+        &mut self,
+        grave_accent: Token,
+        tokenizer: &mut Tokenizer,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> Position {
+        let token = match tokenizer.pop() {
+            Ok(Some(token)) => token,
+            Err(err) => {
+                if self.is_active() {
+                    diagnostics.push(err);
+                }
+                return grave_accent.pos.end();
+            }
+            Ok(None) => {
+                diagnostics.error(&grave_accent.pos, "Expecting identifier");
+                return grave_accent.pos.end();
+            }
+        };
+
+        match token.kind {
+            If => {
+                let parent_active = self.is_active();
+                let mut ignored = Vec::new();
+                let condition_diagnostics: &mut dyn DiagnosticHandler = if parent_active {
+                    diagnostics
+                } else {
+                    &mut ignored
+                };
+                let (value, end) = self.condition(tokenizer, &token, condition_diagnostics);
+                self.branches.push(Branch {
+                    pos: grave_accent.pos.combine(&token.pos),
+                    parent_active,
+                    taken: value,
+                    active: parent_active && value,
+                    seen_else: false,
+                });
+                end
+            }
+            Elsif => {
+                let parent_active = self
+                    .branches
+                    .last()
+                    .map(|branch| branch.parent_active)
+                    .unwrap_or(true);
+                let mut ignored = Vec::new();
+                let condition_diagnostics: &mut dyn DiagnosticHandler = if parent_active {
+                    &mut *diagnostics
+                } else {
+                    &mut ignored
+                };
+                let (value, end) = self.condition(tokenizer, &token, condition_diagnostics);
+                match self.branches.last_mut() {
+                    Some(branch) if !branch.seen_else => {
+                        branch.active = branch.parent_active && !branch.taken && value;
+                        branch.taken |= value;
+                    }
+                    Some(_) => diagnostics.error(&token.pos, "`elsif after `else"),
+                    None => diagnostics.error(&token.pos, "`elsif without matching `if"),
+                }
+                end
+            }
+            Else => {
+                let end = rest_of_line(tokenizer, &token, &[], diagnostics);
+                match self.branches.last_mut() {
+                    Some(branch) if !branch.seen_else => {
+                        branch.active = branch.parent_active && !branch.taken;
+                        branch.taken = true;
+                        branch.seen_else = true;
+                    }
+                    Some(_) => diagnostics.error(&token.pos, "Duplicate `else"),
+                    None => diagnostics.error(&token.pos, "`else without matching `if"),
+                }
+                end
+            }
+            End => {
+                let end = rest_of_line(tokenizer, &token, &["if"], diagnostics);
+                if self.branches.pop().is_none() {
+                    diagnostics.error(&token.pos, "`end without matching `if");
+                }
+                end
+            }
+            Identifier => {
+                let name = token
+                    .to_identifier_value()
+                    .map(|ident| ident.item.name_utf8());
+                match name.as_deref().map(str::to_lowercase).as_deref() {
+                    Ok("warning") | Ok("error") => {
+                        self.message(tokenizer, &grave_accent, &token, diagnostics)
+                    }
+                    _ => match tokenizer.text_until_newline() {
+                        Ok(text) => text.pos.end(),
+                        Err(err) => {
+                            if self.is_active() {
+                                diagnostics.push(err);
+                            }
+                            token.pos.end()
+                        }
+                    },
+                }
+            }
+            _ => {
+                if self.is_active() {
+                    diagnostics.error(token.pos, "Expecting identifier");
+                }
+                // skip potentially invalid tokens
+                match tokenizer.text_until_newline() {
+                    Ok(text) => text.pos.end(),
+                    Err(_) => grave_accent.pos.end(),
+                }
+            }
+        }
+    }
+
+    /// `warning "message" or `error "message"
+    fn message(
+        &self,
+        tokenizer: &mut Tokenizer,
+        grave_accent: &Token,
+        directive: &Token,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> Position {
+        let message = match tokenizer.pop() {
+            Ok(Some(token)) => token.to_string_value(),
+            Ok(None) => Err(directive.kinds_error(&[StringLiteral])),
+            Err(err) => Err(err),
+        };
+
+        match message {
+            Ok(message) => {
+                if self.is_active() {
+                    let pos = grave_accent.pos.combine(&message.pos);
+                    let text = message.item.to_string();
+                    let is_error = matches!(
+                        directive.to_identifier_value(),
+                        Ok(ident) if ident.item.name_utf8().eq_ignore_ascii_case("error")
+                    );
+                    diagnostics.push(if is_error {
+                        Diagnostic::error(pos, text)
+                    } else {
+                        Diagnostic::warning(pos, text)
+                    });
+                }
+                message.pos.end()
+            }
+            Err(err) => {
+                if self.is_active() {
+                    diagnostics.push(err);
+                }
+                directive.pos.end()
+            }
+        }
+    }
+
+    /// Parse and evaluate the condition of `if or `elsif up to and including then
+    fn condition(
+        &self,
+        tokenizer: &mut Tokenizer,
+        directive: &Token,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> (bool, Position) {
+        let mut tokens = Vec::new();
+        let end = loop {
+            match tokenizer.pop() {
+                Ok(Some(token)) if token.kind == Then => break token.pos.end(),
+                Ok(Some(token)) => tokens.push(token),
+                Ok(None) => {
+                    let pos = tokens
+                        .last()
+                        .map(|token| &token.pos)
+                        .unwrap_or(&directive.pos);
+                    diagnostics.push(kinds_error(pos, &[Then]));
+                    return (false, pos.end());
+                }
+                Err(err) => {
+                    diagnostics.push(err);
+                    return (false, directive.pos.end());
+                }
+            }
+        };
+
+        let mut parser = ConditionParser {
+            identifiers: self.identifiers,
+            standard: self.standard,
+            tokens: &tokens,
+            idx: 0,
+        };
+
+        match parser.evaluate(directive) {
+            Ok(value) => (value, end),
+            Err(err) => {
+                diagnostics.push(err);
+                (false, end)
+            }
+        }
+    }
+}
+
+/// Skip the rest of the line after `else or `end, which may only contain the given words
+fn rest_of_line(
+    tokenizer: &mut Tokenizer,
+    directive: &Token,
+    allowed: &[&str],
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> Position {
+    match tokenizer.text_until_newline() {
+        Ok(text) => {
+            let rest = match text.value {
+                Value::Text(ref text) => text.to_string(),
+                _ => String::new(),
+            };
+            let rest = rest.split("--").next().unwrap_or_default().trim();
+            if !rest.is_empty() && !allowed.iter().any(|word| rest.eq_ignore_ascii_case(word)) {
+                diagnostics.error(
+                    &text.pos,
+                    format!("Unexpected text after `{}", kind_str(directive.kind)),
+                );
+            }
+            text.pos.end()
+        }
+        Err(err) => {
+            diagnostics.push(err);
+            directive.pos.end()
+        }
+    }
+}
+
+struct ConditionParser<'t> {
+    identifiers: &'t ConditionalIdentifiers,
+    standard: VHDLStandard,
+    tokens: &'t [Token],
+    idx: usize,
+}
+
+impl<'t> ConditionParser<'t> {
+    fn evaluate(&mut self, directive: &Token) -> DiagnosticResult<bool> {
+        if self.tokens.is_empty() {
+            return Err(kinds_error(&directive.pos, &[Identifier, Not, LeftPar]));
+        }
+        let value = self.expression()?;
+        if let Some(token) = self.peek() {
+            return Err(token.kinds_error(&[Then]));
+        }
+        Ok(value)
+    }
+
+    fn peek(&self) -> Option<&'t Token> {
+        self.tokens.get(self.idx)
+    }
+
+    fn pop(&mut self) -> DiagnosticResult<&'t Token> {
+        match self.tokens.get(self.idx) {
+            Some(token) => {
+                self.idx += 1;
+                Ok(token)
+            }
+            None => {
+                let last = self.tokens.last().unwrap();
+                Err(kinds_error(&last.pos, &[Identifier, Not, LeftPar]))
+            }
+        }
+    }
+
+    fn expression(&mut self) -> DiagnosticResult<bool> {
+        let mut value = self.and_expression()?;
+        while let Some(token) = self.peek() {
+            let op = token.kind;
+            if !matches!(op, Or | Xor | Xnor) {
+                break;
+            }
+            self.idx += 1;
+            let rhs = self.and_expression()?;
+            value = match op {
+                Or => value || rhs,
+                Xor => value != rhs,
+                _ => value == rhs,
+            };
+        }
+        Ok(value)
+    }
+
+    fn and_expression(&mut self) -> DiagnosticResult<bool> {
+        let mut value = self.primary()?;
+        while self.peek().map(|token| token.kind) == Some(And) {
+            self.idx += 1;
+            // Evaluate both sides to report errors in the right hand side
+            let rhs = self.primary()?;
+            value = value && rhs;
+        }
+        Ok(value)
+    }
+
+    fn primary(&mut self) -> DiagnosticResult<bool> {
+        let token = self.pop()?;
+        match token.kind {
+            Not => Ok(!self.primary()?),
+            LeftPar => {
+                let value = self.expression()?;
+                let right_par = self.pop()?;
+                if right_par.kind != RightPar {
+                    return Err(right_par.kinds_error(&[RightPar]));
+                }
+                Ok(value)
+            }
+            Identifier => {
+                let ident = token.to_identifier_value()?;
+                let name = ident.item.name_utf8();
+                let op = self.pop()?;
+                if !matches!(op.kind, EQ | NE | LT | LTE | GT | GTE) {
+                    return Err(op.kinds_error(&[EQ, NE, LT, LTE, GT, GTE]));
+                }
+                let literal = self.pop()?.to_string_value()?.item.to_string();
+
+                let Some(value) = self.identifiers.get(&name, self.standard) else {
+                    return Err(Diagnostic::error(
+                        &ident.pos,
+                        format!("Undefined conditional analysis identifier '{name}'"),
+                    ));
+                };
+
+                Ok(match op.kind {
+                    EQ => value == literal,
+                    NE => value != literal,
+                    LT => value < literal,
+                    LTE => value <= literal,
+                    GT => value > literal,
+                    _ => value >= literal,
+                })
+            }
+            _ => Err(token.kinds_error(&[Identifier, Not, LeftPar])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+    use crate::syntax::tokens::TokenStream;
+
+    /// The positions of the tokens that remain after conditional analysis
+    fn analyze(
+        code: &Code,
+        identifiers: &ConditionalIdentifiers,
+    ) -> (Vec<SrcPos>, Vec<Diagnostic>) {
+        let source = code.source();
+        let contents = source.contents();
+        let tokenizer = Tokenizer::new(
+            &code.symbols,
+            source,
+            ContentReader::new(&contents),
+            code.standard(),
+        );
+        let mut diagnostics = Vec::new();
+        let stream = TokenStream::new_with_identifiers(tokenizer, identifiers, &mut diagnostics);
+
+        let mut positions = Vec::new();
+        while let Some(token) = stream.peek() {
+            positions.push(token.pos.clone());
+            stream.skip();
+        }
+        (positions, diagnostics)
+    }
+
+    fn token_positions(code: &Code) -> Vec<SrcPos> {
+        code.tokenize().into_iter().map(|token| token.pos).collect()
+    }
+
+    fn inactive(code: &Code) -> Diagnostic {
+        Diagnostic::hint(code, "Inactive conditional analysis region")
+            .with_tag(DiagnosticTag::Unnecessary)
+    }
+
+    #[test]
+    fn selects_branch_from_predefined_identifiers() {
+        let code = Code::new(
+            "\
+`if VHDL_VERSION >= \"2019\" and TOOL_TYPE /= \"SYNTHESIS\" then
+constant c : natural := 0;
+`elsif VHDL_VERSION = \"2008\" then
+constant c : natural := 1;
+`else
+constant c : natural := 2;
+`end if
+constant d : natural := c;",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+
+        let (tokens, diagnostics) = analyze(&code, &ConditionalIdentifiers::default());
+        assert_eq!(
+            tokens,
+            [
+                token_positions(&code.s1("constant c : natural := 0;")),
+                token_positions(&code.s1("constant d : natural := c;"))
+            ]
+            .concat()
+        );
+        assert_eq!(
+            diagnostics,
+            vec![inactive(&code.s1(
+                "\nconstant c : natural := 1;\n`else\nconstant c : natural := 2;\n"
+            ))]
+        );
+    }
+
+    #[test]
+    fn nested_conditional_analysis() {
+        let code = Code::new(
+            "\
+`if TOOL_TYPE = \"SIMULATION\" then
+  `if TOOL_NAME = \"other\" then
+  constant c : natural := 0;
+  `else
+  constant d : natural := 0;
+  `end if
+`else
+  `if UNDEFINED = \"1\" then
+  `end if
+`end if",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+
+        let (tokens, diagnostics) = analyze(&code, &ConditionalIdentifiers::default());
+        assert_eq!(
+            tokens,
+            token_positions(&code.s1("constant d : natural := 0;"))
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                inactive(&code.s1("\n  constant c : natural := 0;\n  ")),
+                inactive(&code.s1("\n  `if UNDEFINED = \"1\" then\n  `end if\n")),
+            ]
+        );
+    }
+
+    #[test]
+    fn user_defined_identifiers() {
+        let code = Code::new(
+            "\
+`if MY_DEFINE = \"1\" and tool_type = \"SYNTHESIS\" then
+constant c : natural := 0;
+`end if",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+
+        let mut identifiers = ConditionalIdentifiers::default();
+        identifiers.set("my_define", "1");
+        identifiers.set("TOOL_TYPE", "SYNTHESIS");
+        let (tokens, diagnostics) = analyze(&code, &identifiers);
+        assert_eq!(
+            tokens,
+            token_positions(&code.s1("constant c : natural := 0;"))
+        );
+        assert_eq!(diagnostics, vec![]);
+    }
+
+    #[test]
+    fn warning_and_error_directives() {
+        let code = Code::new(
+            "\
+`if VHDL_VERSION < \"2008\" then
+`error \"Unsupported\"
+`else
+`warning \"Untested\"
+`end if
+`if VHDL_VERSION = \"2019\" then
+`error \"Unsupported\"
+`end if",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+
+        let (_, diagnostics) = analyze(&code, &ConditionalIdentifiers::default());
+        assert_eq!(
+            diagnostics,
+            vec![
+                inactive(&code.s1("\n`error \"Unsupported\"\n")),
+                Diagnostic::warning(code.s1("`warning \"Untested\""), "Untested"),
+                Diagnostic::error(code.s("`error \"Unsupported\"", 2), "Unsupported"),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_conditional_analysis() {
+        let code = Code::new(
+            "\
+`if MY_DEFINE = \"1\" then
+`end if
+`else
+`end if
+`if VHDL_VERSION = \"2019\" then",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+
+        let (_, diagnostics) = analyze(&code, &ConditionalIdentifiers::default());
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::error(
+                    code.s1("MY_DEFINE"),
+                    "Undefined conditional analysis identifier 'MY_DEFINE'"
+                ),
+                inactive(&code.s1("then\n`end").s1("\n")),
+                Diagnostic::error(code.s1("`else").s1("else"), "`else without matching `if"),
+                Diagnostic::error(code.s("`end", 2).s1("end"), "`end without matching `if"),
+                Diagnostic::error(
+                    code.s("`if", 2),
+                    "Missing `end if for conditional analysis `if"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unexpected_text_after_directive() {
+        let code = Code::new(
+            "\
+`if VHDL_VERSION = \"2019\" then
+`else -- comment
+`end if foo",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+
+        let (_, diagnostics) = analyze(&code, &ConditionalIdentifiers::default());
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::error(code.s1("if foo"), "Unexpected text after `end"),
+                inactive(&code.s1("\n`end").s1("\n")),
+            ]
+        );
+    }
+}
@@ -9,6 +9,7 @@ use fnv::FnvHashMap;
 use crate::ast::{self, AttributeDesignator, Operator, WithRef};
 use crate::ast::{BaseSpecifier, Ident};
 use crate::data::*;
+use crate::standard::VHDLStandard;
 
 /// The kind of a Token
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
@@ -22,6 +23,9 @@ pub enum Kind {
     Process,
     Generate,
     Postponed,
+    Guarded,
+    Disconnect,
+    Group,
     Library,
     Label,
     Use,
@@ -81,6 +85,8 @@ pub enum Kind {
     Open,
     Alias,
     Shared,
+    Bus,
+    Register,
     Others,
     Record,
     Type,
@@ -96,7 +102,20 @@ pub enum Kind {
     Procedure,
     Vunit,
     Parameter,
+    View,
+    Private,
     Literal,
+    Property,
+    Sequence,
+    Assume,
+    AssumeGuarantee,
+    Cover,
+    Fairness,
+    Restrict,
+    RestrictGuarantee,
+    Strong,
+    Vmode,
+    Vprop,
 
     // Unary operators
     Abs,
@@ -153,6 +172,9 @@ pub enum Kind {
     RightPar,
     LeftSquare,
     RightSquare,
+    LeftCurly,
+    RightCurly,
+    Exclamation,
     SemiColon,
     Colon,
     Bar,
@@ -275,6 +297,9 @@ pub fn kind_str(kind: Kind) -> &'static str {
         Process => "process",
         Generate => "generate",
         Postponed => "postponed",
+        Guarded => "guarded",
+        Disconnect => "disconnect",
+        Group => "group",
         Library => "library",
         Label => "label",
         Use => "use",
@@ -334,6 +359,8 @@ pub fn kind_str(kind: Kind) -> &'static str {
         Open => "open",
         Alias => "alias",
         Shared => "shared",
+        Bus => "bus",
+        Register => "register",
         Others => "others",
         Record => "record",
         Type => "type",
@@ -349,7 +376,20 @@ pub fn kind_str(kind: Kind) -> &'static str {
         Procedure => "procedure",
         Vunit => "vunit",
         Parameter => "parameter",
+        View => "view",
+        Private => "private",
         Literal => "literal",
+        Property => "property",
+        Sequence => "sequence",
+        Assume => "assume",
+        AssumeGuarantee => "assume_guarantee",
+        Cover => "cover",
+        Fairness => "fairness",
+        Restrict => "restrict",
+        RestrictGuarantee => "restrict_guarantee",
+        Strong => "strong",
+        Vmode => "vmode",
+        Vprop => "vprop",
 
         // Unary operators
         Abs => "abs",
@@ -406,6 +446,9 @@ pub fn kind_str(kind: Kind) -> &'static str {
         RightPar => ")",
         LeftSquare => "[",
         RightSquare => "]",
+        LeftCurly => "{",
+        RightCurly => "}",
+        Exclamation => "!",
         SemiColon => ";",
         Colon => ":",
         Bar => "|",
@@ -1272,6 +1315,7 @@ fn parse_basic_identifier_or_keyword(
     buffer: &mut Latin1String,
     reader: &mut ContentReader,
     symbols: &Symbols,
+    standard: VHDLStandard,
 ) -> Result<(Kind, Value), TokenError> {
     buffer.bytes.clear();
     while let Some(b) = reader.peek()? {
@@ -1286,7 +1330,7 @@ fn parse_basic_identifier_or_keyword(
         }
     }
 
-    Ok(symbols.insert_or_keyword(buffer))
+    Ok(symbols.insert_or_keyword(buffer, standard))
 }
 
 /// Assumes leading ' has already been consumed
@@ -1327,7 +1371,88 @@ fn read_until_newline(
     Ok(())
 }
 
-fn get_leading_comments(reader: &mut ContentReader) -> Result<Vec<Comment>, TokenError> {
+/// The reserved words that a PSL directive, declaration or verification unit starts with
+const PSL_STATEMENT_START: [&[u8]; 15] = [
+    b"assert",
+    b"assume",
+    b"assume_guarantee",
+    b"restrict",
+    b"restrict_guarantee",
+    b"cover",
+    b"fairness",
+    b"strong",
+    b"property",
+    b"sequence",
+    b"default",
+    b"endpoint",
+    b"vunit",
+    b"vprop",
+    b"vmode",
+];
+
+/// Reads the lower case word after any whitespace on the same line
+fn read_word_in_line(reader: &mut ContentReader) -> Result<Vec<u8>, TokenError> {
+    skip_whitespace_in_line(reader);
+    let mut word = Vec::new();
+    while let Some(byte) = reader.peek()? {
+        if !(byte.is_ascii_alphanumeric() || byte == b'_') {
+            break;
+        }
+        word.push(byte.to_ascii_lowercase());
+        reader.skip();
+    }
+    Ok(word)
+}
+
+/// Checks whether the comment whose leading `--` was just consumed starts with the `psl` keyword
+/// followed by a PSL statement, possibly labeled, such that plain comments that happen to start
+/// with the word psl are not tokenized.
+/// When it does the keyword is consumed and the rest of the comment is to be tokenized as PSL,
+/// LRM 15.10 and IEEE 1850 Annex B.
+fn is_psl_comment(reader: &mut ContentReader) -> Result<bool, TokenError> {
+    let state = reader.state();
+    skip_whitespace_in_line(reader);
+    for expected in b"psl" {
+        match reader.pop()? {
+            Some(byte) if byte.to_ascii_lowercase() == *expected => {}
+            _ => {
+                reader.set_state(state);
+                return Ok(false);
+            }
+        }
+    }
+    if !matches!(reader.peek()?, Some(b' ' | b'\t')) {
+        reader.set_state(state);
+        return Ok(false);
+    }
+
+    let keyword_end = reader.state();
+    let mut word = read_word_in_line(reader)?;
+    if !word.is_empty() && !PSL_STATEMENT_START.contains(&word.as_slice()) {
+        // The label of a directive
+        skip_whitespace_in_line(reader);
+        if reader.pop()? == Some(b':') {
+            word = read_word_in_line(reader)?;
+        }
+    }
+
+    if PSL_STATEMENT_START.contains(&word.as_slice()) {
+        reader.set_state(keyword_end);
+        Ok(true)
+    } else {
+        reader.set_state(state);
+        Ok(false)
+    }
+}
+
+/// Collects the comments before the next token.
+/// If a `-- psl` comment is found, PSL mode is entered and the comment is tokenized instead.
+/// Within PSL mode, the leading `--` of every continuation line is skipped.
+fn get_leading_comments(
+    reader: &mut ContentReader,
+    psl: &mut bool,
+    standard: VHDLStandard,
+) -> Result<Vec<Comment>, TokenError> {
     let mut comments: Vec<Comment> = Vec::new();
 
     loop {
@@ -1351,6 +1476,13 @@ fn get_leading_comments(reader: &mut ContentReader) -> Result<Vec<Comment>, Toke
             }
             b'-' => {
                 if reader.pop()? == Some(b'-') {
+                    if *psl {
+                        continue;
+                    }
+                    if standard >= VHDLStandard::VHDL2008 && is_psl_comment(reader)? {
+                        *psl = true;
+                        continue;
+                    }
                     comments.push(parse_comment(reader));
                 } else {
                     reader.set_state(state);
@@ -1427,16 +1559,29 @@ impl Symbols {
         &self.symtab
     }
 
-    fn insert_or_keyword(&self, name: &Latin1String) -> (Kind, Value) {
+    /// Keywords that were reserved in a later revision than `standard` are plain identifiers
+    fn insert_or_keyword(&self, name: &Latin1String, standard: VHDLStandard) -> (Kind, Value) {
         let symbol = self.symtab.insert(name);
-        if let Some(kind) = self.keywords.get(symbol.id) {
-            (*kind, Value::NoValue)
-        } else {
-            (Identifier, Value::Identifier(symbol))
+        match self.keywords.get(symbol.id) {
+            Some(kind) if keyword_standard(*kind) <= standard => (*kind, Value::NoValue),
+            _ => (Identifier, Value::Identifier(symbol)),
         }
     }
 }
 
+/// The first revision of the language in which `kind` is a reserved word
+fn keyword_standard(kind: Kind) -> VHDLStandard {
+    match kind {
+        Protected => VHDLStandard::VHDL2002,
+        Context | Default | Force | Release | Parameter | Vunit => VHDLStandard::VHDL2008,
+        // Reserved words of the embedded PSL language, LRM 15.10
+        Property | Sequence | Assume | AssumeGuarantee | Cover | Fairness | Restrict
+        | RestrictGuarantee | Strong | Vmode | Vprop => VHDLStandard::VHDL2008,
+        View | Private => VHDLStandard::VHDL2019,
+        _ => VHDLStandard::VHDL1993,
+    }
+}
+
 impl std::default::Default for Symbols {
     fn default() -> Symbols {
         let keywords_init = [
@@ -1448,6 +1593,9 @@ impl std::default::Default for Symbols {
             ("process", Process),
             ("generate", Generate),
             ("postponed", Postponed),
+            ("guarded", Guarded),
+            ("disconnect", Disconnect),
+            ("group", Group),
             ("library", Library),
             ("label", Label),
             ("use", Use),
@@ -1507,6 +1655,8 @@ impl std::default::Default for Symbols {
             ("open", Open),
             ("alias", Alias),
             ("shared", Shared),
+            ("bus", Bus),
+            ("register", Register),
             ("others", Others),
             ("record", Record),
             ("type", Type),
@@ -1538,6 +1688,20 @@ impl std::default::Default for Symbols {
             ("rem", Rem),
             ("vunit", Vunit),
             ("parameter", Parameter),
+            ("view", View),
+            ("private", Private),
+            ("literal", Literal),
+            ("property", Property),
+            ("sequence", Sequence),
+            ("assume", Assume),
+            ("assume_guarantee", AssumeGuarantee),
+            ("cover", Cover),
+            ("fairness", Fairness),
+            ("restrict", Restrict),
+            ("restrict_guarantee", RestrictGuarantee),
+            ("strong", Strong),
+            ("vmode", Vmode),
+            ("vprop", Vprop),
         ];
 
         let attributes = [
@@ -1611,6 +1775,8 @@ impl std::default::Default for Symbols {
             ("simple_name", AttributeDesignator::SimpleName),
             ("instance_name", AttributeDesignator::InstanceName),
             ("path_name", AttributeDesignator::PathName),
+            ("converse", AttributeDesignator::Converse),
+            ("reflect", AttributeDesignator::Reflect),
         ];
 
         let symtab = SymbolTable::default();
@@ -1640,11 +1806,16 @@ impl std::default::Default for Symbols {
 
 pub struct Tokenizer<'a> {
     symbols: &'a Symbols,
+    standard: VHDLStandard,
     buffer: Latin1String,
     state: TokenState,
     pub source: &'a Source,
     reader: ContentReader<'a>,
     final_comments: Option<Vec<Comment>>,
+    // Set while tokenizing a PSL statement embedded in a `-- psl` comment
+    psl: bool,
+    // Nesting of PSL braces, semicolons within a sequence do not end the statement
+    psl_braces: usize,
 }
 
 impl<'a> Tokenizer<'a> {
@@ -1652,17 +1823,26 @@ impl<'a> Tokenizer<'a> {
         symbols: &'a Symbols,
         source: &'a Source,
         reader: ContentReader<'a>,
+        standard: VHDLStandard,
     ) -> Tokenizer<'a> {
         Tokenizer {
             symbols,
+            standard,
             state: TokenState::new(reader.state()),
             buffer: Latin1String::empty(),
             source,
             reader,
             final_comments: None,
+            psl: false,
+            psl_braces: 0,
         }
     }
 
+    /// The revision of the language being tokenized
+    pub fn standard(&self) -> VHDLStandard {
+        self.standard
+    }
+
     pub fn attribute(&self, sym: Symbol) -> AttributeDesignator {
         self.symbols
             .attributes
@@ -1698,6 +1878,7 @@ impl<'a> Tokenizer<'a> {
                         &mut self.buffer,
                         &mut self.reader,
                         self.symbols,
+                        self.standard,
                     )?
                 }
             }
@@ -1875,6 +2056,18 @@ impl<'a> Tokenizer<'a> {
                 self.reader.skip();
                 (RightSquare, Value::NoValue)
             }
+            b'{' => {
+                self.reader.skip();
+                (LeftCurly, Value::NoValue)
+            }
+            b'}' => {
+                self.reader.skip();
+                (RightCurly, Value::NoValue)
+            }
+            b'!' => {
+                self.reader.skip();
+                (Exclamation, Value::NoValue)
+            }
             b'\\' => {
                 self.reader.skip();
                 // LRM 15.4.3 Extended identifers
@@ -1895,11 +2088,21 @@ impl<'a> Tokenizer<'a> {
     }
 
     fn pop_raw(&mut self) -> Result<Option<Token>, TokenError> {
-        let leading_comments = get_leading_comments(&mut self.reader)?;
+        let leading_comments =
+            get_leading_comments(&mut self.reader, &mut self.psl, self.standard)?;
         self.state.start = self.reader.state();
 
         match self.parse_token()? {
             Some((kind, value)) => {
+                if self.psl {
+                    match kind {
+                        LeftCurly => self.psl_braces += 1,
+                        RightCurly => self.psl_braces = self.psl_braces.saturating_sub(1),
+                        // A PSL statement in a comment ends with its semicolon
+                        SemiColon if self.psl_braces == 0 => self.psl = false,
+                        _ => {}
+                    }
+                }
                 // Parsed a token.
                 let pos_start = self.state.start.pos();
                 let pos_end = self.reader.pos();
@@ -2006,6 +2209,36 @@ mod tests {
         assert_eq!(kinds_tokenize("not"), vec![Not]);
     }
 
+    #[test]
+    fn keywords_of_later_revisions_are_identifiers() {
+        let code = Code::new("context protected force").with_standard(VHDLStandard::VHDL1993);
+        assert_eq!(
+            kinds(&code.tokenize()),
+            vec![Identifier, Identifier, Identifier]
+        );
+
+        let code = Code::new("context protected force").with_standard(VHDLStandard::VHDL2002);
+        assert_eq!(
+            kinds(&code.tokenize()),
+            vec![Identifier, Protected, Identifier]
+        );
+
+        assert_eq!(
+            kinds_tokenize("context protected force"),
+            vec![Context, Protected, Force]
+        );
+
+        let code = Code::new("property sequence cover").with_standard(VHDLStandard::VHDL2002);
+        assert_eq!(
+            kinds(&code.tokenize()),
+            vec![Identifier, Identifier, Identifier]
+        );
+        assert_eq!(
+            kinds_tokenize("property sequence cover"),
+            vec![Property, Sequence, Cover]
+        );
+    }
+
     #[test]
     fn tokenize_newline() {
         assert_eq!(
@@ -2656,6 +2889,98 @@ my_other_ident",
         );
     }
 
+    #[test]
+    fn tokenize_psl_comments() {
+        assert_eq!(
+            kinds_tokenize(
+                "
+-- psl assert always
+--   {a} |-> !b;
+-- a plain comment
+1
+"
+            ),
+            vec![
+                Assert,
+                Identifier,
+                LeftCurly,
+                Identifier,
+                RightCurly,
+                Bar,
+                Minus,
+                GT,
+                Exclamation,
+                Identifier,
+                SemiColon,
+                AbstractLiteral
+            ]
+        );
+    }
+
+    #[test]
+    fn psl_comments_are_comments_before_vhdl_2008() {
+        let code = Code::new(
+            "
+-- psl assert always a;
+1
+",
+        )
+        .with_standard(VHDLStandard::VHDL2002);
+        assert_eq!(kinds(&code.tokenize()), vec![AbstractLiteral]);
+    }
+
+    #[test]
+    fn tokenize_labeled_psl_comments() {
+        assert_eq!(
+            kinds_tokenize(
+                "
+-- psl check_ack : cover {req; ack};
+1
+"
+            ),
+            vec![
+                Identifier,
+                Colon,
+                Cover,
+                LeftCurly,
+                Identifier,
+                SemiColon,
+                Identifier,
+                RightCurly,
+                SemiColon,
+                AbstractLiteral
+            ]
+        );
+    }
+
+    #[test]
+    fn comments_starting_with_the_word_psl_are_comments() {
+        assert_eq!(
+            kinds_tokenize(
+                "
+-- psl checks are done in the formal flow
+-- PSL: see the verification plan
+-- psl
+1
+"
+            ),
+            vec![AbstractLiteral]
+        );
+    }
+
+    #[test]
+    fn comments_starting_with_psl_prefix_are_comments() {
+        assert_eq!(
+            kinds_tokenize(
+                "
+-- pslx assert
+1
+"
+            ),
+            vec![AbstractLiteral]
+        );
+    }
+
     #[test]
     fn tokenize_ignores_multi_line_comments() {
         assert_eq!(
@@ -2761,7 +3086,7 @@ comment
 
     #[test]
     fn tokenize_illegal() {
-        let code = Code::new("begin!end");
+        let code = Code::new("begin$end");
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
@@ -2772,7 +3097,7 @@ comment
                     pos: code.s1("begin").pos(),
                     comments: None,
                 }),
-                Err(Diagnostic::error(&code.s1("!"), "Illegal token")),
+                Err(Diagnostic::error(code.s1("$"), "Illegal token")),
                 Ok(Token {
                     kind: End,
                     value: Value::NoValue,
@@ -6,10 +6,12 @@
 
 use std::cell::Cell;
 
+use super::conditional_analysis::{ConditionalAnalysis, ConditionalIdentifiers};
 use super::tokenizer::Kind::*;
 use super::tokenizer::*;
 use crate::ast::{AttributeDesignator, Ident, RangeAttribute, TypeAttribute};
 use crate::data::{DiagnosticHandler, DiagnosticResult, WithPos};
+use crate::standard::VHDLStandard;
 use crate::{Diagnostic, SrcPos};
 
 pub struct TokenStream<'a> {
@@ -23,52 +25,44 @@ pub struct TokenStream<'a> {
 }
 
 impl<'a> TokenStream<'a> {
-    /// Special handling for a tool directive of the form
-    /// ```vhdl
-    /// `identifier { any chars until newline }
-    /// ```
-    /// This needs special handling as the text that follows the identifier is arbitrary.
-    fn handle_tool_directive(
-        grave_accent: Token,
-        tokenizer: &mut Tokenizer,
+    /// Create a token stream using the default conditional analysis identifiers
+    #[cfg(test)]
+    pub fn new(
+        tokenizer: Tokenizer<'a>,
         diagnostics: &mut dyn DiagnosticHandler,
-    ) {
-        let start_pos = grave_accent.pos.clone();
-        match tokenizer.pop() {
-            Ok(Some(tok)) => {
-                if tok.kind != Identifier {
-                    diagnostics.error(tok, "Expecting identifier");
-                    let _ = tokenizer.text_until_newline(); // skip potentially invalid tokens
-                    return;
-                }
-            }
-            Err(err) => diagnostics.push(err),
-            Ok(None) => {
-                diagnostics.error(start_pos, "Expecting identifier");
-                return;
-            }
-        }
-        match tokenizer.text_until_newline() {
-            Ok(_) => {}
-            Err(err) => diagnostics.push(err),
-        }
+    ) -> TokenStream<'a> {
+        Self::new_with_identifiers(tokenizer, &ConditionalIdentifiers::default(), diagnostics)
     }
 
-    pub fn new(
+    /// Create a token stream where conditional analysis directives are evaluated
+    /// against the given identifiers. Tokens in inactive regions are skipped.
+    pub fn new_with_identifiers(
         mut tokenizer: Tokenizer<'a>,
+        identifiers: &ConditionalIdentifiers,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> TokenStream<'a> {
+        let mut conditional = ConditionalAnalysis::new(identifiers, tokenizer.standard());
         let mut tokens = Vec::new();
         loop {
             match tokenizer.pop() {
                 Ok(Some(token)) if token.kind == GraveAccent => {
-                    TokenStream::handle_tool_directive(token, &mut tokenizer, diagnostics)
+                    conditional.directive(token, &mut tokenizer, diagnostics)
+                }
+                Ok(Some(token)) => {
+                    if conditional.is_active() {
+                        tokens.push(token)
+                    }
                 }
-                Ok(Some(token)) => tokens.push(token),
                 Ok(None) => break,
-                Err(err) => diagnostics.push(err),
+                Err(err) => {
+                    if conditional.is_active() {
+                        diagnostics.push(err)
+                    }
+                }
             }
         }
+        conditional.finish(tokenizer.source, diagnostics);
+
         TokenStream {
             tokenizer,
             idx: Cell::new(0),
@@ -77,6 +71,11 @@ impl<'a> TokenStream<'a> {
         }
     }
 
+    /// The revision of the language being parsed
+    pub fn standard(&self) -> VHDLStandard {
+        self.tokenizer.standard()
+    }
+
     pub fn state(&self) -> usize {
         self.get_idx()
     }
@@ -301,51 +300,6 @@ impl<'a> TokenAccess for TokenStream<'a> {
     }
 }
 
-pub trait Recover<T> {
-    fn or_recover_until<F>(
-        self,
-        stream: &TokenStream,
-        msgs: &mut dyn DiagnosticHandler,
-        cond: F,
-    ) -> DiagnosticResult<T>
-    where
-        F: Fn(Kind) -> bool;
-
-    fn log(self, msgs: &mut dyn DiagnosticHandler);
-}
-
-impl<T> Recover<T> for DiagnosticResult<T> {
-    fn or_recover_until<F>(
-        self,
-        stream: &TokenStream,
-        msgs: &mut dyn DiagnosticHandler,
-        cond: F,
-    ) -> DiagnosticResult<T>
-    where
-        F: Fn(Kind) -> bool,
-    {
-        match self {
-            Ok(res) => Ok(res),
-            Err(ref original_err) => {
-                let res = stream.skip_until(cond);
-                match res {
-                    Ok(_) => self,
-                    Err(err) => {
-                        msgs.push(original_err.clone());
-                        Err(err)
-                    }
-                }
-            }
-        }
-    }
-
-    fn log(self, msgs: &mut dyn DiagnosticHandler) {
-        if let Err(err) = self {
-            msgs.push(err)
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -357,13 +311,23 @@ mod tests {
         ($code:ident, $stream:ident) => {
             let source = $code.source();
             let contents = source.contents();
-            let tokenizer = Tokenizer::new(&$code.symbols, source, ContentReader::new(&contents));
+            let tokenizer = Tokenizer::new(
+                &$code.symbols,
+                source,
+                ContentReader::new(&contents),
+                $code.standard(),
+            );
             let $stream = TokenStream::new(tokenizer, &mut NoDiagnostics);
         };
         ($code:ident, $stream:ident, $diagnostics:ident) => {
             let source = $code.source();
             let contents = source.contents();
-            let tokenizer = Tokenizer::new(&$code.symbols, source, ContentReader::new(&contents));
+            let tokenizer = Tokenizer::new(
+                &$code.symbols,
+                source,
+                ContentReader::new(&contents),
+                $code.standard(),
+            );
             let $stream = TokenStream::new(tokenizer, &mut $diagnostics);
         };
     }
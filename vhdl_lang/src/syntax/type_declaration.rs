@@ -5,17 +5,21 @@
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
 use super::common::check_end_identifier_mismatch;
+use super::common::check_standard;
 use super::common::ParseResult;
+use super::concurrent_statement::parse_map_aspect;
 use super::declarative_part::parse_declarative_part;
+use super::interface_declaration::parse_generic_interface_list;
 use super::names::parse_identifier_list;
 use super::range::{parse_array_index_constraint, parse_range};
 use super::subprogram::parse_subprogram_declaration;
 use super::subtype_indication::parse_subtype_indication;
-use super::tokens::{Kind::*, TokenSpan, TokenStream};
+use super::tokens::{Kind::*, TokenAccess, TokenSpan, TokenStream};
 use crate::ast::*;
 use crate::ast::{AbstractLiteral, Range};
 use crate::data::DiagnosticHandler;
 use crate::named_entity::Reference;
+use crate::standard::VHDLStandard;
 use crate::syntax::names::parse_type_mark;
 
 /// LRM 5.2.2 Enumeration types
@@ -111,6 +115,24 @@ pub fn parse_protected_type_declaration(
     stream: &TokenStream,
     diagnostics: &mut dyn DiagnosticHandler,
 ) -> ParseResult<(ProtectedTypeDeclaration, Option<Ident>)> {
+    let mut generic_clause = None;
+    let mut generic_map = None;
+    if let Some(generic) = stream.pop_if_kind(Generic) {
+        check_standard(
+            stream,
+            stream.get_pos(generic),
+            "Protected type generics",
+            VHDLStandard::VHDL2019,
+            diagnostics,
+        );
+        generic_clause = Some(parse_generic_interface_list(stream, diagnostics)?);
+        stream.expect_kind(SemiColon)?;
+        generic_map = parse_map_aspect(stream, Generic, diagnostics)?;
+        if generic_map.is_some() {
+            stream.expect_kind(SemiColon)?;
+        }
+    }
+
     let mut items = Vec::new();
 
     loop {
@@ -129,7 +151,14 @@ pub fn parse_protected_type_declaration(
     }
     stream.expect_kind(Protected)?;
     let end_ident = stream.pop_optional_ident();
-    Ok((ProtectedTypeDeclaration { items }, end_ident))
+    Ok((
+        ProtectedTypeDeclaration {
+            generic_clause,
+            generic_map,
+            items,
+        },
+        end_ident,
+    ))
 }
 
 /// LRM 5.2.4 Physical types
@@ -292,6 +321,7 @@ mod tests {
     use crate::HasTokenSpan;
 
     use crate::ast::{DiscreteRange, Ident};
+    use crate::data::Diagnostic;
     use crate::syntax::test::{token_to_string, Code};
     use crate::SrcPos;
 
@@ -657,7 +687,11 @@ end foo;",
         TypeDeclaration {
             span: token_span,
             ident: ident.into(),
-            def: TypeDefinition::Protected(ProtectedTypeDeclaration { items }),
+            def: TypeDefinition::Protected(ProtectedTypeDeclaration {
+                generic_clause: None,
+                generic_map: None,
+                items,
+            }),
             end_ident_pos,
         }
     }
@@ -718,6 +752,63 @@ end protected;
         )
     }
 
+    #[test]
+    fn test_protected_type_declaration_with_generics() {
+        let code = Code::new(
+            "\
+type foo is protected
+  generic (type T; constant width : natural);
+  generic map (T => bit, width => 8);
+  procedure proc;
+end protected;
+",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_type_declaration),
+            TypeDeclaration {
+                span: code.token_span(),
+                ident: code.s1("foo").decl_ident(),
+                def: TypeDefinition::Protected(ProtectedTypeDeclaration {
+                    generic_clause: Some(vec![
+                        code.s1("type T").generic(),
+                        code.s1("constant width : natural").generic()
+                    ]),
+                    generic_map: Some(
+                        code.s1("generic map (T => bit, width => 8)")
+                            .generic_map_aspect()
+                    ),
+                    items: vec![ProtectedTypeDeclarativeItem::Subprogram(
+                        code.s1("procedure proc;").subprogram_decl()
+                    )],
+                }),
+                end_ident_pos: None,
+            }
+        )
+    }
+
+    #[test]
+    fn protected_type_generics_require_vhdl_2019() {
+        let code = Code::new(
+            "\
+type foo is protected
+  generic (type T);
+end protected;
+",
+        )
+        .with_standard(VHDLStandard::VHDL2008);
+
+        let (_, diagnostics) = code.with_stream_diagnostics(parse_type_declaration);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                code.s1("generic"),
+                "Protected type generics requires VHDL-2019 or later, the library uses VHDL-2008"
+            )]
+        );
+    }
+
     #[test]
     fn test_protected_type_body() {
         let code = Code::new(
@@ -0,0 +1,157 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::common::{check_end_identifier_mismatch, ParseResult};
+use super::interface_declaration::parse_view_name;
+use super::names::parse_identifier_list;
+use super::subtype_indication::parse_subtype_indication;
+use super::tokens::{Kind::*, TokenSpan, TokenStream};
+use crate::ast::*;
+use crate::data::*;
+
+/// LRM 6.5.2 element_mode_indication
+fn parse_element_mode_indication(stream: &TokenStream) -> ParseResult<ElementModeIndication> {
+    let token = stream.peek_expect()?;
+    let mode = match token.kind {
+        In => Mode::In,
+        Out => Mode::Out,
+        InOut => Mode::InOut,
+        Buffer => Mode::Buffer,
+        Linkage => Mode::Linkage,
+        View => {
+            stream.skip();
+            let (kind, name) = parse_view_name(stream)?;
+            return Ok(ElementModeIndication::View(kind, name));
+        }
+        _ => return Err(token.kinds_error(&[In, Out, InOut, Buffer, Linkage, View])),
+    };
+    stream.skip();
+    Ok(ElementModeIndication::Simple(WithPos::new(
+        mode,
+        token.pos.clone(),
+    )))
+}
+
+/// LRM 6.5.2 mode_view_declaration
+pub fn parse_mode_view_declaration(
+    stream: &TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<ModeViewDeclaration> {
+    let start_token = stream.expect_kind(View)?;
+    let ident = WithDecl::new(stream.expect_ident()?);
+    stream.expect_kind(Of)?;
+    let typ = parse_subtype_indication(stream)?;
+    stream.expect_kind(Is)?;
+
+    let mut elements = Vec::new();
+    while !stream.next_kind_is(End) {
+        let names = parse_identifier_list(stream)?
+            .into_iter()
+            .map(WithRef::new)
+            .collect();
+        stream.expect_kind(Colon)?;
+        let mode = parse_element_mode_indication(stream)?;
+        stream.expect_kind(SemiColon)?;
+        elements.push(ModeViewElementDefinition { names, mode });
+    }
+
+    stream.expect_kind(End)?;
+    stream.expect_kind(View)?;
+    let end_ident = stream.pop_optional_ident();
+    let end_token = stream.expect_kind(SemiColon)?;
+
+    Ok(ModeViewDeclaration {
+        span: TokenSpan::new(start_token, end_token),
+        end_ident_pos: check_end_identifier_mismatch(&ident.tree, end_ident, diagnostics),
+        ident,
+        typ,
+        elements,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+    use crate::VHDLStandard;
+
+    #[test]
+    fn parses_mode_view_declaration() {
+        let code = Code::new(
+            "\
+view master of bus_t is
+  addr, wdata : out;
+  rdata : in;
+  sub : view sub_view;
+  arr : view (sub_view'converse);
+end view master;",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_mode_view_declaration),
+            ModeViewDeclaration {
+                span: code.token_span(),
+                ident: code.s1("master").decl_ident(),
+                typ: code.s1("bus_t").subtype_indication(),
+                elements: vec![
+                    ModeViewElementDefinition {
+                        names: vec![
+                            WithRef::new(code.s1("addr").ident()),
+                            WithRef::new(code.s1("wdata").ident())
+                        ],
+                        mode: ElementModeIndication::Simple(WithPos::new(
+                            Mode::Out,
+                            code.s1("out").pos()
+                        )),
+                    },
+                    ModeViewElementDefinition {
+                        names: vec![WithRef::new(code.s1("rdata").ident())],
+                        mode: ElementModeIndication::Simple(WithPos::new(
+                            Mode::In,
+                            code.s1("in;").s1("in").pos()
+                        )),
+                    },
+                    ModeViewElementDefinition {
+                        names: vec![WithRef::new(code.s1("sub ").s1("sub").ident())],
+                        mode: ElementModeIndication::View(
+                            ModeViewIndicationKind::Record,
+                            code.s1("sub_view").name()
+                        ),
+                    },
+                    ModeViewElementDefinition {
+                        names: vec![WithRef::new(code.s1("arr").ident())],
+                        mode: ElementModeIndication::View(
+                            ModeViewIndicationKind::Array,
+                            code.s1("sub_view'converse").name()
+                        ),
+                    },
+                ],
+                end_ident_pos: Some(code.s("master", 2).pos()),
+            }
+        );
+    }
+
+    #[test]
+    fn mode_view_declaration_end_identifier_mismatch() {
+        let code = Code::new(
+            "\
+view master of bus_t is
+  addr : out;
+end view slave;",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+
+        let (_, diagnostics) = code.with_stream_diagnostics(parse_mode_view_declaration);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                code.s1("slave"),
+                "End identifier mismatch, expected master"
+            )]
+        );
+    }
+}
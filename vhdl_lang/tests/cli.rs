@@ -0,0 +1,62 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn run_in(folder: &Path, args: &[&str]) -> Output {
+    std::fs::write(
+        folder.join("ent.vhd"),
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : missing;
+begin
+end architecture;
+",
+    )
+    .unwrap();
+    std::fs::write(
+        folder.join("vhdl_ls.toml"),
+        "
+[libraries]
+lib.files = ['ent.vhd']
+",
+    )
+    .unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_vhdl_lang"))
+        .current_dir(folder)
+        .args(["--config", "vhdl_ls.toml"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn perf_output_does_not_end_up_in_json_report() {
+    let folder = tempfile::tempdir().unwrap();
+    let output = run_in(folder.path(), &["--format", "json", "--perf"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect(&stdout);
+    assert_eq!(report[0]["message"], "No declaration of 'missing'");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Total time to run was"), "{stderr}");
+}
+
+#[test]
+fn perf_output_is_printed_with_text_diagnostics() {
+    let folder = tempfile::tempdir().unwrap();
+    let output = run_in(folder.path(), &["--perf"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("No declaration of 'missing'"), "{stdout}");
+    assert!(stdout.contains("Total time to run was"), "{stdout}");
+}
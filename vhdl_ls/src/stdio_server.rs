@@ -15,6 +15,7 @@ use serde_json::Value;
 use std::{cell::RefCell, rc::Rc};
 
 use crate::rpc_channel::{RpcChannel, SharedRpcChannel};
+use crate::vhdl_server::RegisterReportRequest;
 use crate::vhdl_server::VHDLServer;
 use crate::vhdl_server::VHDLServerSettings;
 
@@ -174,6 +175,14 @@ impl ConnectionRpcChannel {
             }
             Err(request) => request,
         };
+        let request = match extract::<request::CodeActionRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.code_action(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
         let request = match extract::<request::WorkspaceSymbolRequest>(request) {
             Ok((id, params)) => {
                 let result = server.workspace_symbol(&params);
@@ -222,6 +231,14 @@ impl ConnectionRpcChannel {
             }
             Err(request) => request,
         };
+        let request = match extract::<RegisterReportRequest>(request) {
+            Ok((id, params)) => {
+                let res = server.register_report(&params);
+                self.send_response(lsp_server::Response::new_ok(id, res));
+                return;
+            }
+            Err(request) => request,
+        };
 
         debug!("Unhandled request: {:?}", request);
         self.send_response(lsp_server::Response::new_err(
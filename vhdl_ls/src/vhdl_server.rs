@@ -7,6 +7,7 @@
 use lsp_types::*;
 
 use fnv::FnvHashMap;
+use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use vhdl_lang::ast::{Designator, ObjectClass};
@@ -15,8 +16,8 @@ use crate::rpc_channel::SharedRpcChannel;
 use std::io;
 use std::path::{Path, PathBuf};
 use vhdl_lang::{
-    kind_str, AnyEntKind, Concurrent, Config, Diagnostic, EntHierarchy, EntRef, EntityId, Message,
-    MessageHandler, Object, Overloaded, Project, Severity, Source, SrcPos, Type,
+    kind_str, AnyEntKind, Baseline, Concurrent, Config, Diagnostic, EntHierarchy, EntRef, EntityId,
+    Message, MessageHandler, Object, Overloaded, Project, Severity, Source, SrcPos, Type,
 };
 
 #[derive(Default, Clone)]
@@ -25,6 +26,30 @@ pub struct VHDLServerSettings {
     pub silent: bool,
 }
 
+/// Custom request that lists the registers and resets inferred for each architecture
+pub enum RegisterReportRequest {}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterReportParams {
+    /// Only report the architectures of this document
+    pub text_document: Option<TextDocumentIdentifier>,
+}
+
+impl request::Request for RegisterReportRequest {
+    type Params = RegisterReportParams;
+    type Result = serde_json::Value;
+    const METHOD: &'static str = "vhdl_ls/registerReport";
+}
+
+/// Data attached to a published diagnostic that the client passes back in code action requests
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticData {
+    /// Replacements offered as quick fixes
+    fixes: Vec<TextEdit>,
+}
+
 pub struct VHDLServer {
     rpc: SharedRpcChannel,
     settings: VHDLServerSettings,
@@ -34,6 +59,8 @@ pub struct VHDLServer {
     files_with_notifications: FnvHashMap<Url, ()>,
     init_params: Option<InitializeParams>,
     config_file: Option<PathBuf>,
+    // Diagnostics that are not published
+    baseline: Option<Baseline>,
 }
 
 impl VHDLServer {
@@ -46,6 +73,7 @@ impl VHDLServer {
             files_with_notifications: FnvHashMap::default(),
             init_params: None,
             config_file: None,
+            baseline: None,
         }
     }
 
@@ -59,6 +87,7 @@ impl VHDLServer {
             files_with_notifications: FnvHashMap::default(),
             init_params: None,
             config_file: None,
+            baseline: None,
         }
     }
 
@@ -107,9 +136,30 @@ impl VHDLServer {
         config
     }
 
+    /// Load the baseline file of the configuration, if any
+    fn load_baseline(&mut self, config: &Config) {
+        self.baseline =
+            config
+                .baseline()
+                .and_then(|file_name| match Baseline::read_file(file_name) {
+                    Ok(baseline) => {
+                        self.message(Message::log(format!(
+                            "Loaded baseline file: {}",
+                            file_name.display()
+                        )));
+                        Some(baseline)
+                    }
+                    Err(err) => {
+                        self.message(Message::error(err));
+                        None
+                    }
+                });
+    }
+
     pub fn initialize_request(&mut self, init_params: InitializeParams) -> InitializeResult {
         self.config_file = self.root_uri_config_file(&init_params);
         let config = self.load_config();
+        self.load_baseline(&config);
         self.project = Project::from_config(config, &mut self.message_filter());
         self.project.enable_unused_declaration_detection();
         self.init_params = Some(init_params);
@@ -129,6 +179,7 @@ impl VHDLServer {
                 work_done_progress_options: Default::default(),
             })),
             workspace_symbol_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             document_symbol_provider: Some(OneOf::Left(true)),
             completion_provider: Some(CompletionOptions {
                 resolve_provider: Some(true),
@@ -233,10 +284,13 @@ impl VHDLServer {
             self.project.update_source(&source);
             self.publish_diagnostics();
         } else {
-            self.message(Message::warning(format!(
-                "Opening file {} that is not part of the project",
-                file_name.to_string_lossy()
-            )));
+            // Files that match the library patterns of the config are mapped to their libraries
+            if !self.project.is_mapped_by_config(&file_name) {
+                self.message(Message::warning(format!(
+                    "Opening file {} that is not part of the project",
+                    file_name.to_string_lossy()
+                )));
+            }
             self.project
                 .update_source(&Source::inline(&file_name, text));
             self.publish_diagnostics();
@@ -254,6 +308,7 @@ impl VHDLServer {
                     "Configuration file has changed, reloading project...",
                 ));
                 let config = self.load_config();
+                self.load_baseline(&config);
 
                 self.project
                     .update_config(config, &mut self.message_filter());
@@ -413,6 +468,11 @@ impl VHDLServer {
             return;
         }
 
+        let diagnostics = match self.baseline {
+            Some(ref baseline) => baseline.filter(&self.project, diagnostics),
+            None => diagnostics,
+        };
+
         let supports_related_information = self.client_supports_related_information();
         let diagnostics = {
             if supports_related_information {
@@ -551,6 +611,41 @@ impl VHDLServer {
         })
     }
 
+    /// Offer the fixes attached to the diagnostics in the requested range as quick fixes
+    pub fn code_action(&self, params: &CodeActionParams) -> Option<CodeActionResponse> {
+        let mut actions = Vec::new();
+
+        for diagnostic in params.context.diagnostics.iter() {
+            let Some(data) = diagnostic
+                .data
+                .clone()
+                .and_then(|data| serde_json::from_value::<DiagnosticData>(data).ok())
+            else {
+                continue;
+            };
+
+            for (idx, fix) in data.fixes.into_iter().enumerate() {
+                let title = format!("Replace with '{}'", fix.new_text);
+                let mut changes: HashMap<Url, Vec<TextEdit>> = Default::default();
+                changes.insert(params.text_document.uri.clone(), vec![fix]);
+
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(changes),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(idx == 0),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Some(actions)
+    }
+
     pub fn workspace_symbol(
         &self,
         params: &WorkspaceSymbolParams,
@@ -592,6 +687,25 @@ impl VHDLServer {
         ))
     }
 
+    pub fn register_report(&self, params: &RegisterReportParams) -> serde_json::Value {
+        let file_name = params
+            .text_document
+            .as_ref()
+            .map(|doc| uri_to_file_name(&doc.uri));
+
+        serde_json::Value::Array(
+            self.project
+                .register_report()
+                .iter()
+                .filter(|arch| match file_name {
+                    Some(ref file_name) => arch.pos.file_name() == file_name,
+                    None => true,
+                })
+                .map(|arch| arch.to_json())
+                .collect(),
+        )
+    }
+
     pub fn document_symbol(&self, params: &DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
         let source = self
             .project
@@ -760,6 +874,9 @@ fn entity_kind_to_completion_kind(kind: &AnyEntKind) -> CompletionItemKind {
         AnyEntKind::DeferredConstant(_) => CompletionItemKind::CONSTANT,
         AnyEntKind::Library => CompletionItemKind::MODULE,
         AnyEntKind::Design(_) => CompletionItemKind::MODULE,
+        AnyEntKind::View(_) => CompletionItemKind::INTERFACE,
+        AnyEntKind::Psl(_) => CompletionItemKind::PROPERTY,
+        AnyEntKind::Group(_) => CompletionItemKind::STRUCT,
     }
 }
 
@@ -901,6 +1018,36 @@ fn to_lsp_diagnostic(diagnostic: Diagnostic) -> lsp_types::Diagnostic {
         None
     };
 
+    let tags = if !diagnostic.tags.is_empty() {
+        Some(
+            diagnostic
+                .tags
+                .iter()
+                .map(|tag| match tag {
+                    vhdl_lang::DiagnosticTag::Unnecessary => DiagnosticTag::UNNECESSARY,
+                })
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    // Fixes are only offered within the document of the diagnostic
+    let fixes: Vec<TextEdit> = diagnostic
+        .fixes
+        .iter()
+        .filter(|(pos, _)| pos.source == diagnostic.pos.source)
+        .map(|(pos, replacement)| TextEdit {
+            range: to_lsp_range(pos.range()),
+            new_text: replacement.clone(),
+        })
+        .collect();
+    let data = if !fixes.is_empty() {
+        serde_json::to_value(DiagnosticData { fixes }).ok()
+    } else {
+        None
+    };
+
     lsp_types::Diagnostic {
         range: to_lsp_range(diagnostic.pos.range()),
         severity: Some(severity),
@@ -908,6 +1055,8 @@ fn to_lsp_diagnostic(diagnostic: Diagnostic) -> lsp_types::Diagnostic {
         source: Some("vhdl ls".to_owned()),
         message: diagnostic.message,
         related_information,
+        tags,
+        data,
         ..Default::default()
     }
 }
@@ -982,14 +1131,18 @@ fn to_symbol_kind(kind: &AnyEntKind) -> SymbolKind {
         AnyEntKind::Library => SymbolKind::NAMESPACE,
         AnyEntKind::Design(d) => match d {
             vhdl_lang::Design::Entity(_, _) => SymbolKind::MODULE,
-            vhdl_lang::Design::Architecture(_) => SymbolKind::MODULE,
+            vhdl_lang::Design::Architecture(..) => SymbolKind::MODULE,
             vhdl_lang::Design::Configuration => SymbolKind::MODULE,
             vhdl_lang::Design::Package(_, _) => SymbolKind::PACKAGE,
             vhdl_lang::Design::PackageBody => SymbolKind::PACKAGE,
             vhdl_lang::Design::UninstPackage(_, _) => SymbolKind::PACKAGE,
             vhdl_lang::Design::PackageInstance(_) => SymbolKind::PACKAGE,
             vhdl_lang::Design::Context(_) => SymbolKind::NAMESPACE,
+            vhdl_lang::Design::VerificationUnit(_) => SymbolKind::MODULE,
         },
+        AnyEntKind::View(_) => SymbolKind::INTERFACE,
+        AnyEntKind::Psl(_) => SymbolKind::PROPERTY,
+        AnyEntKind::Group(_) => SymbolKind::STRUCT,
     }
 }
 
@@ -1204,23 +1357,28 @@ lib.files = [
 ",
         );
 
+        let range = Range {
+            start: lsp_types::Position {
+                line: 3,
+                character: "architecture rtl of ".len() as u32,
+            },
+            end: lsp_types::Position {
+                line: 3,
+                character: "architecture rtl of ent2".len() as u32,
+            },
+        };
         let publish_diagnostics = PublishDiagnosticsParams {
             uri: file_uri,
             diagnostics: vec![lsp_types::Diagnostic {
-                range: Range {
-                    start: lsp_types::Position {
-                        line: 3,
-                        character: "architecture rtl of ".len() as u32,
-                    },
-                    end: lsp_types::Position {
-                        line: 3,
-                        character: "architecture rtl of ent2".len() as u32,
-                    },
-                },
+                range,
                 code: None,
                 severity: Some(DiagnosticSeverity::ERROR),
                 source: Some("vhdl ls".to_owned()),
-                message: "No primary unit \'ent2\' within library \'lib\'".to_owned(),
+                message: "No primary unit \'ent2\' within library \'lib\', did you mean \'ent\'?"
+                    .to_owned(),
+                data: Some(serde_json::json!({
+                    "fixes": [TextEdit { range, new_text: "ent".to_owned() }]
+                })),
                 ..Default::default()
             }],
             version: None,
@@ -1232,6 +1390,55 @@ lib.files = [
         initialize_server(&mut server, root_uri);
     }
 
+    #[test]
+    fn initialize_with_baseline() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        write_file(
+            &root_uri,
+            "file.vhd",
+            "\
+entity ent is
+end entity;
+
+architecture rtl of ent2 is
+begin
+end;
+",
+        );
+        let baseline_uri = write_file(
+            &root_uri,
+            "baseline.json",
+            r#"{
+  "version": 1,
+  "diagnostics": [{
+    "rule": "analysis",
+    "unit": "lib.ent2(rtl)",
+    "message": "No primary unit 'ent2' within library 'lib'",
+    "count": 1
+  }]
+}"#,
+        );
+
+        let config_uri = write_config(
+            &root_uri,
+            "
+baseline = 'baseline.json'
+[libraries]
+lib.files = [
+  'file.vhd'
+]
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_message_contains(format!(
+            "Loaded baseline file: {}",
+            baseline_uri.to_file_path().unwrap().display()
+        ));
+        initialize_server(&mut server, root_uri);
+    }
+
     #[test]
     fn initialize_with_bad_config() {
         let (mock, mut server) = setup_server();
@@ -1487,4 +1694,63 @@ lib.files = [
             }],
         });
     }
+
+    #[test]
+    fn code_action_offers_diagnostic_fixes() {
+        let (_mock, server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_url = root_uri.join("pkg.vhd").unwrap();
+
+        let source = Source::inline(
+            &uri_to_file_name(&file_url),
+            "constant c : natural := countr;",
+        );
+        let pos = source.pos(
+            vhdl_lang::Position::new(0, 24),
+            vhdl_lang::Position::new(0, 30),
+        );
+        let mut diagnostic = Diagnostic::error(&pos, "No declaration of 'countr'");
+        diagnostic.add_fix(&pos, "counter");
+        let diagnostic = to_lsp_diagnostic(diagnostic);
+
+        let edit = TextEdit {
+            range: to_lsp_range(pos.range()),
+            new_text: "counter".to_owned(),
+        };
+        assert_eq!(
+            diagnostic.data,
+            Some(serde_json::json!({ "fixes": [edit.clone()] }))
+        );
+
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier {
+                uri: file_url.clone(),
+            },
+            range: diagnostic.range,
+            context: CodeActionContext {
+                diagnostics: vec![diagnostic.clone()],
+                only: None,
+                trigger_kind: None,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        let mut changes = HashMap::new();
+        changes.insert(file_url, vec![edit]);
+        assert_eq!(
+            server.code_action(&params),
+            Some(vec![CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Replace with 'counter'".to_owned(),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }),
+                is_preferred: Some(true),
+                ..Default::default()
+            })])
+        );
+    }
 }